@@ -0,0 +1,254 @@
+mod table;
+
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use sqlx::postgres::PgPoolOptions;
+use uuid::Uuid;
+
+use taxis::admin::{Admin, Catalogue, NewTaxi, RankData};
+
+use crate::table::Tabular;
+
+/// Operates a TXpress database, configured by `Rocket.toml` as the server is.
+#[derive(Parser)]
+#[command(name = "txpress-admin")]
+struct Cli {
+    /// How results are printed.
+    #[arg(long, value_enum, global = true, default_value_t = Format::Table)]
+    format: Format,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Table,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Manage the taxi ranks.
+    #[command(subcommand)]
+    Ranks(Ranks),
+    /// Manage the taxis.
+    #[command(subcommand)]
+    Taxis(Taxis),
+    /// Manage the owners of the taxis.
+    #[command(subcommand)]
+    Owners(Owners),
+    /// Manage the journeys.
+    #[command(subcommand)]
+    Journeys(Journeys),
+    /// Manage the fares between the ranks.
+    #[command(subcommand)]
+    Fares(Fares),
+    /// Write the ranks and the routes between them as JSON.
+    Export {
+        /// Where to write them, the standard output otherwise.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Add the ranks and the routes of an export missing from the database.
+    Import {
+        /// The export to read, `-` for the standard input.
+        file: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum Ranks {
+    /// Create a rank, unless its name is taken.
+    Create(NewRank),
+    /// List the ranks by name.
+    List {
+        /// Include the inactive ranks.
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Args)]
+struct NewRank {
+    name: String,
+    #[arg(long, requires = "longitude", allow_hyphen_values = true)]
+    latitude: Option<f64>,
+    #[arg(long, requires = "latitude", allow_hyphen_values = true)]
+    longitude: Option<f64>,
+    #[arg(long)]
+    address: Option<String>,
+    #[arg(long)]
+    city: Option<String>,
+    /// Distance around the rank it serves.
+    #[arg(long)]
+    radius_meters: Option<i32>,
+}
+
+#[derive(Subcommand)]
+enum Taxis {
+    /// Register a taxi with its owner.
+    Register(Registration),
+}
+
+#[derive(Args)]
+struct Registration {
+    number: String,
+    #[arg(long)]
+    brand: String,
+    #[arg(long)]
+    seats: u8,
+    /// Full name of the owner.
+    #[arg(long)]
+    owner: String,
+    #[arg(long, env = "TXPRESS_PASSWORD", hide_env_values = true)]
+    password: String,
+    #[arg(long)]
+    phone_number: Option<String>,
+    #[arg(long)]
+    email: Option<String>,
+    #[arg(long)]
+    language: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Owners {
+    /// Replace the password of the owner of a taxi.
+    ResetPassword {
+        /// Number of the taxi.
+        number: String,
+        #[arg(long, env = "TXPRESS_PASSWORD", hide_env_values = true)]
+        password: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum Journeys {
+    /// Close the journeys still open long after their departure.
+    CloseStale {
+        /// Hours since the departure.
+        #[arg(long, default_value_t = 24)]
+        hours: i64,
+    },
+}
+
+#[derive(Subcommand)]
+enum Fares {
+    /// Set the fare from a rank to another, replacing the one set before.
+    Set {
+        /// ID of the departure rank.
+        origin: Uuid,
+        /// ID of the arrival rank.
+        destination: Uuid,
+        /// Price of a seat, in minor units of the currency.
+        #[arg(long)]
+        amount: u64,
+        /// ISO 4217 code of the currency, XAF otherwise.
+        #[arg(long)]
+        currency: Option<String>,
+    },
+}
+
+fn print<T: Serialize + Tabular>(format: Format, value: &T) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        Format::Table => println!("{}", table::render(value)),
+    }
+    Ok(())
+}
+
+async fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let url = rocket::Config::figment().extract_inner::<String>("databases.txpress.url")?;
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&url)
+        .await?;
+    let admin = Admin::new(pool);
+
+    match cli.command {
+        Command::Ranks(Ranks::Create(rank)) => {
+            let rank = RankData {
+                name: rank.name,
+                latitude: rank.latitude,
+                longitude: rank.longitude,
+                address: rank.address,
+                city: rank.city,
+                radius_meters: rank.radius_meters,
+                active: true,
+            };
+            print(cli.format, &admin.add_ranks(&[rank]).await?)
+        }
+        Command::Ranks(Ranks::List { all }) => print(cli.format, &admin.list_ranks(all).await?),
+        Command::Taxis(Taxis::Register(registration)) => {
+            let taxi = NewTaxi {
+                number: registration.number,
+                brand: registration.brand,
+                number_of_seats: i32::from(registration.seats),
+                owner: registration.owner,
+                password: registration.password,
+                phone_number: registration.phone_number,
+                email: registration.email,
+                language: registration.language,
+            };
+            print(cli.format, &admin.register_taxi(taxi).await?)
+        }
+        Command::Owners(Owners::ResetPassword { number, password }) => {
+            admin.reset_password(&number, password).await?;
+            eprintln!("the password of `{number}` is reset");
+            Ok(())
+        }
+        Command::Journeys(Journeys::CloseStale { hours }) => {
+            let closed = admin
+                .close_stale_journeys(chrono::Duration::hours(hours))
+                .await?;
+            print(cli.format, &closed)
+        }
+        Command::Fares(Fares::Set {
+            origin,
+            destination,
+            amount,
+            currency,
+        }) => {
+            let fare = admin
+                .set_fare(origin, destination, amount, currency.as_deref())
+                .await?;
+            print(cli.format, &fare)
+        }
+        Command::Export { output } => {
+            let catalogue = serde_json::to_string_pretty(&admin.export().await?)?;
+            match output {
+                Some(path) => fs::write(path, catalogue)?,
+                None => println!("{catalogue}"),
+            }
+            Ok(())
+        }
+        Command::Import { file } => {
+            let content = match file.to_str() {
+                Some("-") => {
+                    let mut content = String::new();
+                    io::stdin().read_to_string(&mut content)?;
+                    content
+                }
+                _ => fs::read_to_string(file)?,
+            };
+            let catalogue = serde_json::from_str::<Catalogue>(&content)?;
+            print(cli.format, &admin.import(&catalogue).await?)
+        }
+    }
+}
+
+#[rocket::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use taxis::admin::{AddedRanks, ImportSummary, Rank, RankFare, RegisteredTaxi};
+use uuid::Uuid;
+
+/// Something printable as rows of columns.
+pub trait Tabular {
+    fn headers(&self) -> Vec<&'static str>;
+    fn rows(&self) -> Vec<Vec<String>>;
+}
+
+/// The rows under their headers, each column as wide as its widest cell.
+pub fn render(value: &impl Tabular) -> String {
+    let headers = value.headers();
+    let rows = value.rows();
+
+    let mut widths = headers.iter().map(|h| h.len()).collect::<Vec<_>>();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: Vec<String>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![line(headers.into_iter().map(String::from).collect())];
+    lines.extend(rows.into_iter().map(line));
+    lines.join("\n")
+}
+
+fn optional<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(T::to_string).unwrap_or_default()
+}
+
+impl Tabular for Vec<Rank> {
+    fn headers(&self) -> Vec<&'static str> {
+        vec!["ID", "NAME", "CITY", "LATITUDE", "LONGITUDE", "ACTIVE"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.iter()
+            .map(|r| {
+                vec![
+                    r.id.to_string(),
+                    r.name.clone(),
+                    optional(&r.city),
+                    optional(&r.latitude),
+                    optional(&r.longitude),
+                    r.active.to_string(),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl Tabular for AddedRanks {
+    fn headers(&self) -> Vec<&'static str> {
+        vec!["ID", "NAME", "STATUS"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        let created = self.created.iter().map(|r| (r, "created"));
+        let existing = self.existing.iter().map(|r| (r, "existing"));
+
+        created
+            .chain(existing)
+            .map(|(r, status)| vec![r.id.to_string(), r.name.clone(), status.into()])
+            .collect()
+    }
+}
+
+impl Tabular for RegisteredTaxi {
+    fn headers(&self) -> Vec<&'static str> {
+        vec!["ID", "NUMBER", "BRAND", "SEATS", "OWNER"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![
+            self.id.to_string(),
+            self.number.clone(),
+            self.brand.clone(),
+            self.number_of_seats.to_string(),
+            self.owner.clone(),
+        ]]
+    }
+}
+
+impl Tabular for RankFare {
+    fn headers(&self) -> Vec<&'static str> {
+        vec!["ORIGIN", "DESTINATION", "AMOUNT", "CURRENCY"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![
+            self.origin.to_string(),
+            self.destination.to_string(),
+            self.amount.to_string(),
+            self.currency.into(),
+        ]]
+    }
+}
+
+impl Tabular for Vec<Uuid> {
+    fn headers(&self) -> Vec<&'static str> {
+        vec!["ID"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.iter().map(|id| vec![id.to_string()]).collect()
+    }
+}
+
+impl Tabular for ImportSummary {
+    fn headers(&self) -> Vec<&'static str> {
+        vec!["", "CREATED", "EXISTING"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![
+            vec![
+                String::from("ranks"),
+                self.created_ranks.to_string(),
+                self.existing_ranks.to_string(),
+            ],
+            vec![
+                String::from("routes"),
+                self.created_routes.to_string(),
+                self.existing_routes.to_string(),
+            ],
+        ]
+    }
+}
@@ -8,15 +8,32 @@ pub fn derive_from_request(input: TokenStream) -> TokenStream {
     let DeriveInput { ref ident, .. } = parse_macro_input!(input);
 
     let output = quote! {
-        use rocket::request::{FromRequest, Outcome};
-        use rocket::Request;
-
         #[rocket::async_trait]
-        impl<'r> FromRequest<'r> for #ident {
+        impl<'r> rocket::request::FromRequest<'r> for #ident {
             type Error = ();
-            async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-                let conn = request.guard::<Connection<TXpressDB>>().await.unwrap();
-                Outcome::Success(#ident::new(conn))
+
+            async fn from_request(
+                request: &'r rocket::Request<'_>,
+            ) -> rocket::request::Outcome<Self, Self::Error> {
+                use rocket_db_pools::Database;
+
+                let db = match database::TXpressDB::fetch(request.rocket()) {
+                    Some(db) => db,
+                    None => {
+                        return rocket::request::Outcome::Failure((
+                            rocket::http::Status::InternalServerError,
+                            (),
+                        ))
+                    }
+                };
+
+                match db.acquire().await {
+                    Ok(conn) => rocket::request::Outcome::Success(#ident::new(conn)),
+                    Err(_) => rocket::request::Outcome::Failure((
+                        rocket::http::Status::ServiceUnavailable,
+                        (),
+                    )),
+                }
             }
         }
     };
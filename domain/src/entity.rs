@@ -4,7 +4,7 @@ use uuid::Uuid;
 pub use booking::Booking;
 pub use journey::Journey;
 pub use journey_criteria::JourneyCriteria;
-pub use price::Price;
+pub use price::{Currency, Price, UnknownCurrency};
 pub use search::Search;
 pub use search_criteria::SearchCriteria;
 pub use searched_taxi::SearchedTaxi;
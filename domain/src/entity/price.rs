@@ -1,8 +1,74 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Price {
     pub amount: u64,
     pub currency: Currency,
 }
 
+impl Price {
+    pub fn new(amount: u64, currency: Currency) -> Self {
+        Price { amount, currency }
+    }
+
+    pub fn checked_mul(&self, quantity: u64) -> Option<Price> {
+        self.amount
+            .checked_mul(quantity)
+            .map(|amount| Price::new(amount, self.currency))
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let exponent = self.currency.exponent();
+        if exponent == 0 {
+            return write!(f, "{} {}", self.amount, self.currency.code());
+        }
+
+        let factor = 10u64.pow(exponent);
+        write!(
+            f,
+            "{}.{:0width$} {}",
+            self.amount / factor,
+            self.amount % factor,
+            self.currency.code(),
+            width = exponent as usize
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum Currency {
+    #[default]
     XAF,
 }
+
+impl Currency {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::XAF => "XAF",
+        }
+    }
+
+    /// Number of decimal digits of the minor unit, as defined by ISO 4217.
+    pub fn exponent(&self) -> u32 {
+        match self {
+            Currency::XAF => 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UnknownCurrency(pub String);
+
+impl FromStr for Currency {
+    type Err = UnknownCurrency;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().as_str() {
+            "XAF" => Ok(Currency::XAF),
+            _ => Err(UnknownCurrency(s.into())),
+        }
+    }
+}
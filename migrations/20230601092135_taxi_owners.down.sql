@@ -1 +1 @@
-drop table taxi_owner;
+drop table taxi_owners;
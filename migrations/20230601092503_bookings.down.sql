@@ -1 +1 @@
-drop index idx_trips_owner;
+drop table bookings;
@@ -0,0 +1,6 @@
+alter table trips
+    drop constraint fare_amount_with_currency,
+    drop column fare_currency,
+    drop column fare_amount;
+
+drop table fares;
@@ -0,0 +1,13 @@
+create table fares (
+    origin uuid not null references taxi_ranks,
+    destination uuid not null references taxi_ranks,
+    amount bigint not null check (amount >= 0),
+    currency varchar(3) not null default 'XAF',
+    primary key (origin, destination),
+    constraint diff_fare_origin_and_destination check (origin != destination)
+);
+
+alter table trips
+    add column fare_amount bigint check (fare_amount >= 0),
+    add column fare_currency varchar(3),
+    add constraint fare_amount_with_currency check ((fare_amount is null) = (fare_currency is null));
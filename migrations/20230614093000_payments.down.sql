@@ -0,0 +1,13 @@
+drop table payments;
+
+drop index idx_bookings_holds;
+drop index idx_bookings_journey;
+
+alter table bookings
+    drop constraint booking_status,
+    drop column hold_expires_at,
+    drop column created_at,
+    drop column currency,
+    drop column amount,
+    drop column phone_number,
+    drop column status;
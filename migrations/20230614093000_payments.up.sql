@@ -0,0 +1,30 @@
+alter table bookings
+    add column status varchar(16) not null default 'confirmed',
+    add column phone_number varchar(20),
+    add column amount bigint check (amount >= 0),
+    add column currency varchar(3),
+    add column created_at timestamp with time zone not null default now(),
+    add column hold_expires_at timestamp with time zone,
+    add constraint booking_status check (status in ('pending', 'confirmed', 'expired', 'failed', 'cancelled'));
+
+alter table bookings alter column status set default 'pending';
+
+create index idx_bookings_journey on bookings using btree(journey_id, status);
+create index idx_bookings_holds on bookings using btree(hold_expires_at) where status = 'pending';
+
+create table payments (
+    id uuid primary key,
+    booking_id uuid not null references bookings on delete cascade,
+    provider varchar(32) not null,
+    reference varchar(128) not null,
+    amount bigint not null check (amount >= 0),
+    currency varchar(3) not null,
+    status varchar(16) not null default 'pending',
+    failure_reason text,
+    created_at timestamp with time zone not null default now(),
+    updated_at timestamp with time zone not null default now(),
+    constraint payment_status check (status in ('pending', 'succeeded', 'failed', 'refunded')),
+    unique (provider, reference)
+);
+
+create index idx_payments_booking on payments using btree(booking_id);
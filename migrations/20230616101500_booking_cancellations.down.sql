@@ -0,0 +1,13 @@
+drop index idx_payments_refunds;
+
+update payments set status = 'succeeded' where status = 'refund_pending';
+
+alter table payments
+    drop constraint payment_status,
+    add constraint payment_status check (status in ('pending', 'succeeded', 'failed', 'refunded')),
+    drop column refund_amount;
+
+alter table bookings
+    drop column refund_amount,
+    drop column cancellation_reason,
+    drop column cancelled_at;
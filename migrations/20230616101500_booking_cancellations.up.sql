@@ -0,0 +1,11 @@
+alter table bookings
+    add column cancelled_at timestamp with time zone,
+    add column cancellation_reason varchar(280),
+    add column refund_amount bigint check (refund_amount >= 0);
+
+alter table payments
+    add column refund_amount bigint check (refund_amount >= 0),
+    drop constraint payment_status,
+    add constraint payment_status check (status in ('pending', 'succeeded', 'failed', 'refund_pending', 'refunded'));
+
+create index idx_payments_refunds on payments using btree(updated_at) where status = 'refund_pending';
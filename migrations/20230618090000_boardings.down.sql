@@ -0,0 +1,2 @@
+alter table bookings
+    drop column boarded_at;
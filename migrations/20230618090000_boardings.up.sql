@@ -0,0 +1,2 @@
+alter table bookings
+    add column boarded_at timestamp with time zone;
@@ -0,0 +1,16 @@
+create table waitlist_entries (
+    id uuid primary key,
+    seq bigserial not null unique,
+    journey_id uuid not null references trips on delete cascade,
+    seats integer not null check (seats > 0),
+    phone_number varchar(20) not null,
+    status varchar(16) not null default 'waiting',
+    created_at timestamp with time zone not null default now(),
+    offered_at timestamp with time zone,
+    offer_expires_at timestamp with time zone,
+    booking_id uuid references bookings on delete set null,
+    constraint waitlist_status check (status in ('waiting', 'offered', 'claimed', 'expired', 'cancelled'))
+);
+
+create index idx_waitlist_queue on waitlist_entries using btree(journey_id, seq) where status = 'waiting';
+create index idx_waitlist_offers on waitlist_entries using btree(offer_expires_at) where status = 'offered';
@@ -0,0 +1,20 @@
+create table booking_seats (
+    journey_id uuid not null references trips on delete cascade,
+    seat_number integer not null check (seat_number > 0),
+    booking_id uuid not null references bookings on delete cascade,
+    primary key (journey_id, seat_number)
+);
+
+create index idx_booking_seats_booking on booking_seats using btree(booking_id);
+
+-- Bookings holding seats get them in the order they were made, the front seat last.
+insert into booking_seats (journey_id, seat_number, booking_id)
+select b.journey_id, case when s.n = t.number_of_seats then 1 else s.n + 1 end, b.id
+from (select id, journey_id, reserved_seats,
+             sum(reserved_seats) over (partition by journey_id order by created_at, id) - reserved_seats as taken
+      from bookings
+      where status in ('pending', 'confirmed')) b
+    inner join trips tp on tp.id = b.journey_id
+    inner join taxis t on t.number = tp.owner
+    cross join lateral generate_series(b.taken + 1, b.taken + b.reserved_seats) s(n)
+where s.n <= t.number_of_seats;
@@ -0,0 +1,11 @@
+drop table notification_attempts;
+drop table notifications;
+
+alter table bookings
+    drop column language,
+    drop column email;
+
+alter table taxi_owners
+    drop column language,
+    drop column email,
+    drop column phone_number;
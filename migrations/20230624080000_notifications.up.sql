@@ -0,0 +1,41 @@
+alter table taxi_owners
+    add column phone_number varchar(20),
+    add column email varchar(254),
+    add column language varchar(5) not null default 'fr';
+
+alter table bookings
+    add column email varchar(254),
+    add column language varchar(5) not null default 'fr';
+
+create table notifications (
+    id uuid primary key,
+    event varchar(32) not null,
+    channel varchar(8) not null,
+    recipient varchar(254) not null,
+    language varchar(5) not null,
+    subject text,
+    body text not null,
+    journey_id uuid,
+    booking_id uuid,
+    dedupe_key varchar(160) unique,
+    status varchar(16) not null default 'pending',
+    attempts integer not null default 0,
+    next_attempt_at timestamp with time zone not null default now(),
+    created_at timestamp with time zone not null default now(),
+    sent_at timestamp with time zone,
+    constraint notification_channel check (channel in ('sms', 'email')),
+    constraint notification_status check (status in ('pending', 'sent', 'failed'))
+);
+
+create index idx_notifications_due on notifications using btree(next_attempt_at) where status = 'pending';
+
+create table notification_attempts (
+    id bigserial primary key,
+    notification_id uuid not null references notifications on delete cascade,
+    transport varchar(16) not null,
+    attempted_at timestamp with time zone not null default now(),
+    succeeded boolean not null,
+    error text
+);
+
+create index idx_notification_attempts on notification_attempts using btree(notification_id);
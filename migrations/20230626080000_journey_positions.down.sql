@@ -0,0 +1,6 @@
+drop table journey_positions;
+
+alter table taxi_ranks
+    drop constraint taxi_ranks_coordinates,
+    drop column longitude,
+    drop column latitude;
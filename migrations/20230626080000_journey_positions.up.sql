@@ -0,0 +1,18 @@
+alter table taxi_ranks
+    add column latitude double precision check (latitude between -90 and 90),
+    add column longitude double precision check (longitude between -180 and 180),
+    add constraint taxi_ranks_coordinates check ((latitude is null) = (longitude is null));
+
+create table journey_positions (
+    id bigserial primary key,
+    journey_id uuid not null references trips on delete cascade,
+    latitude double precision not null check (latitude between -90 and 90),
+    longitude double precision not null check (longitude between -180 and 180),
+    speed_kmh double precision check (speed_kmh >= 0),
+    heading double precision check (heading >= 0 and heading < 360),
+    recorded_at timestamp with time zone not null,
+    received_at timestamp with time zone not null default now()
+);
+
+create index idx_journey_positions on journey_positions using btree(journey_id, recorded_at desc);
+create index idx_journey_positions_age on journey_positions using btree(recorded_at);
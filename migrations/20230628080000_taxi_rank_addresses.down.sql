@@ -0,0 +1,6 @@
+drop index idx_taxi_ranks_coordinates;
+
+alter table taxi_ranks
+    drop column radius_meters,
+    drop column city,
+    drop column address;
@@ -0,0 +1,6 @@
+alter table taxi_ranks
+    add column address text,
+    add column city varchar(120),
+    add column radius_meters integer check (radius_meters > 0);
+
+create index idx_taxi_ranks_coordinates on taxi_ranks using btree(latitude, longitude) where latitude is not null;
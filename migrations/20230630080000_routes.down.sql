@@ -0,0 +1,5 @@
+alter table trips
+    drop column duration_minutes,
+    drop column route_id;
+
+drop table routes;
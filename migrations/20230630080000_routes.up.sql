@@ -0,0 +1,15 @@
+create table routes (
+    id uuid primary key,
+    origin uuid not null references taxi_ranks,
+    destination uuid not null references taxi_ranks,
+    distance_km double precision not null check (distance_km > 0),
+    duration_minutes integer not null check (duration_minutes > 0),
+    active boolean not null default true,
+    created_at timestamp with time zone not null default now(),
+    constraint routes_distinct_ranks check (origin != destination),
+    constraint routes_unique_ranks unique (origin, destination)
+);
+
+alter table trips
+    add column route_id uuid references routes on delete set null,
+    add column duration_minutes integer check (duration_minutes > 0);
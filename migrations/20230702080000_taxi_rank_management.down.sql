@@ -0,0 +1,4 @@
+drop index idx_taxi_ranks_name;
+
+alter table taxi_ranks
+    drop column active;
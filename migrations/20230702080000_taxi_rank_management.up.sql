@@ -0,0 +1,9 @@
+alter table taxi_ranks
+    add column active boolean not null default true;
+
+-- Names become unique regardless of case, earlier duplicates are numbered.
+update taxi_ranks t set name = left(t.name, 74) || ' (' || d.n || ')'
+    from (select id, row_number() over (partition by lower(name) order by id) as n from taxi_ranks) d
+    where d.id = t.id and d.n > 1;
+
+create unique index idx_taxi_ranks_name on taxi_ranks using btree(lower(name));
@@ -0,0 +1,4 @@
+drop table rank_queue_entries;
+
+alter table trips
+    drop column boarding_at;
@@ -0,0 +1,20 @@
+alter table trips
+    add column boarding_at timestamp with time zone;
+
+create table rank_queue_entries (
+    id uuid primary key,
+    seq bigserial not null unique,
+    rank_id uuid not null references taxi_ranks on delete cascade,
+    taxi varchar(8) not null references taxis(number),
+    journey_id uuid not null references trips on delete cascade,
+    status varchar(16) not null default 'waiting',
+    checked_in_at timestamp with time zone not null default now(),
+    dispatched_at timestamp with time zone,
+    ended_at timestamp with time zone,
+    constraint queue_status check (status in ('waiting', 'dispatched', 'departed', 'left'))
+);
+
+-- A taxi stands in one queue at a time, and each rank loads one taxi at a time.
+create unique index idx_rank_queue_taxi on rank_queue_entries using btree(taxi) where status in ('waiting', 'dispatched');
+create unique index idx_rank_queue_head on rank_queue_entries using btree(rank_id) where status = 'dispatched';
+create index idx_rank_queue on rank_queue_entries using btree(rank_id, seq) where status = 'waiting';
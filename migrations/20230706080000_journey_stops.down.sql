@@ -0,0 +1,24 @@
+drop function journey_load(uuid, integer, integer);
+
+-- Seats resold on later legs cannot be told apart once the segments are gone.
+delete from booking_seats bs
+    where exists (
+        select 1 from booking_seats o
+        where o.journey_id = bs.journey_id and o.seat_number = bs.seat_number
+            and o.board_stop < bs.board_stop
+    );
+
+alter table booking_seats
+    drop constraint booking_seats_overlap,
+    add primary key (journey_id, seat_number),
+    drop column alight_stop,
+    drop column board_stop;
+
+alter table bookings
+    drop constraint bookings_segment,
+    drop column alight_stop,
+    drop column board_stop;
+
+drop table journey_stops;
+
+drop extension btree_gist;
@@ -0,0 +1,64 @@
+create extension btree_gist;
+
+-- Every rank a journey calls at, the origin being stop 0 and the destination the last one.
+create table journey_stops (
+    journey_id uuid not null references trips on delete cascade,
+    position integer not null check (position >= 0),
+    rank_id uuid not null references taxi_ranks,
+    scheduled_at timestamp with time zone,
+    primary key (journey_id, position),
+    constraint journey_stops_unique_rank unique (journey_id, rank_id)
+);
+
+create index idx_journey_stops_rank on journey_stops using btree(rank_id);
+
+insert into journey_stops (journey_id, position, rank_id, scheduled_at)
+    select id, 0, origin, departure_schedule from trips;
+insert into journey_stops (journey_id, position, rank_id, scheduled_at)
+    select id, 1, destination, departure_schedule + duration_minutes * interval '1 minute' from trips;
+
+alter table bookings
+    add column board_stop integer not null default 0,
+    add column alight_stop integer;
+
+update bookings set alight_stop = 1;
+
+alter table bookings
+    alter column alight_stop set not null,
+    add constraint bookings_segment check (board_stop >= 0 and alight_stop > board_stop);
+
+-- A seat is taken only over the legs of its booking, so it can be sold again past them.
+alter table booking_seats
+    add column board_stop integer,
+    add column alight_stop integer;
+
+update booking_seats bs set board_stop = b.board_stop, alight_stop = b.alight_stop
+    from bookings b
+    where b.id = bs.booking_id;
+
+alter table booking_seats
+    alter column board_stop set not null,
+    alter column alight_stop set not null,
+    drop constraint booking_seats_pkey,
+    add constraint booking_seats_overlap exclude using gist (
+        journey_id with =,
+        seat_number with =,
+        int4range(board_stop, alight_stop) with &&
+    );
+
+-- The most seats held at once on the legs between two stops, waitlist offers holding their
+-- seats over the whole journey.
+create function journey_load(journey uuid, from_stop integer, to_stop integer) returns integer as $$
+    select coalesce(max(l.seats), 0)::integer
+    from (
+        select (
+            select coalesce(sum(b.reserved_seats), 0) from bookings b
+            where b.journey_id = journey and b.status in ('pending', 'confirmed')
+                and b.board_stop <= leg and b.alight_stop > leg
+        ) + (
+            select coalesce(sum(w.seats), 0) from waitlist_entries w
+            where w.journey_id = journey and w.status = 'offered'
+        ) as seats
+        from generate_series(from_stop, to_stop - 1) leg
+    ) l;
+$$ language sql volatile;
@@ -0,0 +1,6 @@
+drop index idx_taxi_ranks_lower_city;
+drop index idx_taxi_ranks_name_prefix;
+drop index idx_taxi_ranks_city;
+drop index idx_taxi_ranks_name_keyset;
+drop index idx_trips_owner_seats;
+drop index idx_trips_owner_departure;
@@ -0,0 +1,10 @@
+-- Keysets of the paginated listings, the id breaking ties.
+create index idx_trips_owner_departure on trips using btree(owner, departure_schedule, id);
+create index idx_trips_owner_seats on trips using btree(owner, reserved_seats, id);
+
+create index idx_taxi_ranks_name_keyset on taxi_ranks using btree(name, id);
+create index idx_taxi_ranks_city on taxi_ranks using btree(coalesce(city, ''), id);
+
+-- Filters on the beginning of the name and on the city, regardless of case.
+create index idx_taxi_ranks_name_prefix on taxi_ranks using btree(lower(name) text_pattern_ops);
+create index idx_taxi_ranks_lower_city on taxi_ranks using btree(lower(city)) where city is not null;
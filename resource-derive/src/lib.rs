@@ -0,0 +1,141 @@
+use proc_macro::{self, TokenStream};
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, LitStr};
+
+/// Implements `hal::Resource` from the `#[link_to]` attributes of a struct.
+///
+/// Each `#[link_to(rel = "...", route = path(args), when = condition)]` adds the link to the route,
+/// built with `uri!` so that its parameters are checked, whenever the optional condition holds.
+/// `href = expression` can be given instead of a route for links computed otherwise. The
+/// expressions can refer to the resource as `self`, and the links are stored in the field
+/// marked `#[links]`.
+#[proc_macro_derive(Resource, attributes(link_to, links))]
+pub fn derive_resource(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(&input) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+struct Link {
+    rel: LitStr,
+    href: TokenStream2,
+    when: Option<Expr>,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "Resource can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "Resource requires named fields",
+        ));
+    };
+
+    let mut marked = fields
+        .named
+        .iter()
+        .filter(|f| f.attrs.iter().any(|a| a.path().is_ident("links")));
+    let links_field = match (marked.next(), marked.next()) {
+        (Some(field), None) => field.ident.clone().unwrap(),
+        (Some(_), Some(extra)) => {
+            return Err(syn::Error::new_spanned(
+                extra,
+                "only one field can be marked #[links]",
+            ))
+        }
+        (None, _) => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "a field must be marked #[links] to hold the links",
+            ))
+        }
+    };
+
+    let links = input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("link_to"))
+        .map(parse_link)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let inserts = links.iter().map(|Link { rel, href, when }| {
+        let insert = quote! {
+            links.insert(#rel, crate::Link { href: #href });
+        };
+        match when {
+            Some(when) => quote! { if #when { #insert } },
+            None => insert,
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics crate::hal::Resource for #ident #ty_generics #where_clause {
+            fn links(&self) -> ::std::collections::HashMap<&'static str, crate::Link> {
+                let mut links = ::std::collections::HashMap::new();
+                #(#inserts)*
+                links
+            }
+
+            fn set_links(&mut self, links: ::std::collections::HashMap<&'static str, crate::Link>) {
+                self.#links_field = links;
+            }
+        }
+    })
+}
+
+fn parse_link(attr: &syn::Attribute) -> syn::Result<Link> {
+    let mut rel = None;
+    let mut route = None;
+    let mut href = None;
+    let mut when = None;
+
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("rel") {
+            rel = Some(meta.value()?.parse::<LitStr>()?);
+        } else if meta.path.is_ident("route") {
+            route = Some(meta.value()?.parse::<Expr>()?);
+        } else if meta.path.is_ident("href") {
+            href = Some(meta.value()?.parse::<Expr>()?);
+        } else if meta.path.is_ident("when") {
+            when = Some(meta.value()?.parse::<Expr>()?);
+        } else {
+            return Err(meta.error("expected `rel`, `route`, `href` or `when`"));
+        }
+        Ok(())
+    })?;
+
+    let rel = rel.ok_or_else(|| syn::Error::new_spanned(attr, "a link needs a `rel`"))?;
+    let href = match (route, href) {
+        // `uri!` names the parameter types as the route declares them, which must then resolve
+        // where the link is derived: those outside the prelude are imported here rather than
+        // left to the imports of the caller.
+        (Some(route), None) => quote! {{
+            #[allow(unused_imports)]
+            use ::rocket::serde::uuid::Uuid;
+
+            ::rocket::uri!(crate::BASE_URL, #route).to_string()
+        }},
+        (None, Some(href)) => quote! { #href },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "a link needs either a `route` or an `href`",
+            ))
+        }
+    };
+
+    Ok(Link { rel, href, when })
+}
@@ -19,19 +19,25 @@ impl Fairing for CORS {
         }
     }
 
-    async fn on_response<'r>(&self, _request: &'r Request<'_>, response: &mut Response<'r>) {
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        // The probes of the orchestrator are not for browsers.
+        if request.uri().path().starts_with("/health/") {
+            return;
+        }
+
         response.set_header(Header::new(
             "Access-Control-Allow-Origin",
             "http://localhost:3000",
         ));
         response.set_header(Header::new(
             "Access-Control-Allow-Methods",
-            "POST, GET, PATCH, OPTIONS",
+            "POST, GET, PUT, PATCH, DELETE, OPTIONS",
         ));
         response.set_header(Header::new(
             "Access-Control-Allow-Headers",
-            "Content-Type, Cookie",
+            "Content-Type, Cookie, X-Request-Id",
         ));
+        response.set_header(Header::new("Access-Control-Expose-Headers", "X-Request-Id"));
         response.set_header(Header::new("Access-Control-Allow-Credentials", "true"));
     }
 }
@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use database::TXpressDB;
+use log::error;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::serde::Serialize;
+use rocket::{get, routes, Route};
+use sqlx::migrate::Migrate;
+use sqlx::PgConnection;
+
+use crate::migrations::MIGRATOR;
+
+const TARGET: &'static str = "HEALTH";
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(crate = "rocket::serde", rename_all = "lowercase")]
+enum Condition {
+    Up,
+    Down,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+struct Check {
+    status: Condition,
+    duration_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl Check {
+    fn new(started: Instant, result: Result<(), String>) -> Self {
+        let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+        match result {
+            Ok(()) => Check {
+                status: Condition::Up,
+                duration_ms,
+                error: None,
+            },
+            Err(error) => Check {
+                status: Condition::Down,
+                duration_ms,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+struct Health {
+    /// Up when every check is.
+    status: Condition,
+    checks: BTreeMap<&'static str, Check>,
+}
+
+impl Health {
+    fn respond(checks: BTreeMap<&'static str, Check>) -> (Status, Json<Health>) {
+        let (status, code) = match checks.values().all(|c| c.status == Condition::Up) {
+            true => (Condition::Up, Status::Ok),
+            false => (Condition::Down, Status::ServiceUnavailable),
+        };
+        (code, Json(Health { status, checks }))
+    }
+}
+
+/// The process serves requests.
+#[get("/health/live")]
+fn live() -> (Status, Json<Health>) {
+    let started = Instant::now();
+    Health::respond(BTreeMap::from([("process", Check::new(started, Ok(())))]))
+}
+
+/// The database answers, and every migration the server was built with is applied to it.
+#[get("/health/ready")]
+async fn ready(db: &TXpressDB) -> (Status, Json<Health>) {
+    let mut checks = BTreeMap::new();
+
+    let started = Instant::now();
+    let mut conn = match db.acquire().await {
+        Ok(conn) => conn,
+        Err(err) => {
+            error!(target: TARGET, "{err:?}");
+            checks.insert("database", Check::new(started, Err(err.to_string())));
+            let skipped = Err(String::from("no connection to the database"));
+            checks.insert("migrations", Check::new(Instant::now(), skipped));
+            return Health::respond(checks);
+        }
+    };
+    let result = sqlx::query("SELECT 1")
+        .execute(&mut *conn)
+        .await
+        .map(|_| ())
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            err.to_string()
+        });
+    checks.insert("database", Check::new(started, result));
+
+    let started = Instant::now();
+    let result = pending_migrations(&mut conn).await;
+    checks.insert("migrations", Check::new(started, result));
+
+    Health::respond(checks)
+}
+
+/// Whether no migration is left to apply.
+async fn pending_migrations(conn: &mut PgConnection) -> Result<(), String> {
+    let applied = conn.list_applied_migrations().await.map_err(|err| {
+        error!(target: TARGET, "{err:?}");
+        err.to_string()
+    })?;
+
+    let pending = MIGRATOR
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .filter(|m| !applied.iter().any(|a| a.version == m.version))
+        .count();
+    match pending {
+        0 => Ok(()),
+        n => Err(format!("{n} migrations pending")),
+    }
+}
+
+/// Probes of the orchestrator, outside of the API: neither versioned, signed in nor subject to
+/// CORS.
+pub fn routes() -> Vec<Route> {
+    routes![live, ready]
+}
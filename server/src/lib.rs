@@ -1,11 +1,29 @@
 mod cors;
+mod health;
+pub mod migrations;
+pub mod telemetry;
+mod versioning;
 
 use rocket::{routes, Build, Rocket};
 
 pub fn server() -> Rocket<Build> {
     rocket::build()
         .attach(database::init())
+        .attach(migrations::stage())
+        .attach(taxis::events())
+        .attach(taxis::payments())
+        .attach(taxis::tickets())
+        .attach(taxis::waitlist())
+        .attach(taxis::notifications())
+        .attach(taxis::tracking())
+        .attach(taxis::metrics())
+        .attach(taxis::request_ids())
         .attach(cors::CORS)
+        .attach(versioning::stage())
         .mount("/", routes![cors::for_cors])
-        .mount("/", taxis::routes())
+        .mount("/v1", telemetry::traced(taxis::routes()))
+        // The unversioned aliases of the first version, deprecated.
+        .mount("/", telemetry::traced(taxis::routes()))
+        .mount("/", taxis::docs())
+        .mount("/", health::routes())
 }
@@ -1,7 +1,39 @@
-use server::server;
+use std::process::ExitCode;
+
+use server::migrations::{self, Command};
+use server::{server, telemetry};
+
+const USAGE: &'static str = "usage: server [migrate up|down [version]|status]";
 
 #[rocket::main]
-async fn main() -> Result<(), rocket::Error> {
-    server().launch().await?;
-    Ok(())
+async fn main() -> ExitCode {
+    if let Err(err) = telemetry::init() {
+        eprintln!("{err}");
+        return ExitCode::FAILURE;
+    }
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    let result = match args.split_first() {
+        None => server().launch().await.map(|_| ()).map_err(Into::into),
+        Some((subcommand, args)) if subcommand == "migrate" => match Command::parse(args) {
+            Some(command) => migrations::run(command).await,
+            None => {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        },
+        Some(_) => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
 }
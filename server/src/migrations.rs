@@ -0,0 +1,144 @@
+use std::error::Error;
+
+use database::TXpressDB;
+use log::error;
+use rocket::fairing::AdHoc;
+use rocket::serde::Deserialize;
+use rocket_db_pools::Database;
+use sqlx::migrate::{Migrate, Migrator};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::PgPool;
+
+const TARGET: &'static str = "MIGRATIONS";
+
+/// Every migration of `migrations/`, embedded at build time.
+pub static MIGRATOR: Migrator = sqlx::migrate!("../migrations");
+
+#[derive(Deserialize, Default)]
+#[serde(crate = "rocket::serde")]
+struct MigrationsConfig {
+    /// Whether the pending migrations are applied when the server starts.
+    #[serde(default)]
+    on_startup: bool,
+}
+
+/// Applies the pending migrations before the server starts, when `migrations.on_startup` is set.
+/// It must be attached after the database.
+pub fn stage() -> AdHoc {
+    AdHoc::try_on_ignite("Migrations", |rocket| async {
+        let config = match rocket
+            .figment()
+            .extract_inner::<MigrationsConfig>("migrations")
+        {
+            Ok(config) => config,
+            Err(err) if err.missing() => MigrationsConfig::default(),
+            Err(err) => {
+                error!(target: TARGET, "invalid migrations configuration\n{err:?}");
+                return Err(rocket);
+            }
+        };
+
+        if !config.on_startup {
+            return Ok(rocket);
+        }
+
+        let Some(db) = TXpressDB::fetch(&rocket) else {
+            error!(target: TARGET, "the database is not attached");
+            return Err(rocket);
+        };
+
+        match MIGRATOR.run(&**db).await {
+            Ok(()) => Ok(rocket),
+            Err(err) => {
+                error!(target: TARGET, "{err:?}");
+                Err(rocket)
+            }
+        }
+    })
+}
+
+pub enum Command {
+    /// Applies every pending migration.
+    Up,
+    /// Reverts the migrations applied after the given version, or else the latest one.
+    Down(Option<i64>),
+    /// Lists the migrations, applied or pending.
+    Status,
+}
+
+impl Command {
+    pub fn parse(args: &[String]) -> Option<Self> {
+        match args {
+            [command] if command == "up" => Some(Command::Up),
+            [command] if command == "status" => Some(Command::Status),
+            [command] if command == "down" => Some(Command::Down(None)),
+            [command, version] if command == "down" => {
+                version.parse().ok().map(Some).map(Command::Down)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Runs the command against the database of the Rocket configuration.
+pub async fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    let url = rocket::Config::figment().extract_inner::<String>("databases.txpress.url")?;
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&url)
+        .await?;
+
+    match command {
+        Command::Up => {
+            MIGRATOR.run(&pool).await?;
+            println!("every migration is applied");
+        }
+        Command::Down(target) => {
+            let applied = applied(&pool).await?;
+            let target = match target {
+                Some(target) => target,
+                // The latest one is reverted by going back to the one before.
+                None => applied.iter().rev().nth(1).copied().unwrap_or(0),
+            };
+
+            MIGRATOR.undo(&pool, target).await?;
+            for version in applied.iter().filter(|v| **v > target).rev() {
+                println!("reverted {version}");
+            }
+        }
+        Command::Status => {
+            let applied = applied(&pool).await?;
+            for migration in MIGRATOR
+                .iter()
+                .filter(|m| !m.migration_type.is_down_migration())
+            {
+                let status = match applied.contains(&migration.version) {
+                    true => "applied",
+                    false => "pending",
+                };
+                println!(
+                    "{} {status:<8} {}",
+                    migration.version, migration.description
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The versions of the applied migrations, in order.
+async fn applied(pool: &PgPool) -> Result<Vec<i64>, Box<dyn Error>> {
+    let mut conn = pool.acquire().await?;
+    conn.ensure_migrations_table().await?;
+
+    let mut versions = conn
+        .list_applied_migrations()
+        .await?
+        .into_iter()
+        .map(|m| m.version)
+        .collect::<Vec<_>>();
+    versions.sort();
+
+    Ok(versions)
+}
@@ -0,0 +1,129 @@
+use std::time::Instant;
+
+use rocket::route::{Handler, Outcome};
+use rocket::serde::Deserialize;
+use rocket::{Config, Data, Request, Route};
+use tracing::level_filters::LevelFilter;
+use tracing::{field, info_span, Instrument};
+
+use taxis::RequestId;
+
+const TARGET: &'static str = "TELEMETRY";
+
+#[derive(Deserialize, Default)]
+#[serde(crate = "rocket::serde", rename_all = "lowercase")]
+enum LogFormat {
+    #[default]
+    Text,
+    /// One object per line, with the fields of the request span.
+    Json,
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde", default)]
+struct LoggingConfig {
+    format: LogFormat,
+    level: String,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        LoggingConfig {
+            format: LogFormat::default(),
+            level: String::from("info"),
+        }
+    }
+}
+
+/// Sends the logs, Rocket's and sqlx's included, to a `tracing` subscriber configured like the
+/// server. It must be installed before Rocket ignites, which would set up its own logger instead.
+pub fn init() -> Result<(), String> {
+    let config = match Config::figment().extract_inner::<LoggingConfig>("logging") {
+        Ok(config) => config,
+        Err(err) if err.missing() => LoggingConfig::default(),
+        Err(err) => return Err(format!("invalid logging configuration\n{err}")),
+    };
+
+    let level = config
+        .level
+        .parse::<LevelFilter>()
+        .map_err(|err| format!("invalid logging configuration\n{err}"))?;
+
+    let subscriber = tracing_subscriber::fmt().with_max_level(level);
+    match config.format {
+        LogFormat::Text => subscriber.try_init(),
+        LogFormat::Json => subscriber.json().with_current_span(true).try_init(),
+    }
+    .map_err(|err| err.to_string())
+}
+
+/// The routes, each handled within a span of its request.
+pub fn traced(routes: Vec<Route>) -> Vec<Route> {
+    routes
+        .into_iter()
+        .map(|mut route| {
+            route.handler = Box::new(Traced(route.handler));
+            route
+        })
+        .collect()
+}
+
+/// The taxi number of the request: the segment after `/taxis/`, the `<taxi>` segment, or else the
+/// `number` query parameter.
+fn taxi_number<'r>(request: &'r Request<'_>) -> Option<&'r str> {
+    let route = request.route()?;
+    let segments = route
+        .uri
+        .unmounted_origin
+        .path()
+        .segments()
+        .collect::<Vec<_>>();
+
+    let position = segments.iter().enumerate().position(|(i, segment)| {
+        let after_taxis = i > 0 && segments[i - 1] == "taxis";
+        segment.starts_with('<') && (*segment == "<taxi>" || after_taxis)
+    });
+    match position {
+        Some(i) => request.routed_segment(i),
+        None => request.query_value::<&str>("number").and_then(Result::ok),
+    }
+}
+
+#[derive(Clone)]
+struct Traced(Box<dyn Handler>);
+
+#[rocket::async_trait]
+impl Handler for Traced {
+    async fn handle<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        let span = info_span!(
+            "request",
+            request_id = %RequestId::of(request),
+            method = %request.method(),
+            route = field::Empty,
+            taxi = field::Empty,
+            status = field::Empty,
+            latency_ms = field::Empty,
+        );
+        if let Some(route) = request.route() {
+            span.record("route", field::display(&route.uri));
+        }
+        if let Some(number) = taxi_number(request) {
+            span.record("taxi", number);
+        }
+
+        let started = Instant::now();
+        let outcome = self.0.handle(request, data).instrument(span.clone()).await;
+
+        let status = match &outcome {
+            Outcome::Success(response) => response.status(),
+            Outcome::Failure(status) => *status,
+            // The next route traces the request in turn.
+            Outcome::Forward(_) => return outcome,
+        };
+        span.record("status", status.code);
+        span.record("latency_ms", started.elapsed().as_millis() as u64);
+        span.in_scope(|| tracing::info!(target: TARGET, "request handled"));
+
+        outcome
+    }
+}
@@ -0,0 +1,171 @@
+use std::io::Cursor;
+
+use log::error;
+use rocket::fairing::{AdHoc, Fairing, Info, Kind};
+use rocket::http::uri::Origin;
+use rocket::http::{ContentType, Header, Status};
+use rocket::serde::Deserialize;
+use rocket::{Data, Request, Response};
+
+use taxis::RequestId;
+
+const TARGET: &'static str = "VERSIONING";
+
+/// The version `taxis::routes()` are mounted under.
+pub const CURRENT: u32 = 1;
+
+/// Paths served the same whatever the version, and thus never deprecated.
+const UNVERSIONED: [&'static str; 4] = ["/docs", "/openapi.json", "/metrics", "/health/"];
+
+#[derive(Deserialize, Default)]
+#[serde(crate = "rocket::serde")]
+struct VersioningConfig {
+    /// When the unversioned aliases go away, as an HTTP date.
+    sunset: Option<String>,
+}
+
+/// How the version of a request was chosen.
+enum Version {
+    /// By the path, e.g. `/v1/taxi-ranks`.
+    Path,
+    /// By the `version` parameter of `Accept`, the path being rewritten accordingly.
+    Accepted,
+    /// Not at all, through a deprecated unversioned alias.
+    Alias,
+    Unsupported(Option<u32>),
+    Unversioned,
+}
+
+/// Routes requests to a version, either from their path or their `Accept` header, and flags the
+/// unversioned aliases as deprecated.
+pub struct Versioning {
+    sunset: Option<String>,
+}
+
+pub fn stage() -> AdHoc {
+    AdHoc::try_on_ignite("Versioning", |rocket| async {
+        let config = match rocket
+            .figment()
+            .extract_inner::<VersioningConfig>("versioning")
+        {
+            Ok(config) => config,
+            Err(err) if err.missing() => VersioningConfig::default(),
+            Err(err) => {
+                error!(target: TARGET, "invalid versioning configuration\n{err:?}");
+                return Err(rocket);
+            }
+        };
+
+        Ok(rocket.attach(Versioning {
+            sunset: config.sunset,
+        }))
+    })
+}
+
+/// The version in the first segment of the path, e.g. `1` for `/v1/taxi-ranks`.
+fn path_version(path: &str) -> Option<&str> {
+    path.trim_start_matches('/')
+        .split('/')
+        .next()
+        .and_then(|segment| segment.strip_prefix('v'))
+        .filter(|v| !v.is_empty() && v.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// The `version` parameter of the first media type of `Accept` with one, e.g.
+/// `application/hal+json; version=1`.
+fn accepted_version<'r>(request: &'r Request<'_>) -> Option<&'r str> {
+    request.accept()?.iter().find_map(|media_type| {
+        media_type
+            .params()
+            .find(|(name, _)| name.as_str().eq_ignore_ascii_case("version"))
+            .map(|(_, value)| value.trim_start_matches('v'))
+    })
+}
+
+fn rewrite(request: &mut Request<'_>, version: &str) {
+    let uri = match request.uri().query() {
+        Some(query) => format!("/v{version}{}?{query}", request.uri().path()),
+        None => format!("/v{version}{}", request.uri().path()),
+    };
+
+    match Origin::parse_owned(uri) {
+        Ok(uri) => request.set_uri(uri),
+        Err(err) => error!(target: TARGET, "{err:?}"),
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for Versioning {
+    fn info(&self) -> Info {
+        Info {
+            name: "Route requests to an API version",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
+        let path = request.uri().path().as_str();
+
+        let version = if UNVERSIONED.iter().any(|p| path.starts_with(p)) {
+            Version::Unversioned
+        } else if path_version(path).is_some() {
+            Version::Path
+        } else {
+            match accepted_version(request).map(|v| v.parse::<u32>()) {
+                None => Version::Alias,
+                Some(Ok(CURRENT)) => {
+                    rewrite(request, &CURRENT.to_string());
+                    Version::Accepted
+                }
+                // Left unrouted, so that nothing is performed for an unknown version.
+                Some(Ok(version)) => {
+                    rewrite(request, &version.to_string());
+                    Version::Unsupported(Some(version))
+                }
+                Some(Err(_)) => {
+                    rewrite(request, "0");
+                    Version::Unsupported(None)
+                }
+            }
+        };
+
+        request.local_cache(|| version);
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        match request.local_cache(|| Version::Unversioned) {
+            Version::Path | Version::Unversioned => {}
+            Version::Accepted => response.adjoin_header(Header::new("Vary", "Accept")),
+            // Unknown paths are not aliases of anything.
+            Version::Alias if request.route().is_none() => {}
+            Version::Alias => {
+                response.set_header(Header::new("Deprecation", "true"));
+                if let Some(sunset) = &self.sunset {
+                    response.set_header(Header::new("Sunset", sunset.clone()));
+                }
+                response.set_header(Header::new(
+                    "Link",
+                    format!(
+                        "</v{CURRENT}{}>; rel=\"successor-version\"",
+                        request.uri().path()
+                    ),
+                ));
+            }
+            Version::Unsupported(version) => {
+                let description = match version {
+                    Some(version) => format!("The version `{version}` is not supported"),
+                    None => String::from("The version is not a number"),
+                };
+                let body = format!(
+                    "{{\"error\":\"unsupported_version\",\"error_description\":\"{description}\",\
+                    \"request_id\":\"{}\"}}",
+                    RequestId::of(request)
+                );
+
+                response.set_status(Status::NotAcceptable);
+                response.set_header(ContentType::JSON);
+                response.set_sized_body(body.len(), Cursor::new(body));
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use server::migrations::MIGRATOR;
+use sqlx::PgPool;
+
+/// The tables of the public schema, but the bookkeeping of the migrations.
+async fn tables(pool: &PgPool) -> Vec<String> {
+    sqlx::query_scalar(
+        "SELECT tablename FROM pg_tables
+            WHERE schemaname = 'public' AND tablename <> '_sqlx_migrations'
+                ORDER BY tablename",
+    )
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+#[sqlx::test(migrations = false)]
+async fn applies_and_reverts_every_migration(pool: PgPool) {
+    MIGRATOR.run(&pool).await.unwrap();
+    assert!(!tables(&pool).await.is_empty());
+
+    MIGRATOR.undo(&pool, 0).await.unwrap();
+    let left = tables(&pool).await;
+    assert!(left.is_empty(), "tables left after reverting: {left:?}");
+
+    // Everything reverted can be applied again.
+    MIGRATOR.run(&pool).await.unwrap();
+}
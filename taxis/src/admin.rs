@@ -0,0 +1,469 @@
+//! Operations of the `txpress-admin` tooling, run straight against the database through the data
+//! providers of the API.
+
+use std::collections::HashMap;
+
+use chrono::{Duration, Utc};
+use domain::entity::{Currency, Price};
+use serde::{Deserialize, Serialize};
+use sqlx::pool::PoolConnection;
+use sqlx::{PgPool, Postgres};
+use uuid::Uuid;
+
+use crate::data::{
+    FareDataProvider, JourneyDataProvider, LoginDataProvider, QueueDataProvider,
+    RegistrationDataProvider, RouteDataProvider, StandDataProvider,
+};
+use crate::entity::{
+    Fare, NewRoute, NewStand, Owner, PageRequest, Sort, SortOrder, Stand, StandChanges,
+    StandFilter, StandSortField, Taxi,
+};
+use crate::geo::Coordinates;
+use crate::password;
+use crate::usecase::{
+    AddRoute, AddTaxi, AddTaxiRanks, CloseStaleJourneys, DepartRankQueue, DoesTaxiExists,
+    GetAllStand, GetRoutes, SetFare, SetOwnerPassword, UpdateStand,
+};
+
+const PAGE_SIZE: usize = 100;
+
+#[derive(thiserror::Error, Debug)]
+pub enum AdminError {
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+    #[error("unable to hash the password: {0}")]
+    Password(#[from] bcrypt::BcryptError),
+    #[error("the taxi `{0}` is already registered")]
+    TaxiExists(String),
+    #[error("the taxi `{0}` is unknown")]
+    UnknownTaxi(String),
+    #[error("the rank `{0}` is invalid: {1}")]
+    InvalidRank(String, &'static str),
+    #[error("the rank `{0}` is unknown")]
+    UnknownRank(String),
+    #[error("the rank `{0}` or `{1}` is unknown")]
+    UnknownRanks(Uuid, Uuid),
+    #[error("the fare is invalid: {0}")]
+    InvalidFare(String),
+}
+
+#[derive(Serialize)]
+pub struct Rank {
+    pub id: Uuid,
+    pub name: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub radius_meters: Option<i32>,
+    pub active: bool,
+}
+
+impl From<Stand> for Rank {
+    fn from(value: Stand) -> Self {
+        Rank {
+            id: value.id,
+            name: value.name,
+            latitude: value.latitude,
+            longitude: value.longitude,
+            address: value.address,
+            city: value.city,
+            radius_meters: value.radius_meters,
+            active: value.active,
+        }
+    }
+}
+
+/// A rank as created, or exchanged between databases.
+#[derive(Serialize, Deserialize)]
+pub struct RankData {
+    pub name: String,
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub city: Option<String>,
+    #[serde(default)]
+    pub radius_meters: Option<i32>,
+    #[serde(default = "active")]
+    pub active: bool,
+}
+
+fn active() -> bool {
+    true
+}
+
+impl TryFrom<&RankData> for NewStand {
+    type Error = AdminError;
+
+    fn try_from(value: &RankData) -> Result<Self, Self::Error> {
+        let name = value.name.trim();
+        if name.is_empty() {
+            return Err(AdminError::InvalidRank(
+                value.name.clone(),
+                "the name is empty",
+            ));
+        }
+
+        let coordinates = match (value.latitude, value.longitude) {
+            (None, None) => None,
+            (Some(latitude), Some(longitude)) => {
+                Some(Coordinates::new(latitude, longitude).ok_or_else(|| {
+                    AdminError::InvalidRank(name.into(), "the coordinates are out of range")
+                })?)
+            }
+            _ => {
+                return Err(AdminError::InvalidRank(
+                    name.into(),
+                    "the latitude and the longitude must be given together",
+                ))
+            }
+        };
+
+        Ok(NewStand {
+            name: name.into(),
+            coordinates,
+            address: value.address.clone(),
+            city: value.city.clone(),
+            radius_meters: value.radius_meters,
+        })
+    }
+}
+
+#[derive(Serialize)]
+pub struct AddedRanks {
+    pub created: Vec<Rank>,
+    /// Ranks whose name, regardless of case, was already taken.
+    pub existing: Vec<Rank>,
+}
+
+pub struct NewTaxi {
+    pub number: String,
+    pub brand: String,
+    pub number_of_seats: i32,
+    pub owner: String,
+    pub password: String,
+    pub phone_number: Option<String>,
+    pub email: Option<String>,
+    pub language: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct RegisteredTaxi {
+    pub id: Uuid,
+    pub number: String,
+    pub brand: String,
+    pub number_of_seats: i32,
+    pub owner: String,
+}
+
+#[derive(Serialize)]
+pub struct RankFare {
+    pub origin: Uuid,
+    pub destination: Uuid,
+    pub amount: u64,
+    pub currency: &'static str,
+}
+
+/// A route of the catalogue, between ranks given by name.
+#[derive(Serialize, Deserialize)]
+pub struct RouteData {
+    pub origin: String,
+    pub destination: String,
+    pub distance_km: f64,
+    pub duration_minutes: i32,
+    #[serde(default = "active")]
+    pub active: bool,
+}
+
+/// The reference data of a database: its ranks and the routes between them.
+#[derive(Serialize, Deserialize)]
+pub struct Catalogue {
+    pub ranks: Vec<RankData>,
+    #[serde(default)]
+    pub routes: Vec<RouteData>,
+}
+
+#[derive(Serialize, Default)]
+pub struct ImportSummary {
+    pub created_ranks: usize,
+    pub existing_ranks: usize,
+    pub created_routes: usize,
+    pub existing_routes: usize,
+}
+
+pub struct Admin {
+    pool: PgPool,
+}
+
+impl Admin {
+    pub fn new(pool: PgPool) -> Self {
+        Admin { pool }
+    }
+
+    async fn conn(&self) -> sqlx::Result<PoolConnection<Postgres>> {
+        self.pool.acquire().await
+    }
+
+    /// Creates the ranks whose name is not taken yet, inactive ones included.
+    pub async fn add_ranks(&self, ranks: &[RankData]) -> Result<AddedRanks, AdminError> {
+        let stands = ranks
+            .iter()
+            .map(NewStand::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut data_provider = StandDataProvider::new(self.conn().await?);
+        let added = data_provider.add_taxi_ranks(stands).await?;
+
+        let inactive = ranks
+            .iter()
+            .filter(|r| !r.active)
+            .map(|r| r.name.trim().to_lowercase())
+            .collect::<Vec<_>>();
+        let mut created = Vec::with_capacity(added.created.len());
+        for mut stand in added.created {
+            if inactive.contains(&stand.name.to_lowercase()) {
+                let changes = StandChanges {
+                    active: Some(false),
+                    ..Default::default()
+                };
+                data_provider.update_stand(&stand.id, &changes).await?;
+                stand.active = false;
+            }
+            created.push(Rank::from(stand));
+        }
+
+        Ok(AddedRanks {
+            created,
+            existing: added.existing.into_iter().map(Rank::from).collect(),
+        })
+    }
+
+    /// Every rank by name, the inactive ones only when asked for.
+    pub async fn list_ranks(&self, include_inactive: bool) -> Result<Vec<Rank>, AdminError> {
+        let filter = StandFilter {
+            name_prefix: None,
+            city: None,
+            include_inactive,
+            sort: Sort {
+                field: StandSortField::Name,
+                order: SortOrder::Ascending,
+            },
+        };
+        let mut page = PageRequest {
+            cursor: None,
+            limit: PAGE_SIZE,
+        };
+
+        let mut data_provider = StandDataProvider::new(self.conn().await?);
+        let mut ranks = vec![];
+        loop {
+            let listed = data_provider.get_all_stand(&filter, &page).await?;
+            ranks.extend(listed.items.into_iter().map(Rank::from));
+            match listed.next {
+                Some(next) => page.cursor = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(ranks)
+    }
+
+    pub async fn register_taxi(&self, new: NewTaxi) -> Result<RegisteredTaxi, AdminError> {
+        let mut data_provider = RegistrationDataProvider::new(self.conn().await?);
+        if data_provider.does_taxi_exists(&new.number).await? {
+            return Err(AdminError::TaxiExists(new.number));
+        }
+
+        let taxi = Taxi::new(&new.number, &new.brand, new.number_of_seats);
+        let mut owner = Owner::new(&new.owner, &password::hash(new.password).await?);
+        owner.phone_number = new.phone_number;
+        owner.email = new.email;
+        if let Some(language) = new.language {
+            owner.language = language;
+        }
+
+        data_provider.add_taxi(&taxi, &owner).await?;
+
+        Ok(RegisteredTaxi {
+            id: taxi.id,
+            number: taxi.number.to_lowercase(),
+            brand: taxi.brand,
+            number_of_seats: taxi.number_of_seats,
+            owner: owner.full_name,
+        })
+    }
+
+    pub async fn reset_password(&self, number: &str, password: String) -> Result<(), AdminError> {
+        let hash = password::hash(password).await?;
+
+        let mut data_provider = LoginDataProvider::new(self.conn().await?);
+        match data_provider.set_owner_password(number, &hash).await? {
+            true => Ok(()),
+            false => Err(AdminError::UnknownTaxi(number.into())),
+        }
+    }
+
+    /// Closes the journeys left open more than `age` after their departure, as the owners would,
+    /// their taxis leaving the rank queues.
+    pub async fn close_stale_journeys(&self, age: Duration) -> Result<Vec<Uuid>, AdminError> {
+        let before = Utc::now() - age;
+
+        let mut data_provider = JourneyDataProvider::new(self.conn().await?);
+        let closed = data_provider.close_stale_journeys(&before).await?;
+
+        let mut queue = QueueDataProvider::new(self.conn().await?);
+        for journey_id in &closed {
+            queue.depart_rank_queue(journey_id).await?;
+        }
+
+        Ok(closed)
+    }
+
+    /// Sets the fare between two ranks, in minor units of the currency, replacing the one set
+    /// before.
+    pub async fn set_fare(
+        &self,
+        origin: Uuid,
+        destination: Uuid,
+        amount: u64,
+        currency: Option<&str>,
+    ) -> Result<RankFare, AdminError> {
+        if origin == destination {
+            return Err(AdminError::InvalidFare(String::from(
+                "the departure and the arrival must be different",
+            )));
+        }
+        if i64::try_from(amount).is_err() {
+            return Err(AdminError::InvalidFare(format!(
+                "the amount `{amount}` is too large"
+            )));
+        }
+        let currency = match currency {
+            None => Currency::default(),
+            Some(code) => code.parse::<Currency>().map_err(|_| {
+                AdminError::InvalidFare(format!("the currency `{code}` is not supported"))
+            })?,
+        };
+        let price = Price::new(amount, currency);
+
+        let mut data_provider = FareDataProvider::new(self.conn().await?);
+        data_provider
+            .set_fare(&Fare::new(origin, destination, &price))
+            .await
+            .map_err(|err| match err {
+                sqlx::Error::Database(e) if e.code().as_deref() == Some("23503") => {
+                    AdminError::UnknownRanks(origin, destination)
+                }
+                _ => AdminError::Sqlx(err),
+            })?;
+
+        Ok(RankFare {
+            origin,
+            destination,
+            amount: price.amount,
+            currency: price.currency.code(),
+        })
+    }
+
+    pub async fn export(&self) -> Result<Catalogue, AdminError> {
+        let ranks = self.list_ranks(true).await?;
+        let names = ranks
+            .iter()
+            .map(|r| (r.id, r.name.clone()))
+            .collect::<HashMap<_, _>>();
+
+        let mut data_provider = RouteDataProvider::new(self.conn().await?);
+        let routes = data_provider
+            .get_routes(&Default::default())
+            .await?
+            .into_iter()
+            .filter_map(|route| {
+                Some(RouteData {
+                    origin: names.get(&route.origin)?.clone(),
+                    destination: names.get(&route.destination)?.clone(),
+                    distance_km: route.distance_km,
+                    duration_minutes: route.duration_minutes,
+                    active: route.active,
+                })
+            })
+            .collect();
+
+        Ok(Catalogue {
+            ranks: ranks
+                .into_iter()
+                .map(|r| RankData {
+                    name: r.name,
+                    latitude: r.latitude,
+                    longitude: r.longitude,
+                    address: r.address,
+                    city: r.city,
+                    radius_meters: r.radius_meters,
+                    active: r.active,
+                })
+                .collect(),
+            routes,
+        })
+    }
+
+    /// Adds what the database lacks of the catalogue; ranks and routes already there are left
+    /// untouched, so that an import can be run again.
+    pub async fn import(&self, catalogue: &Catalogue) -> Result<ImportSummary, AdminError> {
+        let known = |name: &str| {
+            catalogue
+                .ranks
+                .iter()
+                .any(|r| r.name.trim().to_lowercase() == name.trim().to_lowercase())
+        };
+        if let Some(route) = catalogue
+            .routes
+            .iter()
+            .find(|r| !known(&r.origin) || !known(&r.destination))
+        {
+            let name = match known(&route.origin) {
+                true => &route.destination,
+                false => &route.origin,
+            };
+            return Err(AdminError::UnknownRank(name.clone()));
+        }
+
+        let added = self.add_ranks(&catalogue.ranks).await?;
+        let mut summary = ImportSummary {
+            created_ranks: added.created.len(),
+            existing_ranks: added.existing.len(),
+            ..Default::default()
+        };
+
+        let ids = added
+            .created
+            .iter()
+            .chain(&added.existing)
+            .map(|r| (r.name.to_lowercase(), r.id))
+            .collect::<HashMap<_, _>>();
+        let id = |name: &String| {
+            ids.get(&name.trim().to_lowercase())
+                .copied()
+                .ok_or_else(|| AdminError::UnknownRank(name.clone()))
+        };
+
+        let mut data_provider = RouteDataProvider::new(self.conn().await?);
+        for route in &catalogue.routes {
+            let route = NewRoute {
+                origin: id(&route.origin)?,
+                destination: id(&route.destination)?,
+                distance_km: route.distance_km,
+                duration_minutes: route.duration_minutes,
+                active: route.active,
+            };
+            match data_provider.add_route(&route).await? {
+                Some(_) => summary.created_routes += 1,
+                None => summary.existing_routes += 1,
+            }
+        }
+
+        Ok(summary)
+    }
+}
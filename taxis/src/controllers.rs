@@ -1,21 +1,50 @@
 use rocket::Route;
 
 pub use errors::Error;
+pub use openapi::{docs, spec};
 
+mod boardings;
+mod bookings;
 mod errors;
+mod events;
+pub(crate) mod fares;
 mod journey;
 mod login;
+mod openapi;
+mod pagination;
+mod payments;
+mod positions;
+mod rank_queue;
 mod registration;
+mod root;
+mod search;
+mod seats;
 mod taxi_ranks;
+mod tickets;
+mod trip_routes;
 mod user;
+mod waitlist;
 
 pub fn routes() -> Vec<Route> {
     let mut routes = vec![];
+    routes.extend(root::routes());
     routes.extend(registration::routes());
     routes.extend(user::routes());
     routes.extend(login::routes());
     routes.extend(journey::routes());
     routes.extend(taxi_ranks::routes());
+    routes.extend(rank_queue::routes());
+    routes.extend(trip_routes::routes());
+    routes.extend(fares::routes());
+    routes.extend(search::routes());
+    routes.extend(seats::routes());
+    routes.extend(bookings::routes());
+    routes.extend(boardings::routes());
+    routes.extend(positions::routes());
+    routes.extend(payments::routes());
+    routes.extend(tickets::routes());
+    routes.extend(waitlist::routes());
+    routes.extend(events::routes());
 
     routes
 }
@@ -0,0 +1,173 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use log::error;
+use rocket::serde::json::Json;
+use rocket::{get, post, routes, Route, State};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::controllers::Error;
+use crate::data::BoardingDataProvider;
+use crate::entity::{self, Boarding};
+use crate::events::EventBus;
+use crate::guards::User;
+use crate::hal::Hal;
+use crate::ticket::{self, TicketError, TicketSigner};
+use crate::usecase::{BoardPassenger, GetJourneyManifest};
+
+const TARGET: &'static str = "BOARDINGS_CONTROLLER";
+
+#[derive(Deserialize, ToSchema)]
+pub(super) struct ScannedTicket {
+    ticket: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct BoardingResponse {
+    booking_id: Uuid,
+    seats: i32,
+    boarded_at: DateTime<Utc>,
+    boarded_seats: i64,
+    expected_seats: i64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct Passenger {
+    booking_id: Uuid,
+    seats: i32,
+    boarded_at: Option<DateTime<Utc>>,
+}
+
+impl From<entity::ManifestEntry> for Passenger {
+    fn from(value: entity::ManifestEntry) -> Self {
+        Passenger {
+            booking_id: value.booking_id,
+            seats: value.seats,
+            boarded_at: value.boarded_at,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct BoardingKeys {
+    journey_id: Uuid,
+    departure_schedule: DateTime<Utc>,
+    algorithm: &'static str,
+    key: String,
+    ticket_version: &'static str,
+    issued_at: DateTime<Utc>,
+    passengers: Vec<Passenger>,
+}
+
+#[utoipa::path(
+    tag = "boardings",
+    request_body = ScannedTicket,
+    responses(
+        (status = 200, description = "The passenger boarded", body = BoardingResponse, content_type = "application/hal+json"),
+        (status = 400, description = "The ticket is invalid", body = Failure),
+        (status = 404, description = "The booking is unknown", body = Failure),
+        (status = 409, description = "The ticket was already used", body = Failure),
+    )
+)]
+#[post("/taxis/<number>/journey/<journey_id>/boardings", data = "<data>")]
+async fn create(
+    number: &str,
+    journey_id: Uuid,
+    data: Json<ScannedTicket>,
+    user: Result<User, Error>,
+    signer: &State<TicketSigner>,
+    bus: &State<EventBus>,
+    mut data_provider: BoardingDataProvider,
+) -> Result<Hal<BoardingResponse>, Error> {
+    user?;
+
+    let claims = signer.verify(data.ticket.trim()).map_err(|err| match err {
+        TicketError::Malformed => Error::invalid_ticket(String::from("The ticket is malformed")),
+        TicketError::InvalidSignature => {
+            Error::invalid_ticket(String::from("The ticket signature is invalid"))
+        }
+    })?;
+
+    if claims.journey_id != journey_id {
+        return Err(Error::invalid_ticket(format!(
+            "The ticket does not belong to the journey `{journey_id}`"
+        )));
+    }
+
+    let boarding = data_provider
+        .board_passenger(number, &journey_id, &claims.booking_id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?;
+
+    match boarding {
+        Boarding::Boarded {
+            seats,
+            boarded_at,
+            boarded_seats,
+            expected_seats,
+        } => {
+            bus.journey_changed(&journey_id, "passenger_boarded");
+            Ok(Hal(BoardingResponse {
+                booking_id: claims.booking_id,
+                seats,
+                boarded_at,
+                boarded_seats,
+                expected_seats,
+            }))
+        }
+        Boarding::AlreadyBoarded { boarded_at } => {
+            Err(Error::ticket_already_used(&claims.booking_id, &boarded_at))
+        }
+        Boarding::NotConfirmed { status } => Err(Error::invalid_ticket(format!(
+            "The booking `{}` is {status}",
+            claims.booking_id
+        ))),
+        Boarding::Unknown => Err(Error::unknown_booking(&claims.booking_id)),
+    }
+}
+
+#[utoipa::path(
+    tag = "boardings",
+    responses(
+        (status = 200, description = "The key and the manifest to check tickets offline", body = BoardingKeys, content_type = "application/hal+json"),
+        (status = 404, description = "The journey is unknown", body = Failure),
+    )
+)]
+#[get("/taxis/<number>/journey/<journey_id>/boarding-keys")]
+async fn keys(
+    number: &str,
+    journey_id: Uuid,
+    user: Result<User, Error>,
+    signer: &State<TicketSigner>,
+    mut data_provider: BoardingDataProvider,
+) -> Result<Hal<BoardingKeys>, Error> {
+    user?;
+
+    let manifest = data_provider
+        .get_journey_manifest(number, &journey_id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .ok_or_else(|| Error::unknown_journey(&journey_id))?;
+
+    Ok(Hal(BoardingKeys {
+        journey_id: manifest.journey_id,
+        departure_schedule: manifest.departure_schedule,
+        algorithm: "Ed25519",
+        key: URL_SAFE_NO_PAD.encode(signer.journey_key(&manifest.journey_id)),
+        ticket_version: ticket::VERSION,
+        issued_at: Utc::now(),
+        passengers: manifest.entries.into_iter().map(Passenger::from).collect(),
+    }))
+}
+
+pub fn routes() -> Vec<Route> {
+    routes![create, keys]
+}
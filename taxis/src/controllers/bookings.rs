@@ -0,0 +1,516 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use log::error;
+use resource_derive::Resource;
+use rocket::http::Cookie;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post, routes, uri, Route, State};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::controllers::fares::Price;
+use crate::controllers::journey;
+use crate::controllers::positions;
+use crate::controllers::taxi_ranks;
+use crate::controllers::tickets;
+use crate::controllers::Error;
+use crate::data::{BookingDataProvider, FareDataProvider, JourneyDataProvider};
+use crate::entity::{self, BookingStatus, NewBooking, PaymentStatus};
+use crate::events::EventBus;
+use crate::guards::{Checkout, Passenger, Promotions};
+use crate::hal::{Hal, Resource};
+use crate::payment::{PaymentRequest, Payments};
+use crate::usecase::{
+    AddPayment, AttachPaymentReference, CancelBooking, GetBooking, GetBookingPayment, GetFare,
+    GetJourney, GetJourneyStops, PerformBooking, ReleaseBooking,
+};
+use crate::{Link, BASE_URL};
+
+const TARGET: &'static str = "BOOKINGS_CONTROLLER";
+
+#[derive(Deserialize, ToSchema, Validate)]
+pub(super) struct BookingRequest {
+    #[validate(range(min = 1))]
+    seats: u8,
+    #[validate(length(min = 8, max = 20))]
+    phone_number: String,
+    #[serde(default)]
+    seat_labels: Option<Vec<String>>,
+    #[validate(email)]
+    email: Option<String>,
+    #[validate(length(min = 2, max = 5))]
+    language: Option<String>,
+    #[validate(url)]
+    departure_id: Option<String>,
+    #[validate(url)]
+    arrival_id: Option<String>,
+}
+
+impl BookingRequest {
+    /// The part of the journey travelled, the whole of it unless both ends are given.
+    fn segment(
+        &self,
+        journey_id: &Uuid,
+        stops: &[entity::JourneyStop],
+    ) -> Result<entity::Segment, Error> {
+        let segment = match (&self.departure_id, &self.arrival_id) {
+            (None, None) => entity::Segment::whole(stops),
+            (Some(departure), Some(arrival)) => {
+                let (Some(origin), Some(destination)) = (rank_id(departure), rank_id(arrival))
+                else {
+                    return Err(Error::invalid_segment(journey_id));
+                };
+                entity::Segment::between(stops, &origin, &destination)
+            }
+            _ => {
+                return Err(Error::invalid_booking(String::from(
+                    "Both the departure and the arrival must be given",
+                )))
+            }
+        };
+
+        segment.ok_or_else(|| Error::invalid_segment(journey_id))
+    }
+
+    fn seat_numbers(&self) -> Result<Option<Vec<i32>>, Error> {
+        let Some(labels) = &self.seat_labels else {
+            return Ok(None);
+        };
+
+        let mut numbers = labels
+            .iter()
+            .map(|label| {
+                entity::seat_number(label)
+                    .ok_or_else(|| Error::invalid_booking(format!("Unknown seat `{label}`")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        numbers.sort();
+        numbers.dedup();
+
+        if numbers.len() != self.seats as usize {
+            return Err(Error::invalid_booking(format!(
+                "{} distinct seat(s) must be chosen",
+                self.seats
+            )));
+        }
+
+        Ok(Some(numbers))
+    }
+}
+
+#[derive(Deserialize, ToSchema, Validate)]
+pub(super) struct Cancellation {
+    #[validate(length(max = 280))]
+    reason: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct PaymentSummary {
+    provider: String,
+    reference: String,
+    status: String,
+}
+
+impl From<entity::Payment> for PaymentSummary {
+    fn from(value: entity::Payment) -> Self {
+        PaymentSummary {
+            provider: value.provider,
+            reference: value.reference,
+            status: value.status,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", route = show(&self.id))]
+#[link_to(rel = "journey", href = self.journey_id.clone())]
+#[link_to(rel = "cancel", route = cancel(&self.id), when = self.active)]
+#[link_to(rel = "ticket", route = tickets::show(&self.id), when = self.confirmed)]
+#[link_to(
+    rel = "position",
+    route = positions::passenger(&self.journey),
+    when = self.confirmed
+)]
+pub struct Booking {
+    id: Uuid,
+    journey_id: String,
+    departure_id: String,
+    arrival_id: String,
+    seats: i32,
+    seat_labels: Vec<String>,
+    status: String,
+    price: Option<Price>,
+    hold_expires_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cancellation_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    refund: Option<Price>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    boarded_at: Option<DateTime<Utc>>,
+    payment: Option<PaymentSummary>,
+    #[serde(skip)]
+    journey: Uuid,
+    /// Pending or confirmed, so that it can still be cancelled.
+    #[serde(skip)]
+    active: bool,
+    #[serde(skip)]
+    confirmed: bool,
+    #[serde(rename = "_links")]
+    #[links]
+    links: HashMap<&'static str, Link>,
+}
+
+impl Booking {
+    fn new(booking: entity::Booking, payment: Option<entity::Payment>) -> Self {
+        Booking {
+            price: booking.price().map(Price::from),
+            refund: booking.refund().map(Price::from),
+            active: booking.is_active(),
+            confirmed: booking.status == BookingStatus::Confirmed.as_str(),
+            id: booking.id,
+            journey_id: uri!(
+                BASE_URL,
+                journey::show(booking.taxi.as_str(), &booking.journey_id)
+            )
+            .to_string(),
+            departure_id: uri!(BASE_URL, taxi_ranks::show(booking.origin)).to_string(),
+            arrival_id: uri!(BASE_URL, taxi_ranks::show(booking.destination)).to_string(),
+            seats: booking.reserved_seats,
+            seat_labels: booking
+                .seat_numbers
+                .iter()
+                .map(|n| entity::seat_label(*n))
+                .collect(),
+            status: booking.status,
+            hold_expires_at: booking.hold_expires_at,
+            cancellation_reason: booking.cancellation_reason,
+            boarded_at: booking.boarded_at,
+            payment: payment.map(PaymentSummary::from),
+            journey: booking.journey_id,
+            links: HashMap::new(),
+        }
+        .linked()
+    }
+}
+
+#[utoipa::path(
+    tag = "bookings",
+    request_body = BookingRequest,
+    responses(
+        (status = 201, description = "The booking, held until it is paid", body = Booking, content_type = "application/hal+json"),
+        (status = 400, description = "The booking is invalid", body = Failure),
+        (status = 404, description = "The trip is unknown", body = Failure),
+        (status = 409, description = "The trip has not enough seats, or the chosen ones are taken", body = Failure),
+        (status = 502, description = "The payment provider is unavailable", body = Failure),
+    )
+)]
+#[post("/trips/<id>/bookings", data = "<data>")]
+async fn create(
+    id: Uuid,
+    data: Json<BookingRequest>,
+    checkout: Checkout<'_>,
+    mut journeys: JourneyDataProvider,
+    mut fares: FareDataProvider,
+    mut data_provider: BookingDataProvider,
+) -> Result<status::Created<Hal<Booking>>, Error> {
+    data.validate()
+        .map_err(|err| Error::invalid_booking(err.to_string()))?;
+
+    let journey = journeys
+        .get_journey(&id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .ok_or_else(|| Error::unknown_journey(&id))?;
+
+    if journey.closed {
+        return Err(Error::invalid_booking(format!(
+            "The journey `{id}` is closed"
+        )));
+    }
+
+    let stops = journeys.get_journey_stops(&id).await.map_err(|err| {
+        error!(target: TARGET, "{err:?}");
+        Error::server_error()
+    })?;
+    let segment = data.segment(&id, &stops)?;
+
+    // Only the whole journey is sold at its own fare, shorter segments at the fare grid's.
+    let unit = if segment.is_whole(&stops) {
+        journey.fare()
+    } else {
+        fares
+            .get_fare(&segment.origin, &segment.destination)
+            .await
+            .map_err(|err| {
+                error!(target: TARGET, "{err:?}");
+                Error::server_error()
+            })?
+            .and_then(|fare| fare.price())
+    };
+    let price = unit
+        .and_then(|unit| unit.checked_mul(data.seats as u64))
+        .ok_or_else(|| Error::invalid_booking(format!("The journey `{id}` has no fare")))?;
+    let seat_numbers = data.seat_numbers()?;
+
+    let booking = data_provider
+        .perform_booking(&NewBooking {
+            id: Uuid::new_v4(),
+            journey_id: id,
+            seats: data.seats as i32,
+            phone_number: data.phone_number.clone(),
+            email: data.email.clone(),
+            language: data.language.clone(),
+            price,
+            hold: checkout.payments.hold(),
+            seat_numbers,
+            segment,
+        })
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .ok_or_else(|| match &data.seat_labels {
+            Some(labels) => Error::seats_unavailable(&id, labels),
+            None => Error::not_enough_seats(&id),
+        })?;
+
+    checkout.bus.journey_changed(&id, "booking_created");
+    pay(
+        booking,
+        &data.phone_number,
+        price,
+        &checkout,
+        &mut data_provider,
+    )
+    .await
+}
+
+/// Starts the payment of a freshly held booking and hands the passenger its credentials.
+pub(super) async fn pay(
+    booking: entity::Booking,
+    phone_number: &str,
+    price: domain::entity::Price,
+    checkout: &Checkout<'_>,
+    data_provider: &mut BookingDataProvider,
+) -> Result<status::Created<Hal<Booking>>, Error> {
+    let Checkout {
+        payments,
+        bus,
+        cookies,
+    } = checkout;
+
+    // Recorded before it is requested, for the callback of the provider may come first.
+    let mut payment = entity::Payment::new(booking.id, payments.provider.name(), &price);
+    if let Err(err) = data_provider.add_payment(&payment).await {
+        error!(target: TARGET, "{err:?}");
+        abandon(&booking, bus, data_provider).await;
+        return Err(Error::server_error());
+    }
+
+    let intent = payments
+        .provider
+        .create_intent(&PaymentRequest {
+            booking_id: booking.id,
+            reference: payment.reference.clone(),
+            phone_number: phone_number.into(),
+            amount: price,
+        })
+        .await;
+
+    let intent = match intent {
+        Ok(intent) => intent,
+        Err(err) => {
+            error!(target: TARGET, "{err:?}");
+            abandon(&booking, bus, data_provider).await;
+            return Err(Error::payment_unavailable());
+        }
+    };
+
+    if intent.reference != payment.reference {
+        let attached = data_provider
+            .attach_payment_reference(&payment.id, &intent.reference)
+            .await;
+        if let Err(err) = attached {
+            error!(target: TARGET, "{err:?}");
+            abandon(&booking, bus, data_provider).await;
+            return Err(Error::server_error());
+        }
+        payment.reference = intent.reference;
+    }
+
+    let passenger = Passenger {
+        journey_id: booking.journey_id,
+    };
+    cookies.add_private(Cookie::new(
+        booking.id.to_string(),
+        serde_json::to_string(&passenger).unwrap(),
+    ));
+
+    let location = uri!(BASE_URL, show(&booking.id)).to_string();
+    Ok(status::Created::new(location).body(Hal(Booking::new(booking, Some(payment)))))
+}
+
+/// Gives up the hold of a booking whose payment could not be started.
+async fn abandon(
+    booking: &entity::Booking,
+    bus: &EventBus,
+    data_provider: &mut BookingDataProvider,
+) {
+    if let Err(err) = data_provider
+        .release_booking(&booking.id, BookingStatus::Failed)
+        .await
+    {
+        error!(target: TARGET, "{err:?}");
+    }
+    bus.journey_changed(&booking.journey_id, "booking_failed");
+}
+
+#[utoipa::path(
+    tag = "bookings",
+    responses(
+        (status = 200, description = "The booking", body = Booking, content_type = "application/hal+json"),
+        (status = 400, description = "The passenger is not identified", body = Failure),
+        (status = 404, description = "The booking is unknown", body = Failure),
+    )
+)]
+#[get("/bookings/<id>")]
+async fn show(
+    id: Uuid,
+    passenger: Result<Passenger, Error>,
+    mut data_provider: BookingDataProvider,
+) -> Result<Hal<Booking>, Error> {
+    passenger?;
+
+    let booking = data_provider
+        .get_booking(&id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .ok_or_else(|| Error::unknown_booking(&id))?;
+
+    let payment = data_provider
+        .get_booking_payment(&id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?;
+
+    Ok(Hal(Booking::new(booking, payment)))
+}
+
+#[utoipa::path(
+    tag = "bookings",
+    request_body(content = Option<Cancellation>, description = "Why the booking is cancelled"),
+    responses(
+        (status = 200, description = "The cancelled booking, with its refund", body = Booking, content_type = "application/hal+json"),
+        (status = 400, description = "The booking can no longer be cancelled", body = Failure),
+        (status = 404, description = "The booking is unknown", body = Failure),
+    )
+)]
+#[delete("/bookings/<id>", data = "<data>")]
+async fn cancel(
+    id: Uuid,
+    data: Option<Json<Cancellation>>,
+    passenger: Result<Passenger, Error>,
+    payments: &State<Payments>,
+    bus: &State<EventBus>,
+    mut promotions: Promotions<'_>,
+    mut data_provider: BookingDataProvider,
+) -> Result<Hal<Booking>, Error> {
+    passenger?;
+
+    let reason = match data {
+        None => None,
+        Some(c) => {
+            c.validate()
+                .map_err(|err| Error::invalid_booking(err.to_string()))?;
+            c.0.reason
+        }
+    };
+
+    let booking = data_provider
+        .get_booking(&id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .ok_or_else(|| Error::unknown_booking(&id))?;
+
+    if !booking.is_active() {
+        return Err(Error::invalid_booking(format!(
+            "The booking `{id}` is {}",
+            booking.status
+        )));
+    }
+
+    let payment = data_provider
+        .get_booking_payment(&id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .filter(|p| p.status == PaymentStatus::Succeeded.as_str());
+
+    let refund = payment.as_ref().and_then(|p| p.price()).map(|paid| {
+        payments
+            .config
+            .refunds
+            .refund(&paid, booking.departure_schedule, Utc::now())
+    });
+
+    let cancelled = data_provider
+        .cancel_booking(&id, reason.as_deref(), refund.as_ref())
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?;
+
+    let Some(journey_id) = cancelled else {
+        return Err(Error::invalid_booking(format!(
+            "The booking `{id}` can no longer be cancelled"
+        )));
+    };
+
+    bus.journey_changed(&journey_id, "booking_cancelled");
+    if promotions.promote(&journey_id).await {
+        bus.journey_changed(&journey_id, "waitlist_offer");
+    }
+
+    if let (Some(mut payment), Some(refund)) = (payment, refund) {
+        if refund.amount > 0 {
+            payment.refund_amount = Some(refund.amount as i64);
+            payments.refund(&mut data_provider, &payment).await;
+        }
+    }
+
+    show(
+        id,
+        Ok(Passenger {
+            journey_id: booking.journey_id,
+        }),
+        data_provider,
+    )
+    .await
+}
+
+pub fn routes() -> Vec<Route> {
+    routes![create, show, cancel]
+}
+
+fn rank_id(url: &str) -> Option<Uuid> {
+    url.rsplit('/').next().and_then(|id| id.parse().ok())
+}
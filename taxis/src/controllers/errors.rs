@@ -1,124 +1,413 @@
-use rocket::response::Responder;
+use chrono::{DateTime, Utc};
+use rocket::response::{self, Responder};
 use rocket::serde::json::Json;
+use rocket::Request;
 use serde::Serialize;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-#[derive(Serialize, Debug)]
+use crate::request_id::RequestId;
+
+#[derive(Serialize, ToSchema, Debug)]
 pub struct Failure {
     error: &'static str,
     error_description: String,
+    /// The `X-Request-Id` of the request, to find it in the logs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+}
+
+impl Failure {
+    fn new(error: &'static str, error_description: String) -> Self {
+        Failure {
+            error,
+            error_description,
+            request_id: None,
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Failure {
+    fn respond_to(mut self, request: &'r Request<'_>) -> response::Result<'static> {
+        self.request_id = Some(RequestId::of(request).to_string());
+        Json(self).respond_to(request)
+    }
 }
 
 #[derive(Responder, Debug)]
 #[response(content_type = "json")]
 pub enum Error {
     #[response(status = 400)]
-    TaxiExists(Json<Failure>),
+    TaxiExists(Failure),
     #[response(status = 500)]
-    ServerError(Json<Failure>),
+    ServerError(Failure),
+    #[response(status = 404)]
+    UnknownRegistrationId(Failure),
+    #[response(status = 400)]
+    InvalidNumber(Failure),
+    #[response(status = 400)]
+    InvalidPassword(Failure),
     #[response(status = 404)]
-    UnknownRegistrationId(Json<Failure>),
+    UnknownTaxi(Failure),
+    #[response(status = 404)]
+    UnknownStand(Failure),
+    #[response(status = 404)]
+    UnknownJourney(Failure),
     #[response(status = 400)]
-    InvalidNumber(Json<Failure>),
+    NoCredentials(Failure),
+    #[response(status = 409)]
+    UnableToCancelJourney(Failure),
     #[response(status = 400)]
-    InvalidPassword(Json<Failure>),
+    HasInProgressJourney(Failure),
+    #[response(status = 404)]
+    NoInProgressJourney(Failure),
     #[response(status = 404)]
-    UnknownTaxi(Json<Failure>),
+    UnknownFare(Failure),
+    #[response(status = 400)]
+    InvalidFare(Failure),
+    #[response(status = 400)]
+    InvalidBooking(Failure),
+    #[response(status = 409)]
+    NotEnoughSeats(Failure),
     #[response(status = 404)]
-    UnknownStand(Json<Failure>),
+    UnknownBooking(Failure),
+    #[response(status = 502)]
+    PaymentUnavailable(Failure),
     #[response(status = 404)]
-    UnknownJourney(Json<Failure>),
+    UnknownPaymentProvider(Failure),
+    #[response(status = 401)]
+    InvalidSignature(Failure),
     #[response(status = 400)]
-    NoCredentials(Json<Failure>),
+    InvalidCallback(Failure),
+    #[response(status = 409)]
+    TicketUnavailable(Failure),
     #[response(status = 400)]
-    UnableToCancelJourney(Json<Failure>),
+    InvalidTicket(Failure),
+    #[response(status = 409)]
+    TicketAlreadyUsed(Failure),
+    #[response(status = 404)]
+    UnknownWaitlistEntry(Failure),
+    #[response(status = 409)]
+    OfferUnavailable(Failure),
+    #[response(status = 409)]
+    SeatsUnavailable(Failure),
+    #[response(status = 409)]
+    JourneyNotFull(Failure),
+    #[response(status = 409)]
+    JourneyNotActive(Failure),
     #[response(status = 400)]
-    HasInProgressJourney(Json<Failure>),
+    InvalidPosition(Failure),
+    #[response(status = 400)]
+    InvalidStand(Failure),
+    #[response(status = 409)]
+    StandExists(Failure),
+    #[response(status = 409)]
+    StandInUse(Failure),
+    #[response(status = 409)]
+    StandInactive(Failure),
+    #[response(status = 409)]
+    AlreadyQueued(Failure),
+    #[response(status = 409)]
+    NoJourneyFromStand(Failure),
+    #[response(status = 404)]
+    UnknownQueueEntry(Failure),
     #[response(status = 404)]
-    NoInProgressJourney(Json<Failure>),
+    UnknownRoute(Failure),
+    #[response(status = 400)]
+    InvalidRoute(Failure),
+    #[response(status = 409)]
+    RouteExists(Failure),
+    #[response(status = 409)]
+    RouteUnavailable(Failure),
+    #[response(status = 400)]
+    InvalidStops(Failure),
+    #[response(status = 400)]
+    InvalidSegment(Failure),
+    #[response(status = 400)]
+    InvalidQuery(Failure),
 }
 
 impl Error {
     pub fn server_error() -> Self {
-        Error::ServerError(Json(Failure {
-            error: "server_error",
-            error_description: String::from("Oops! Something went wrong..."),
-        }))
+        Error::ServerError(Failure::new(
+            "server_error",
+            String::from("Oops! Something went wrong..."),
+        ))
     }
 
     pub fn taxi_exists(error_description: String) -> Self {
-        Error::TaxiExists(Json(Failure {
-            error: "invalid_request",
-            error_description,
-        }))
+        Error::TaxiExists(Failure::new("invalid_request", error_description))
     }
 
     pub fn unknown_registration_id(id: &Uuid) -> Self {
-        Error::UnknownRegistrationId(Json(Failure {
-            error: "not_found",
-            error_description: format!("The processing registration `{id}` does not exists`"),
-        }))
+        Error::UnknownRegistrationId(Failure::new(
+            "not_found",
+            format!("The processing registration `{id}` does not exists`"),
+        ))
     }
     pub fn invalid_number(num: &str) -> Self {
-        Error::InvalidNumber(Json(Failure {
-            error: "invalid_request",
-            error_description: format!("The taxi number `{num}` does not exits"),
-        }))
+        Error::InvalidNumber(Failure::new(
+            "invalid_request",
+            format!("The taxi number `{num}` does not exits"),
+        ))
     }
 
     pub fn invalid_password() -> Self {
-        Error::InvalidPassword(Json(Failure {
-            error: "invalid_request",
-            error_description: format!("The password is incorrect"),
-        }))
+        Error::InvalidPassword(Failure::new(
+            "invalid_request",
+            String::from("The password is incorrect"),
+        ))
     }
 
     pub fn no_credentials() -> Self {
-        Error::NoCredentials(Json(Failure {
-            error: "no_credentials",
-            error_description: format!("You must be authenticated"),
-        }))
+        Error::NoCredentials(Failure::new(
+            "no_credentials",
+            String::from("You must be authenticated"),
+        ))
     }
 
     pub fn unknown_taxi(num: &str) -> Self {
-        Error::UnknownTaxi(Json(Failure {
-            error: "not_found",
-            error_description: format!("The taxi `{num}` does not exists"),
-        }))
+        Error::UnknownTaxi(Failure::new(
+            "not_found",
+            format!("The taxi `{num}` does not exists"),
+        ))
     }
 
     pub fn unknown_stand(id: &Uuid) -> Self {
-        Error::UnknownStand(Json(Failure {
-            error: "not_found",
-            error_description: format!("The stand `{id}` does not exists"),
-        }))
+        Error::UnknownStand(Failure::new(
+            "not_found",
+            format!("The stand `{id}` does not exists"),
+        ))
     }
 
     pub fn unknown_journey(id: &Uuid) -> Self {
-        Error::UnknownJourney(Json(Failure {
-            error: "not_found",
-            error_description: format!("The journey `{id}` does not exists"),
-        }))
+        Error::UnknownJourney(Failure::new(
+            "not_found",
+            format!("The journey `{id}` does not exists"),
+        ))
     }
 
     pub fn unable_to_cancel_journey(id: &Uuid) -> Self {
-        Error::UnableToCancelJourney(Json(Failure {
-            error: "invalid_request",
-            error_description: format!("The journey `{id}` has booking(s)"),
-        }))
+        Error::UnableToCancelJourney(Failure::new(
+            "invalid_request",
+            format!("The journey `{id}` has booking(s)"),
+        ))
     }
 
     pub fn has_in_progress_journey() -> Self {
-        Error::HasInProgressJourney(Json(Failure {
-            error: "invalid_request",
-            error_description: format!("You already have a journey in progress"),
-        }))
+        Error::HasInProgressJourney(Failure::new(
+            "invalid_request",
+            String::from("You already have a journey in progress"),
+        ))
     }
 
     pub fn no_in_progress_journey() -> Self {
-        Error::NoInProgressJourney(Json(Failure {
-            error: "invalid_request",
-            error_description: format!("You have no journey in progress"),
-        }))
+        Error::NoInProgressJourney(Failure::new(
+            "invalid_request",
+            String::from("You have no journey in progress"),
+        ))
+    }
+
+    pub fn unknown_fare(origin: &Uuid, destination: &Uuid) -> Self {
+        Error::UnknownFare(Failure::new(
+            "not_found",
+            format!("No fare is defined from the stand `{origin}` to the stand `{destination}`"),
+        ))
+    }
+
+    pub fn invalid_fare(error_description: String) -> Self {
+        Error::InvalidFare(Failure::new("invalid_request", error_description))
+    }
+
+    pub fn invalid_booking(error_description: String) -> Self {
+        Error::InvalidBooking(Failure::new("invalid_request", error_description))
+    }
+
+    pub fn not_enough_seats(journey_id: &Uuid) -> Self {
+        Error::NotEnoughSeats(Failure::new(
+            "not_enough_seats",
+            format!("The journey `{journey_id}` has not enough free seats"),
+        ))
+    }
+
+    pub fn unknown_booking(id: &Uuid) -> Self {
+        Error::UnknownBooking(Failure::new(
+            "not_found",
+            format!("The booking `{id}` does not exists"),
+        ))
+    }
+
+    pub fn payment_unavailable() -> Self {
+        Error::PaymentUnavailable(Failure::new(
+            "payment_unavailable",
+            String::from("The payment could not be initiated, try again later"),
+        ))
+    }
+
+    pub fn unknown_payment_provider(name: &str) -> Self {
+        Error::UnknownPaymentProvider(Failure::new(
+            "not_found",
+            format!("The payment provider `{name}` does not exists"),
+        ))
+    }
+
+    pub fn invalid_signature() -> Self {
+        Error::InvalidSignature(Failure::new(
+            "invalid_signature",
+            String::from("The signature is missing or incorrect"),
+        ))
+    }
+
+    pub fn invalid_callback() -> Self {
+        Error::InvalidCallback(Failure::new(
+            "invalid_request",
+            String::from("The callback payload is malformed"),
+        ))
+    }
+
+    pub fn ticket_unavailable(id: &Uuid) -> Self {
+        Error::TicketUnavailable(Failure::new(
+            "ticket_unavailable",
+            format!("The booking `{id}` is not confirmed"),
+        ))
+    }
+
+    pub fn invalid_ticket(error_description: String) -> Self {
+        Error::InvalidTicket(Failure::new("invalid_ticket", error_description))
+    }
+
+    pub fn ticket_already_used(id: &Uuid, boarded_at: &DateTime<Utc>) -> Self {
+        Error::TicketAlreadyUsed(Failure::new(
+            "ticket_already_used",
+            format!("The ticket of the booking `{id}` was used at {boarded_at}"),
+        ))
+    }
+
+    pub fn unknown_waitlist_entry(id: &Uuid) -> Self {
+        Error::UnknownWaitlistEntry(Failure::new(
+            "not_found",
+            format!("The waitlist entry `{id}` does not exists"),
+        ))
+    }
+
+    pub fn offer_unavailable(id: &Uuid) -> Self {
+        Error::OfferUnavailable(Failure::new(
+            "offer_unavailable",
+            format!("The waitlist entry `{id}` has no open offer"),
+        ))
+    }
+
+    pub fn seats_unavailable(journey_id: &Uuid, seats: &[String]) -> Self {
+        Error::SeatsUnavailable(Failure::new(
+            "seats_unavailable",
+            format!(
+                "The seat(s) {} of the journey `{journey_id}` are not available",
+                seats.join(", ")
+            ),
+        ))
+    }
+
+    pub fn journey_not_full(id: &Uuid) -> Self {
+        Error::JourneyNotFull(Failure::new(
+            "journey_not_full",
+            format!("The journey `{id}` still has the seats free, book them instead"),
+        ))
+    }
+
+    pub fn journey_not_active(id: &Uuid) -> Self {
+        Error::JourneyNotActive(Failure::new(
+            "journey_not_active",
+            format!("The journey `{id}` is not under way"),
+        ))
+    }
+
+    pub fn invalid_position(error_description: String) -> Self {
+        Error::InvalidPosition(Failure::new("invalid_position", error_description))
+    }
+
+    pub fn invalid_stand(error_description: String) -> Self {
+        Error::InvalidStand(Failure::new("invalid_request", error_description))
+    }
+
+    pub fn unknown_route(id: &Uuid) -> Self {
+        Error::UnknownRoute(Failure::new(
+            "not_found",
+            format!("The route `{id}` does not exists"),
+        ))
+    }
+
+    pub fn invalid_route(error_description: String) -> Self {
+        Error::InvalidRoute(Failure::new("invalid_request", error_description))
+    }
+
+    pub fn route_exists(origin: &Uuid, destination: &Uuid) -> Self {
+        Error::RouteExists(Failure::new(
+            "route_exists",
+            format!("A route from `{origin}` to `{destination}` already exists"),
+        ))
+    }
+
+    pub fn route_unavailable(id: &Uuid) -> Self {
+        Error::RouteUnavailable(Failure::new(
+            "route_unavailable",
+            format!("The route `{id}` is not served anymore"),
+        ))
+    }
+
+    pub fn stand_exists(name: &str) -> Self {
+        Error::StandExists(Failure::new(
+            "stand_exists",
+            format!("A stand named `{name}` already exists"),
+        ))
+    }
+
+    pub fn stand_in_use(id: &Uuid) -> Self {
+        Error::StandInUse(Failure::new(
+            "stand_in_use",
+            format!("The stand `{id}` is used by trips, deactivate it instead"),
+        ))
+    }
+
+    pub fn stand_inactive(id: &Uuid) -> Self {
+        Error::StandInactive(Failure::new(
+            "stand_inactive",
+            format!("The stand `{id}` is not in service anymore"),
+        ))
+    }
+
+    pub fn already_queued(taxi: &str, stand: &Uuid) -> Self {
+        Error::AlreadyQueued(Failure::new(
+            "already_queued",
+            format!("The taxi `{taxi}` already stands in the queue of `{stand}`"),
+        ))
+    }
+
+    pub fn no_journey_from_stand(taxi: &str, stand: &Uuid) -> Self {
+        Error::NoJourneyFromStand(Failure::new(
+            "no_in_progress_journey",
+            format!("The taxi `{taxi}` has no journey in progress departing from `{stand}`"),
+        ))
+    }
+
+    pub fn unknown_queue_entry(taxi: &str, stand: &Uuid) -> Self {
+        Error::UnknownQueueEntry(Failure::new(
+            "not_found",
+            format!("The taxi `{taxi}` is not in the queue of `{stand}`"),
+        ))
+    }
+
+    pub fn invalid_stops(error_description: String) -> Self {
+        Error::InvalidStops(Failure::new("invalid_request", error_description))
+    }
+
+    pub fn invalid_segment(journey_id: &Uuid) -> Self {
+        Error::InvalidSegment(Failure::new(
+            "invalid_request",
+            format!("The journey `{journey_id}` does not go from the departure to the arrival"),
+        ))
+    }
+
+    pub fn invalid_query(error_description: String) -> Self {
+        Error::InvalidQuery(Failure::new("invalid_request", error_description))
     }
 }
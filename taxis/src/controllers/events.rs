@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use rocket::response::stream::{Event, EventStream};
+use rocket::tokio::select;
+use rocket::tokio::sync::broadcast::error::RecvError;
+use rocket::{get, routes, Route, Shutdown, State};
+use uuid::Uuid;
+
+use crate::events::{BusEvent, EventBus};
+use crate::guards::LastEventId;
+
+fn stream(
+    bus: &EventBus,
+    last_event_id: LastEventId,
+    mut shutdown: Shutdown,
+    filter: impl Fn(&BusEvent) -> bool + Send + 'static,
+) -> EventStream![] {
+    // Subscribing before reading the history means nothing published in between is lost.
+    let (mut receiver, latest) = bus.subscribe();
+    let mut missed = last_event_id.0.map(|id| bus.since(id)).unwrap_or_default();
+    let reset = last_event_id.0.is_some_and(|id| is_gone(&missed, id));
+    if reset {
+        missed.clear();
+    }
+    let bus = bus.clone();
+
+    EventStream! {
+        // The last event gone through, whether it was sent or filtered out.
+        let mut last_id = last_event_id.0.unwrap_or(latest);
+
+        // Some of the missed events are gone from the history: rather than replaying the rest,
+        // the client is told to reload, and the stream goes on from the latest event.
+        if reset {
+            last_id = latest;
+            yield Event::empty().event("reset").id(latest.to_string());
+        }
+
+        for event in missed {
+            last_id = event.id;
+            if filter(&event) {
+                yield to_sse(&event);
+            }
+        }
+
+        loop {
+            let events: Vec<Arc<BusEvent>> = select! {
+                event = receiver.recv() => match event {
+                    Ok(event) => vec![event],
+                    Err(RecvError::Closed) => break,
+                    // The events skipped by the receiver are replayed from the history, unless
+                    // they are already gone from it too: the client has to start over then.
+                    Err(RecvError::Lagged(_)) => {
+                        let missed = bus.since(last_id);
+                        if is_gone(&missed, last_id) {
+                            break;
+                        }
+                        missed
+                    }
+                },
+                _ = &mut shutdown => break,
+            };
+
+            for event in events {
+                if event.id <= last_id {
+                    continue;
+                }
+
+                last_id = event.id;
+                if filter(&event) {
+                    yield to_sse(&event);
+                }
+            }
+        }
+    }
+}
+
+/// Whether the history has lost events published after `last_id`, being all that is `missed`.
+fn is_gone(missed: &[Arc<BusEvent>], last_id: u64) -> bool {
+    missed.first().is_some_and(|e| e.id > last_id + 1)
+}
+
+fn to_sse(event: &BusEvent) -> Event {
+    Event::json(&event.update)
+        .id(event.id.to_string())
+        .event(event.kind)
+}
+
+#[utoipa::path(
+    tag = "events",
+    params(
+        ("Last-Event-ID" = Option<u64>, Header, description = "The last event received, to replay the missed ones, or to get a `reset` event when they are gone"),
+    ),
+    responses(
+        (status = 200, description = "The changes of the trip, as server-sent events", body = String, content_type = "text/event-stream"),
+    )
+)]
+#[get("/trips/<id>/events")]
+fn journey(
+    id: Uuid,
+    bus: &State<EventBus>,
+    last_event_id: LastEventId,
+    shutdown: Shutdown,
+) -> EventStream![] {
+    stream(bus, last_event_id, shutdown, move |e| {
+        e.concerns_journey(&id)
+    })
+}
+
+#[utoipa::path(
+    tag = "events",
+    params(
+        ("Last-Event-ID" = Option<u64>, Header, description = "The last event received, to replay the missed ones, or to get a `reset` event when they are gone"),
+    ),
+    responses(
+        (status = 200, description = "The changes of the trips calling at the rank, as server-sent events", body = String, content_type = "text/event-stream"),
+    )
+)]
+#[get("/taxi-ranks/<id>/events")]
+fn rank(
+    id: Uuid,
+    bus: &State<EventBus>,
+    last_event_id: LastEventId,
+    shutdown: Shutdown,
+) -> EventStream![] {
+    stream(bus, last_event_id, shutdown, move |e| e.concerns_rank(&id))
+}
+
+pub fn routes() -> Vec<Route> {
+    routes![journey, rank]
+}
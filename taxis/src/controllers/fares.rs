@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+
+use domain::entity::Currency;
+use log::error;
+use resource_derive::Resource;
+use rocket::{get, routes, uri, Route};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::controllers::taxi_ranks;
+use crate::controllers::Error;
+use crate::data::FareDataProvider;
+use crate::hal::{Hal, Resource};
+use crate::usecase::GetFare;
+use crate::{Link, BASE_URL};
+
+const TARGET: &'static str = "FARES_CONTROLLER";
+
+#[derive(Serialize, ToSchema)]
+pub struct Price {
+    amount: u64,
+    currency: &'static str,
+}
+
+impl From<domain::entity::Price> for Price {
+    fn from(value: domain::entity::Price) -> Self {
+        Price {
+            amount: value.amount,
+            currency: value.currency.code(),
+        }
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct FareData {
+    amount: u64,
+    #[serde(default)]
+    currency: Option<String>,
+}
+
+impl FareData {
+    pub fn to_price(&self) -> Result<domain::entity::Price, Error> {
+        if i64::try_from(self.amount).is_err() {
+            return Err(Error::invalid_fare(format!(
+                "The amount `{}` is too large",
+                self.amount
+            )));
+        }
+
+        let currency = match &self.currency {
+            None => Currency::default(),
+            Some(code) => code.parse::<Currency>().map_err(|_| {
+                Error::invalid_fare(format!("The currency `{code}` is not supported"))
+            })?,
+        };
+
+        Ok(domain::entity::Price::new(self.amount, currency))
+    }
+}
+
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", route = show(self.origin, self.destination))]
+#[link_to(rel = "quote", route = quote(self.origin, self.destination, Some(1u8)))]
+pub(super) struct FareResponse {
+    departure_id: String,
+    arrival_id: String,
+    price: Price,
+    #[serde(skip)]
+    origin: Uuid,
+    #[serde(skip)]
+    destination: Uuid,
+    #[serde(rename = "_links")]
+    #[links]
+    links: HashMap<&'static str, Link>,
+}
+
+impl FareResponse {
+    fn new(origin: &Uuid, destination: &Uuid, price: domain::entity::Price) -> Self {
+        FareResponse {
+            departure_id: uri!(BASE_URL, taxi_ranks::show(origin)).to_string(),
+            arrival_id: uri!(BASE_URL, taxi_ranks::show(destination)).to_string(),
+            price: price.into(),
+            origin: *origin,
+            destination: *destination,
+            links: HashMap::new(),
+        }
+        .linked()
+    }
+}
+
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", route = quote(self.origin, self.destination, Some(self.seats)))]
+#[link_to(rel = "fare", route = show(self.origin, self.destination))]
+pub(super) struct Quote {
+    departure_id: String,
+    arrival_id: String,
+    seats: u8,
+    unit_price: Price,
+    total: Price,
+    #[serde(skip)]
+    origin: Uuid,
+    #[serde(skip)]
+    destination: Uuid,
+    #[serde(rename = "_links")]
+    #[links]
+    links: HashMap<&'static str, Link>,
+}
+
+#[utoipa::path(
+    tag = "fares",
+    responses(
+        (status = 200, description = "The fare between the ranks", body = FareResponse, content_type = "application/hal+json"),
+        (status = 404, description = "No fare is set between the ranks", body = Failure),
+    )
+)]
+#[get("/taxi-ranks/<origin>/fares/<destination>")]
+async fn show(
+    origin: Uuid,
+    destination: Uuid,
+    mut data_provider: FareDataProvider,
+) -> Result<Hal<FareResponse>, Error> {
+    data_provider
+        .get_fare(&origin, &destination)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .and_then(|f| f.price())
+        .map(|price| Hal(FareResponse::new(&origin, &destination, price)))
+        .ok_or_else(|| Error::unknown_fare(&origin, &destination))
+}
+
+#[utoipa::path(
+    tag = "fares",
+    params(
+        ("from" = Uuid, Query, description = "The rank boarded at"),
+        ("to" = Uuid, Query, description = "The rank alighted at"),
+        ("seats" = Option<u8>, Query, description = "Number of seats to quote, 1 by default"),
+    ),
+    responses(
+        (status = 200, description = "The price of the seats", body = Quote, content_type = "application/hal+json"),
+        (status = 404, description = "No fare is set between the ranks", body = Failure),
+    )
+)]
+#[get("/quotes?<from>&<to>&<seats>")]
+async fn quote(
+    from: Uuid,
+    to: Uuid,
+    seats: Option<u8>,
+    mut data_provider: FareDataProvider,
+) -> Result<Hal<Quote>, Error> {
+    let seats = seats.unwrap_or(1).max(1);
+
+    let unit_price = data_provider
+        .get_fare(&from, &to)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .and_then(|f| f.price())
+        .ok_or_else(|| Error::unknown_fare(&from, &to))?;
+
+    let total = unit_price
+        .checked_mul(seats as u64)
+        .ok_or_else(|| Error::invalid_fare(format!("Unable to quote {seats} seats")))?;
+
+    Ok(Hal(Quote {
+        departure_id: uri!(BASE_URL, taxi_ranks::show(from)).to_string(),
+        arrival_id: uri!(BASE_URL, taxi_ranks::show(to)).to_string(),
+        seats,
+        unit_price: unit_price.into(),
+        total: total.into(),
+        origin: from,
+        destination: to,
+        links: HashMap::new(),
+    }
+    .linked()))
+}
+
+pub fn routes() -> Vec<Route> {
+    routes![show, quote]
+}
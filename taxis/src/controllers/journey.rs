@@ -5,116 +5,253 @@ use log::error;
 use rocket::http::Status;
 use rocket::response::status;
 use rocket::serde::json::Json;
-use rocket::{delete, get, patch, post, routes, uri, Route};
+use rocket::{delete, get, patch, post, routes, uri, FromForm, Route, State, UriDisplayQuery};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::controllers::taxi_ranks;
+use resource_derive::Resource;
+
+use crate::controllers::fares::{FareData, Price};
 use crate::controllers::Error;
-use crate::data::JourneyDataProvider;
-use crate::guards::User;
+use crate::controllers::{boardings, pagination, positions, taxi_ranks, trip_routes};
+use crate::data::{JourneyDataProvider, QueueDataProvider, RouteDataProvider, StandDataProvider};
+use crate::entity::{self, NotificationEvent};
+use crate::events::EventBus;
+use crate::guards::{Notifications, Observers, User};
+use crate::hal::{Hal, Resource};
 use crate::usecase::{
-    CancelJourney, CloseJourney, DoesBookingExistsOnJourney, GetAllJourney, GetInProgressJourney,
-    GetJourney, HasAJourneyInProgress, PerformJourney,
+    AdvanceRankQueue, CancelJourney, CloseJourney, DepartRankQueue, DoesBookingExistsOnJourney,
+    GetAllJourney, GetInProgressJourney, GetJourney, GetJourneyStops, GetRouteBetween, GetStand,
+    GetStands, HasAJourneyInProgress, PerformJourney, SetJourneyFare,
 };
-use crate::{entity, Link, BASE_URL};
+use crate::{Link, BASE_URL};
 
 const TARGET: &'static str = "JOURNEY_CONTROLLER";
 
-#[derive(Deserialize, Validate)]
-struct Criteria<'r> {
+#[derive(Deserialize, ToSchema, Validate)]
+pub(super) struct Criteria<'r> {
     departure_schedule: DateTime<Utc>,
     #[validate(url)]
     departure_id: &'r str,
     #[validate(url)]
     arrival_id: &'r str,
+    #[serde(default)]
+    fare: Option<FareData>,
+    #[serde(default)]
+    stops: Vec<StopCriteria>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub(super) struct StopCriteria {
+    rank_id: String,
+    scheduled_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Copy, Default, FromForm, UriDisplayQuery, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct TripQuery<'r> {
+    /// `open` or `closed`
+    status: Option<&'r str>,
+    /// Earliest departure, in RFC 3339
+    from: Option<&'r str>,
+    /// Latest departure, in RFC 3339
+    to: Option<&'r str>,
+    /// The rank the trips leave from
+    departure: Option<Uuid>,
+    /// The rank the trips go to
+    arrival: Option<Uuid>,
+    /// `departure_schedule` or `reserved_seats`, descending with a leading `-`
+    sort: Option<&'r str>,
+    /// The cursor of a `next` or `prev` link
+    cursor: Option<&'r str>,
+    /// Number of trips per page, at most 100
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub(super) struct JourneyFare {
+    fare: Option<FareData>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct Trips {
     trips: Vec<Journey>,
+    #[serde(rename = "_links")]
+    links: HashMap<&'static str, Link>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", route = show(&self.taxi, &self.id))]
+#[link_to(rel = "boardings", route = boardings::create(&self.taxi, &self.id))]
+#[link_to(rel = "boarding_keys", route = boardings::keys(&self.taxi, &self.id))]
+#[link_to(rel = "positions", route = positions::create(&self.taxi, &self.id))]
+#[link_to(
+    rel = "route",
+    href = self.route_id.clone().unwrap_or_default(),
+    when = self.route_id.is_some()
+)]
+#[link_to(rel = "cancel", route = cancel(&self.taxi, &self.id), when = self.cancellable)]
+#[link_to(rel = "close", route = close(&self.taxi, &self.id), when = !self.closed)]
+#[link_to(rel = "fare", route = set_fare(&self.taxi, &self.id), when = !self.closed)]
 pub struct Journey {
     pub id: Uuid,
     pub departure_id: String,
     pub arrival_id: String,
     pub reserved_seats: i32,
     pub departure_schedule: DateTime<Utc>,
+    pub estimated_arrival: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boarding_at: Option<DateTime<Utc>>,
+    pub price: Option<Price>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub route_id: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stops: Vec<Stop>,
+    #[serde(skip)]
+    taxi: String,
+    #[serde(skip)]
+    closed: bool,
+    /// Only a journey nobody booked can be cancelled.
+    #[serde(skip)]
+    cancellable: bool,
     #[serde(rename = "_links")]
+    #[links]
     links: HashMap<&'static str, Link>,
+    /// The origin and destination ranks, so that their names need no further request.
+    #[serde(rename = "_embedded", skip_serializing_if = "HashMap::is_empty")]
+    embedded: HashMap<&'static str, EmbeddedStand>,
 }
 
-struct JourneyLinksOpts {
-    is_cancellable: bool,
-    is_closable: bool,
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", route = taxi_ranks::show(self.id))]
+pub(super) struct EmbeddedStand {
+    #[serde(skip)]
+    id: Uuid,
+    name: String,
+    #[serde(rename = "_links")]
+    #[links]
+    links: HashMap<&'static str, Link>,
 }
 
-impl From<&entity::Journey> for JourneyLinksOpts {
-    fn from(value: &entity::Journey) -> Self {
-        JourneyLinksOpts {
-            is_closable: !value.closed,
-            is_cancellable: !value.closed && value.reserved_seats == 0,
+#[derive(Serialize, ToSchema)]
+pub struct Stop {
+    rank_id: String,
+    scheduled_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    available_seats: Option<i32>,
+}
+
+impl From<entity::JourneyStop> for Stop {
+    fn from(value: entity::JourneyStop) -> Self {
+        Stop {
+            rank_id: uri!(BASE_URL, taxi_ranks::show(value.rank_id)).to_string(),
+            scheduled_at: value.scheduled_at,
+            available_seats: value.available_seats,
         }
     }
 }
 
 impl Journey {
-    fn links(&mut self, taxi: &str, opts: JourneyLinksOpts) {
-        self.links.insert(
-            "self",
-            Link {
-                href: uri!(BASE_URL, show(taxi, &self.id)).to_string(),
-            },
-        );
-
-        if opts.is_cancellable {
-            self.links.insert(
-                "cancel",
-                Link {
-                    href: uri!(BASE_URL, cancel(taxi, &self.id)).to_string(),
-                },
-            );
+    fn new(journey: entity::Journey, taxi: &str, ranks: &HashMap<Uuid, entity::Stand>) -> Self {
+        let ends = [
+            ("origin", journey.origin),
+            ("destination", journey.destination),
+        ];
+
+        let mut response = Journey::from(journey);
+        response.taxi = taxi.into();
+        for (rel, id) in ends {
+            if let Some(stand) = ranks.get(&id) {
+                let stand = EmbeddedStand {
+                    id,
+                    name: stand.name.clone(),
+                    links: HashMap::new(),
+                };
+                response.embedded.insert(rel, stand.linked());
+            }
         }
 
-        if opts.is_closable {
-            self.links.insert(
-                "close",
-                Link {
-                    href: uri!(BASE_URL, close(taxi, &self.id)).to_string(),
-                },
-            );
-        }
+        response.linked()
     }
 }
 
 impl From<entity::Journey> for Journey {
     fn from(value: entity::Journey) -> Self {
         Journey {
+            price: value.fare().map(Price::from),
+            estimated_arrival: value.estimated_arrival(),
+            boarding_at: value.boarding_at,
+            route_id: value
+                .route_id
+                .map(|id| uri!(BASE_URL, trip_routes::show(id)).to_string()),
             id: value.id,
             reserved_seats: value.reserved_seats,
             departure_id: uri!(BASE_URL, taxi_ranks::show(value.origin)).to_string(),
             arrival_id: uri!(BASE_URL, taxi_ranks::show(value.destination)).to_string(),
             departure_schedule: value.departure_schedule,
+            stops: vec![],
+            taxi: String::new(),
+            closed: value.closed,
+            cancellable: !value.closed && value.reserved_seats == 0,
             links: HashMap::new(),
+            embedded: HashMap::new(),
         }
     }
 }
 
-impl Into<entity::JourneyCriteria> for Criteria<'_> {
-    fn into(self) -> entity::JourneyCriteria {
-        entity::JourneyCriteria {
-            origin: get_stand_id_from_url(self.departure_id),
-            destination: get_stand_id_from_url(self.arrival_id),
-            departure_schedule: self.departure_schedule,
+impl TryFrom<Criteria<'_>> for entity::JourneyCriteria {
+    type Error = Error;
+
+    fn try_from(value: Criteria<'_>) -> Result<Self, Self::Error> {
+        let origin = get_stand_id_from_url(value.departure_id);
+        let destination = get_stand_id_from_url(value.arrival_id);
+
+        let mut calls = vec![origin, destination];
+        let mut previous = value.departure_schedule;
+        let mut stops = Vec::with_capacity(value.stops.len());
+        for stop in &value.stops {
+            let rank_id = stop
+                .rank_id
+                .rsplit('/')
+                .next()
+                .and_then(|id| id.parse().ok())
+                .ok_or_else(|| {
+                    Error::invalid_stops(format!("`{}` is not a taxi rank", stop.rank_id))
+                })?;
+            if calls.contains(&rank_id) {
+                return Err(Error::invalid_stops(format!(
+                    "The journey already calls at `{rank_id}`"
+                )));
+            }
+            if stop.scheduled_at <= previous {
+                return Err(Error::invalid_stops(format!(
+                    "The stop at `{rank_id}` must be scheduled after the previous one"
+                )));
+            }
+
+            calls.push(rank_id);
+            previous = stop.scheduled_at;
+            stops.push(entity::NewStop {
+                rank_id,
+                scheduled_at: stop.scheduled_at,
+            });
         }
+
+        Ok(entity::JourneyCriteria {
+            fare: value.fare.as_ref().map(FareData::to_price).transpose()?,
+            origin,
+            destination,
+            departure_schedule: value.departure_schedule,
+            route: None,
+            stops,
+        })
     }
 }
 
-#[derive(Serialize)]
-struct PerformJourneyResponse {
+#[derive(Serialize, ToSchema)]
+pub(super) struct PerformJourneyResponse {
     id: Uuid,
     #[serde(rename = "_links")]
     links: HashMap<&'static str, Link>,
@@ -134,13 +271,26 @@ impl PerformJourneyResponse {
     }
 }
 
+#[utoipa::path(
+    tag = "journeys",
+    request_body = Criteria,
+    responses(
+        (status = 201, description = "The started journey", body = PerformJourneyResponse, content_type = "application/hal+json"),
+        (status = 400, description = "The taxi already has a journey in progress, or the stops are invalid", body = Failure),
+        (status = 404, description = "A rank of the journey is unknown", body = Failure),
+        (status = 409, description = "A rank or the route is no longer in service", body = Failure),
+    )
+)]
 #[post("/taxis/<number>/start-journey", data = "<criteria>")]
 async fn start(
     number: &str,
     criteria: Json<Criteria<'_>>,
     user: Result<User, Error>,
+    observers: Observers<'_>,
+    mut stands: StandDataProvider,
+    mut routes: RouteDataProvider,
     mut data_provider: JourneyDataProvider,
-) -> Result<status::Created<Json<PerformJourneyResponse>>, Error> {
+) -> Result<status::Created<Hal<PerformJourneyResponse>>, Error> {
     user?;
 
     data_provider
@@ -157,27 +307,70 @@ async fn start(
             Ok(())
         })?;
 
-    let jc: entity::JourneyCriteria = criteria.0.into();
+    let mut jc = entity::JourneyCriteria::try_from(criteria.0)?;
+
+    let calls = [jc.origin, jc.destination]
+        .into_iter()
+        .chain(jc.stops.iter().map(|s| s.rank_id))
+        .collect::<Vec<_>>();
+    for id in &calls {
+        let stand = stands
+            .get_stand(id)
+            .await
+            .map_err(|err| {
+                error!(target: TARGET, "{err:?}");
+                Error::server_error()
+            })?
+            .ok_or_else(|| Error::unknown_stand(id))?;
+        if !stand.active {
+            return Err(Error::stand_inactive(id));
+        }
+    }
+
+    // Pairs missing from the catalogue are still allowed, only without an estimated arrival.
+    jc.route = routes
+        .get_route_between(&jc.origin, &jc.destination)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?;
+    if let Some(route) = jc.route.as_ref().filter(|r| !r.active) {
+        return Err(Error::route_unavailable(&route.id));
+    }
+
     match data_provider.perform_journey(number, &jc).await {
         Err(err) => {
             error!(target: TARGET, "{err:?}");
             Err(Error::server_error())
         }
-        Ok(journey_id) => Ok(
-            status::Created::new("").body(Json(PerformJourneyResponse::new(journey_id, number)))
-        ),
+        Ok(journey_id) => {
+            observers
+                .bus
+                .journey_changed(&journey_id, "journey_started");
+            observers.metrics.journey("started");
+            Ok(status::Created::new("").body(Hal(PerformJourneyResponse::new(journey_id, number))))
+        }
     }
 }
 
+#[utoipa::path(
+    tag = "journeys",
+    responses(
+        (status = 200, description = "The journey the taxi is on", body = Journey, content_type = "application/hal+json"),
+        (status = 404, description = "The taxi is not on a journey", body = Failure),
+    )
+)]
 #[get("/taxis/<number>/in-progress-journey", rank = 1)]
 async fn in_progress(
     number: &str,
     user: Result<User, Error>,
+    mut stands: StandDataProvider,
     mut data_provider: JourneyDataProvider,
-) -> Result<Json<Journey>, Error> {
+) -> Result<Hal<Journey>, Error> {
     user?;
 
-    data_provider
+    let journey = data_provider
         .get_in_progress_journey(number)
         .await
         .map_err(|err| {
@@ -186,23 +379,40 @@ async fn in_progress(
         })
         .and_then(|j| match j {
             None => Err(Error::no_in_progress_journey()),
-            Some(jn) => {
-                let opts = JourneyLinksOpts::from(&jn);
-                let mut journey = Journey::from(jn);
-                journey.links(number, opts);
+            Some(jn) => Ok(jn),
+        })?;
 
-                Ok(Json(journey))
-            }
-        })
+    let stops = data_provider
+        .get_journey_stops(&journey.id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?;
+
+    let ranks = ranks_of(std::slice::from_ref(&journey), &mut stands).await?;
+
+    let mut journey = Journey::new(journey, number, &ranks);
+    journey.stops = stops.into_iter().map(Stop::from).collect();
+
+    Ok(Hal(journey))
 }
 
+#[utoipa::path(
+    tag = "journeys",
+    responses(
+        (status = 200, description = "The journey", body = Journey, content_type = "application/hal+json"),
+        (status = 404, description = "The journey is unknown", body = Failure),
+    )
+)]
 #[get("/taxis/<number>/journey/<id>", rank = 3)]
 async fn show(
     number: &str,
     id: Uuid,
+    mut stands: StandDataProvider,
     mut data_provider: JourneyDataProvider,
-) -> Result<Json<Journey>, Error> {
-    data_provider
+) -> Result<Hal<Journey>, Error> {
+    let journey = data_provider
         .get_journey(&id)
         .await
         .map_err(|err| {
@@ -212,21 +422,37 @@ async fn show(
         .and_then(|res| match res {
             None => Err(Error::unknown_journey(&id)),
             Some(j) => Ok(j),
-        })
-        .map(|r| {
-            let opts = JourneyLinksOpts::from(&r);
-            let mut journey = Journey::from(r);
-            journey.links(number, opts);
+        })?;
 
-            Json(journey)
-        })
+    let stops = data_provider.get_journey_stops(&id).await.map_err(|err| {
+        error!(target: TARGET, "{err:?}");
+        Error::server_error()
+    })?;
+
+    let ranks = ranks_of(std::slice::from_ref(&journey), &mut stands).await?;
+
+    let mut journey = Journey::new(journey, number, &ranks);
+    journey.stops = stops.into_iter().map(Stop::from).collect();
+
+    Ok(Hal(journey))
 }
 
-#[delete("/taxis/<_number>/journey/<journey>/cancel")]
+#[utoipa::path(
+    tag = "journeys",
+    responses(
+        (status = 204, description = "The journey is cancelled"),
+        (status = 404, description = "The journey is unknown or of another taxi", body = Failure),
+        (status = 409, description = "The journey has bookings", body = Failure),
+    )
+)]
+#[delete("/taxis/<number>/journey/<journey>/cancel")]
 async fn cancel(
-    _number: &str,
+    number: &str,
     journey: Uuid,
     user: Result<User, Error>,
+    observers: Observers<'_>,
+    mut notifications: Notifications<'_>,
+    mut queue: QueueDataProvider,
     mut data_provider: JourneyDataProvider,
 ) -> Result<status::NoContent, Error> {
     user?;
@@ -247,22 +473,72 @@ async fn cancel(
             }
         })?;
 
-    data_provider
-        .cancel_journey(&journey)
+    // The trip and its waitlist are gone once cancelled, so whom to tell is looked up first.
+    let cancelled = data_provider.get_journey(&journey).await.map_err(|err| {
+        error!(target: TARGET, "{err:?}");
+        Error::server_error()
+    })?;
+    let audience = notifications
+        .audience(NotificationEvent::JourneyCancelled, &journey)
+        .await;
+
+    // Bookings keep the trip they were made on, so one made meanwhile still refuses the cancel.
+    let cancelled_trip = data_provider
+        .cancel_journey(number, &journey)
         .await
-        .map_err(|err| {
-            error!(target: TARGET, "{err:?}");
-            Error::server_error()
+        .map_err(|err| match err {
+            sqlx::Error::Database(e) if e.code().as_deref() == Some("23503") => {
+                Error::unable_to_cancel_journey(&journey)
+            }
+            _ => {
+                error!(target: TARGET, "{err:?}");
+                Error::server_error()
+            }
         })?;
 
+    if !cancelled_trip {
+        return Err(Error::unknown_journey(&journey));
+    }
+    observers.metrics.journey("cancelled");
+
+    if let Some(j) = cancelled {
+        observers
+            .bus
+            .journey_removed(&j.id, &j.origin, &j.destination);
+
+        // Its queue entry went with the trip, which may have freed the head of the rank.
+        match queue.advance_rank_queue(&j.origin).await {
+            Err(err) => error!(target: TARGET, "{err:?}"),
+            Ok(Some(dispatched)) => observers
+                .bus
+                .journey_changed(&dispatched, "journey_dispatched"),
+            Ok(None) => {}
+        }
+    }
+
+    if let Some(audience) = audience {
+        notifications
+            .enqueue(NotificationEvent::JourneyCancelled, &audience)
+            .await;
+    }
+
     Ok(status::NoContent)
 }
 
+#[utoipa::path(
+    tag = "journeys",
+    responses(
+        (status = 200, description = "The journey is closed to bookings"),
+        (status = 400, description = "The owner is not signed in", body = Failure),
+    )
+)]
 #[patch("/taxis/<_number>/journey/<journey_id>/close")]
 async fn close(
     _number: &str,
     journey_id: Uuid,
     user: Result<User, Error>,
+    observers: Observers<'_>,
+    mut queue: QueueDataProvider,
     mut data_provider: JourneyDataProvider,
 ) -> Result<Status, Error> {
     user?;
@@ -275,42 +551,180 @@ async fn close(
             Error::server_error()
         })?;
 
+    observers.bus.journey_changed(&journey_id, "journey_closed");
+    observers.metrics.journey("closed");
+
+    match queue.depart_rank_queue(&journey_id).await {
+        Err(err) => error!(target: TARGET, "{err:?}"),
+        Ok(exit) => {
+            if let Some(dispatched) = exit.and_then(|e| e.dispatched) {
+                observers
+                    .bus
+                    .journey_changed(&dispatched, "journey_dispatched");
+            }
+        }
+    }
     Ok(Status::Ok)
 }
 
-#[get("/taxis/<number>/trips", rank = 2)]
+#[utoipa::path(
+    tag = "journeys",
+    request_body = JourneyFare,
+    responses(
+        (status = 200, description = "The journey with its new fare", body = Journey, content_type = "application/hal+json"),
+        (status = 400, description = "The fare is invalid", body = Failure),
+        (status = 404, description = "The journey is unknown, closed or of another taxi", body = Failure),
+    )
+)]
+#[patch("/taxis/<number>/journey/<journey_id>/fare", data = "<data>")]
+async fn set_fare(
+    number: &str,
+    journey_id: Uuid,
+    data: Json<JourneyFare>,
+    user: Result<User, Error>,
+    bus: &State<EventBus>,
+    stands: StandDataProvider,
+    mut data_provider: JourneyDataProvider,
+) -> Result<Hal<Journey>, Error> {
+    user?;
+
+    let fare = data.fare.as_ref().map(FareData::to_price).transpose()?;
+
+    let updated = data_provider
+        .set_journey_fare(number, &journey_id, fare.as_ref())
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?;
+
+    if !updated {
+        return Err(Error::unknown_journey(&journey_id));
+    }
+
+    bus.journey_changed(&journey_id, "fare_changed");
+    show(number, journey_id, stands, data_provider).await
+}
+
+#[utoipa::path(
+    tag = "journeys",
+    responses(
+        (status = 200, description = "A page of the trips of the taxi", body = Trips, content_type = "application/hal+json"),
+        (status = 400, description = "The query is invalid", body = Failure),
+    )
+)]
+#[get("/taxis/<number>/trips?<query..>", rank = 2)]
 async fn list(
     number: &str,
+    query: TripQuery<'_>,
     user: Result<User, Error>,
+    mut stands: StandDataProvider,
     mut data_provider: JourneyDataProvider,
-) -> Result<Json<Trips>, Error> {
+) -> Result<Hal<Trips>, Error> {
     user?;
 
-    data_provider
-        .get_all_journey(number)
+    let filter = entity::JourneyFilter {
+        status: query
+            .status
+            .map(|status| match status {
+                "open" => Ok(entity::JourneyStatus::Open),
+                "closed" => Ok(entity::JourneyStatus::Closed),
+                _ => Err(Error::invalid_query(format!("Unknown status `{status}`"))),
+            })
+            .transpose()?,
+        departs_after: pagination::timestamp("from", query.from)?,
+        departs_before: pagination::timestamp("to", query.to)?,
+        origin: query.departure,
+        destination: query.arrival,
+        sort: pagination::sort(
+            query.sort,
+            entity::Sort {
+                field: entity::JourneySortField::DepartureSchedule,
+                order: entity::SortOrder::Descending,
+            },
+            |field| match field {
+                "departure_schedule" => Some(entity::JourneySortField::DepartureSchedule),
+                "reserved_seats" => Some(entity::JourneySortField::ReservedSeats),
+                _ => None,
+            },
+        )?,
+    };
+    let page = pagination::page_request(query.cursor, query.limit, |key| {
+        filter.sort.field.accepts(key)
+    })?;
+
+    let trips = data_provider
+        .get_all_journey(number, &filter, &page)
         .await
         .map_err(|err| {
             error!(target: TARGET, "{err:?}");
             Error::server_error()
-        })
-        .map(|trips| {
-            trips
-                .into_iter()
-                .map(|j| {
-                    let opts = JourneyLinksOpts::from(&j);
-                    let mut journey = Journey::from(j);
-                    journey.links(number, opts);
-                    journey
-                })
-                .collect()
-        })
-        .map(|trips| Json(Trips { trips }))
+        })?;
+
+    let mut links = HashMap::from([(
+        "self",
+        Link {
+            href: uri!(BASE_URL, list(number, query)).to_string(),
+        },
+    )]);
+    for (rel, cursor) in [("next", &trips.next), ("prev", &trips.prev)] {
+        if let Some(cursor) = cursor {
+            let cursor = pagination::encode(cursor);
+            links.insert(
+                rel,
+                Link {
+                    href: uri!(
+                        BASE_URL,
+                        list(
+                            number,
+                            TripQuery {
+                                cursor: Some(cursor.as_str()),
+                                ..query
+                            }
+                        )
+                    )
+                    .to_string(),
+                },
+            );
+        }
+    }
+
+    let ranks = ranks_of(&trips.items, &mut stands).await?;
+    let trips = trips
+        .items
+        .into_iter()
+        .map(|j| Journey::new(j, number, &ranks))
+        .collect();
+
+    Ok(Hal(Trips { trips, links }))
 }
 
 pub fn routes() -> Vec<Route> {
-    routes![start, cancel, close, show, list, in_progress]
+    routes![start, cancel, close, show, list, in_progress, set_fare]
 }
 
 fn get_stand_id_from_url(url: &str) -> Uuid {
     url.split("/").last().unwrap().parse().unwrap()
 }
+
+/// The ranks the journeys go from and to, looked up at once to be embedded.
+async fn ranks_of(
+    journeys: &[entity::Journey],
+    stands: &mut StandDataProvider,
+) -> Result<HashMap<Uuid, entity::Stand>, Error> {
+    let mut ids = journeys
+        .iter()
+        .flat_map(|j| [j.origin, j.destination])
+        .collect::<Vec<_>>();
+    ids.sort();
+    ids.dedup();
+
+    stands
+        .get_stands(&ids)
+        .await
+        .map(|stands| stands.into_iter().map(|s| (s.id, s)).collect())
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })
+}
@@ -1,92 +1,69 @@
-use std::collections::HashMap;
-
 use log::error;
 use rocket::http::{Cookie, CookieJar};
 use rocket::serde::json::Json;
-use rocket::{post, routes, uri, Route};
-use serde::{Deserialize, Serialize};
+use rocket::{post, routes, Route, State};
+use serde::Deserialize;
+use utoipa::ToSchema;
 
+use crate::controllers::user::User;
 use crate::controllers::Error;
-use crate::controllers::{journey, user};
 use crate::data::LoginDataProvider;
+use crate::hal::Hal;
+use crate::metrics::Metrics;
+use crate::password;
 use crate::usecase::GetOwner;
-use crate::{password, Link, BASE_URL};
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct Credential<'r> {
     number: &'r str,
     password: &'r str,
 }
 
-#[derive(Serialize)]
-pub struct Response {
-    name: String,
-    #[serde(rename = "_links")]
-    links: HashMap<&'static str, Link>,
-}
-
-impl Response {
-    fn new(taxi_num: &str, name: &str) -> Self {
-        let links = HashMap::from([
-            (
-                "start-journey",
-                Link {
-                    href: uri!(BASE_URL, journey::start(taxi_num)).to_string(),
-                },
-            ),
-            (
-                "trips",
-                Link {
-                    href: uri!(BASE_URL, journey::list(taxi_num)).to_string(),
-                },
-            ),
-            (
-                "self",
-                Link {
-                    href: uri!(BASE_URL, user::show(taxi_num)).to_string(),
-                },
-            ),
-            (
-                "journey_in_progress",
-                Link {
-                    href: uri!(BASE_URL, journey::in_progress(taxi_num)).to_string(),
-                },
-            ),
-        ]);
-        Response {
-            links,
-            name: name.into(),
-        }
-    }
-}
-
+#[utoipa::path(
+    tag = "taxis",
+    request_body = Credential,
+    responses(
+        (status = 200, description = "The owner of the taxi, now signed in", body = User, content_type = "application/hal+json"),
+        (status = 400, description = "The number or the password is wrong", body = Failure),
+    )
+)]
 #[post("/taxis/login", data = "<credential>")]
 async fn index(
     credential: Json<Credential<'_>>,
     mut db: LoginDataProvider,
     cookies: &CookieJar<'_>,
-) -> Result<Json<Response>, Error> {
+    metrics: &State<Metrics>,
+) -> Result<Hal<User>, Error> {
     match db.get_owner(credential.number).await {
         Err(err) => {
             error!(target: "LOGIN_CONTROLLER","{err:?}");
             Err(Error::server_error())
         }
         Ok(op) => match op {
-            None => Err(Error::invalid_number(credential.number)),
-            Some(u) => match password::verify(credential.password, &u.password)
-                .await
-                .unwrap()
-            {
-                false => Err(Error::invalid_password()),
-                true => {
-                    let payload = serde_json::json!({"full_name": &u.full_name});
-                    let num = credential.number.to_lowercase();
+            None => {
+                metrics.login(false);
+                Err(Error::invalid_number(credential.number))
+            }
+            Some(u) => {
+                let timer = metrics.verifying_password();
+                let verified = password::verify(credential.password, &u.password)
+                    .await
+                    .unwrap();
+                timer.observe_duration();
+
+                metrics.login(verified);
+                match verified {
+                    false => Err(Error::invalid_password()),
+                    true => {
+                        let payload = serde_json::json!({"full_name": &u.full_name});
+                        let num = credential.number.to_lowercase();
 
-                    cookies.add_private(Cookie::new(num.to_owned(), payload.to_string()));
+                        cookies.add_private(Cookie::new(num.to_owned(), payload.to_string()));
 
-                    Ok(Json(Response::new(&num, &u.full_name)))
+                        Ok(Hal(User::new(&num, &u.full_name)))
+                    }
                 }
-            },
+            }
         },
     }
 }
@@ -0,0 +1,148 @@
+use rocket::Route;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::controllers::{
+    boardings, bookings, errors, events, fares, journey, login, payments, positions, rank_queue,
+    registration, root, search, seats, taxi_ranks, tickets, trip_routes, user, waitlist,
+};
+use crate::Link;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "txpress", description = "Book seats on shared taxis leaving from taxi ranks."),
+    servers((url = "/v1", description = "First version of the API")),
+    paths(
+        root::index,
+        registration::start,
+        registration::finish,
+        user::show,
+        login::index,
+        journey::start,
+        journey::in_progress,
+        journey::show,
+        journey::cancel,
+        journey::close,
+        journey::set_fare,
+        journey::list,
+        taxi_ranks::add_taxi_ranks,
+        taxi_ranks::list_all,
+        taxi_ranks::show,
+        taxi_ranks::update,
+        taxi_ranks::remove,
+        rank_queue::check_in,
+        rank_queue::index,
+        rank_queue::show,
+        rank_queue::leave,
+        trip_routes::create,
+        trip_routes::list,
+        trip_routes::show,
+        trip_routes::update,
+        fares::show,
+        fares::quote,
+        search::index,
+        seats::index,
+        bookings::create,
+        bookings::show,
+        bookings::cancel,
+        boardings::create,
+        boardings::keys,
+        positions::create,
+        positions::show,
+        positions::passenger,
+        payments::callback,
+        tickets::show,
+        tickets::qr_svg,
+        tickets::qr_png,
+        waitlist::join,
+        waitlist::show,
+        waitlist::claim,
+        waitlist::leave,
+        events::journey,
+        events::rank,
+    ),
+    components(schemas(
+        Link,
+        errors::Failure,
+        root::Root,
+        registration::AdditionalTaxiData,
+        registration::StartResponse,
+        registration::FinishResponse,
+        user::User,
+        login::Credential,
+        journey::Criteria,
+        journey::StopCriteria,
+        journey::JourneyFare,
+        journey::Trips,
+        journey::Journey,
+        journey::EmbeddedStand,
+        journey::Stop,
+        journey::PerformJourneyResponse,
+        taxi_ranks::StandList,
+        taxi_ranks::AddedStands,
+        taxi_ranks::Stand,
+        taxi_ranks::StandResponse,
+        taxi_ranks::NewTaxiRanks,
+        taxi_ranks::NewTaxiRank,
+        taxi_ranks::TaxiRankChanges,
+        rank_queue::QueueCheckIn,
+        rank_queue::QueueEntry,
+        rank_queue::Queue,
+        trip_routes::NewRoute,
+        trip_routes::RouteChanges,
+        trip_routes::TripRoute,
+        trip_routes::RouteList,
+        fares::Price,
+        fares::FareData,
+        fares::FareResponse,
+        fares::Quote,
+        search::SearchResults,
+        search::AvailableJourney,
+        seats::Seat,
+        seats::SeatMap,
+        bookings::BookingRequest,
+        bookings::Cancellation,
+        bookings::PaymentSummary,
+        bookings::Booking,
+        boardings::ScannedTicket,
+        boardings::BoardingResponse,
+        boardings::Passenger,
+        boardings::BoardingKeys,
+        positions::Ping,
+        positions::Position,
+        tickets::Ticket,
+        waitlist::WaitlistRequest,
+        waitlist::WaitlistEntry,
+    )),
+    tags(
+        (name = "root", description = "Entry point of the API"),
+        (name = "registration", description = "Registration of taxis by their owners"),
+        (name = "taxis", description = "Owners of the taxis, and how they sign in"),
+        (name = "journeys", description = "Journeys driven by the taxis"),
+        (name = "taxi-ranks", description = "Ranks the journeys go from and to"),
+        (name = "queues", description = "Taxis queuing at the ranks"),
+        (name = "routes", description = "Catalogue of the routes between ranks"),
+        (name = "fares", description = "Prices between ranks"),
+        (name = "search", description = "Trips open to passengers"),
+        (name = "bookings", description = "Seats booked by passengers"),
+        (name = "payments", description = "Notifications of the payment providers"),
+        (name = "tickets", description = "Tickets of the confirmed bookings"),
+        (name = "boardings", description = "Passengers boarding the taxis"),
+        (name = "positions", description = "Where the taxis are along their journey"),
+        (name = "waitlist", description = "Passengers waiting for seats on full trips"),
+        (name = "events", description = "Live changes of the trips"),
+    )
+)]
+struct ApiDoc;
+
+/// The OpenAPI document of every route of `routes()`.
+pub fn spec() -> utoipa::openapi::OpenApi {
+    ApiDoc::openapi()
+}
+
+/// The document at `/openapi.json`, and the interactive documentation browsing it at `/docs`.
+pub fn docs() -> Vec<Route> {
+    SwaggerUi::new("/docs/<_..>")
+        .url("/openapi.json", spec())
+        .into()
+}
@@ -0,0 +1,95 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::controllers::Error;
+use crate::entity::{Cursor, PageRequest, Sort, SortOrder};
+
+const DEFAULT_LIMIT: usize = 20;
+const MAX_LIMIT: usize = 100;
+
+/// What a cursor carries, encoded so that clients treat it as opaque.
+#[derive(Serialize, Deserialize)]
+struct Token {
+    key: String,
+    id: Uuid,
+    #[serde(default)]
+    backward: bool,
+}
+
+/// Reads the `cursor` and `limit` parameters, the cursor key having to be one `accepts` takes
+/// for the field sorted by.
+pub(super) fn page_request(
+    cursor: Option<&str>,
+    limit: Option<usize>,
+    accepts: impl Fn(&str) -> bool,
+) -> Result<PageRequest, Error> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(Error::invalid_query(format!(
+            "The limit must be between 1 and {MAX_LIMIT}"
+        )));
+    }
+
+    Ok(PageRequest {
+        cursor: cursor.map(|c| decode(c, accepts)).transpose()?,
+        limit,
+    })
+}
+
+pub(super) fn encode(cursor: &Cursor) -> String {
+    let token = Token {
+        key: cursor.key.clone(),
+        id: cursor.id,
+        backward: cursor.backward,
+    };
+    URL_SAFE_NO_PAD.encode(serde_json::to_vec(&token).unwrap())
+}
+
+fn decode(cursor: &str, accepts: impl Fn(&str) -> bool) -> Result<Cursor, Error> {
+    URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<Token>(&bytes).ok())
+        // The key is cast to the type of the sort column, a cursor of another sort could not be.
+        .filter(|token| accepts(&token.key))
+        .map(|token| Cursor {
+            key: token.key,
+            id: token.id,
+            backward: token.backward,
+        })
+        .ok_or_else(|| Error::invalid_query(format!("`{cursor}` is not a valid cursor")))
+}
+
+/// Reads a `sort` parameter: one of the fields `field` knows, prefixed with `-` for the
+/// descending order.
+pub(super) fn sort<F>(
+    value: Option<&str>,
+    default: Sort<F>,
+    field: impl Fn(&str) -> Option<F>,
+) -> Result<Sort<F>, Error> {
+    let Some(value) = value else {
+        return Ok(default);
+    };
+
+    let (name, order) = match value.strip_prefix('-') {
+        Some(name) => (name, SortOrder::Descending),
+        None => (value, SortOrder::Ascending),
+    };
+
+    field(name)
+        .map(|field| Sort { field, order })
+        .ok_or_else(|| Error::invalid_query(format!("The listing cannot be sorted by `{name}`")))
+}
+
+pub(super) fn timestamp(name: &str, value: Option<&str>) -> Result<Option<DateTime<Utc>>, Error> {
+    value
+        .map(|value| {
+            DateTime::parse_from_rfc3339(value)
+                .map(|date| date.with_timezone(&Utc))
+                .map_err(|_| Error::invalid_query(format!("The {name} must be an RFC 3339 date")))
+        })
+        .transpose()
+}
@@ -0,0 +1,77 @@
+use log::error;
+use rocket::response::status;
+use rocket::{post, routes, Route, State};
+
+use crate::controllers::Error;
+use crate::data::PaymentDataProvider;
+use crate::entity::{NotificationEvent, Settlement};
+use crate::events::EventBus;
+use crate::guards::{CallbackSignature, Notifications};
+use crate::payment::{CallbackError, Payments};
+
+const TARGET: &'static str = "PAYMENTS_CONTROLLER";
+
+#[utoipa::path(
+    tag = "payments",
+    request_body(content = [u8], description = "The notification, as sent by the provider", content_type = "application/octet-stream"),
+    params(
+        ("X-Signature" = String, Header, description = "Signature of the notification by the provider"),
+    ),
+    responses(
+        (status = 204, description = "The payment is settled"),
+        (status = 400, description = "The notification is malformed", body = Failure),
+        (status = 401, description = "The signature is missing or invalid", body = Failure),
+        (status = 404, description = "The provider is unknown", body = Failure),
+    )
+)]
+#[post("/payments/<provider>/callback", data = "<payload>")]
+async fn callback(
+    provider: &str,
+    payload: Vec<u8>,
+    signature: Result<CallbackSignature, Error>,
+    payments: &State<Payments>,
+    bus: &State<EventBus>,
+    mut notifications: Notifications<'_>,
+    mut data_provider: PaymentDataProvider,
+) -> Result<status::NoContent, Error> {
+    let signature = signature?;
+
+    let settlement = payments
+        .process_callback(&mut data_provider, provider, &payload, &signature.0)
+        .await
+        .map_err(|err| match err {
+            CallbackError::UnknownProvider => Error::unknown_payment_provider(provider),
+            CallbackError::InvalidSignature => Error::invalid_signature(),
+            CallbackError::Malformed => Error::invalid_callback(),
+            CallbackError::Provider(err) => {
+                error!(target: TARGET, "{err:?}");
+                Error::payment_unavailable()
+            }
+            CallbackError::Sqlx(err) => {
+                error!(target: TARGET, "{err:?}");
+                Error::server_error()
+            }
+        })?;
+
+    match settlement {
+        Settlement::Confirmed {
+            booking_id,
+            journey_id,
+        } => {
+            bus.journey_changed(&journey_id, "booking_confirmed");
+            notifications
+                .notify(NotificationEvent::BookingConfirmed, &booking_id)
+                .await;
+        }
+        Settlement::Released { journey_id, .. } => {
+            bus.journey_changed(&journey_id, "booking_failed")
+        }
+        _ => {}
+    }
+
+    Ok(status::NoContent)
+}
+
+pub fn routes() -> Vec<Route> {
+    routes![callback]
+}
@@ -0,0 +1,241 @@
+use chrono::{DateTime, Duration, Utc};
+use log::error;
+use rocket::serde::json::Json;
+use rocket::{get, post, routes, Route, State};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::controllers::Error;
+use crate::data::TrackingDataProvider;
+use crate::entity::{self, TrackedJourney, Viewer};
+use crate::guards::{JourneyPassenger, User};
+use crate::hal::Hal;
+use crate::tracking::Tracking;
+use crate::usecase::{GetLatestPosition, GetTrackedJourney, RecordPosition};
+
+const TARGET: &'static str = "POSITIONS_CONTROLLER";
+
+#[derive(Deserialize, ToSchema)]
+pub(super) struct Ping {
+    latitude: f64,
+    longitude: f64,
+    speed_kmh: Option<f64>,
+    heading: Option<f64>,
+    recorded_at: Option<DateTime<Utc>>,
+}
+
+impl Ping {
+    fn into_position(
+        self,
+        journey_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<entity::Position, Error> {
+        if !(-90.0..=90.0).contains(&self.latitude) || !(-180.0..=180.0).contains(&self.longitude) {
+            return Err(Error::invalid_position(String::from(
+                "The coordinates are out of range",
+            )));
+        }
+        if self
+            .speed_kmh
+            .is_some_and(|speed| !(0.0..=400.0).contains(&speed))
+        {
+            return Err(Error::invalid_position(String::from(
+                "The speed must be between 0 and 400 km/h",
+            )));
+        }
+        if self
+            .heading
+            .is_some_and(|heading| !(0.0..360.0).contains(&heading))
+        {
+            return Err(Error::invalid_position(String::from(
+                "The heading must be between 0 and 360 degrees",
+            )));
+        }
+
+        let recorded_at = self.recorded_at.unwrap_or(now);
+        if recorded_at > now + Duration::minutes(1) {
+            return Err(Error::invalid_position(String::from(
+                "The position is recorded in the future",
+            )));
+        }
+
+        Ok(entity::Position {
+            journey_id,
+            latitude: self.latitude,
+            longitude: self.longitude,
+            speed_kmh: self.speed_kmh,
+            heading: self.heading,
+            recorded_at,
+        })
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct Position {
+    latitude: f64,
+    longitude: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speed_kmh: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    heading: Option<f64>,
+    recorded_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distance_km: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    eta: Option<DateTime<Utc>>,
+}
+
+impl Position {
+    fn new(position: entity::Position, journey: &TrackedJourney, tracking: &Tracking) -> Self {
+        let eta = tracking.eta(journey, &position);
+        Position {
+            latitude: position.latitude,
+            longitude: position.longitude,
+            speed_kmh: position.speed_kmh,
+            heading: position.heading,
+            recorded_at: position.recorded_at,
+            distance_km: eta.map(|(distance, _)| (distance * 1000.0).round() / 1000.0),
+            eta: eta.map(|(_, eta)| eta),
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "positions",
+    request_body = Ping,
+    responses(
+        (status = 200, description = "The recorded position, with the arrival it forecasts", body = Position, content_type = "application/hal+json"),
+        (status = 400, description = "The position is invalid", body = Failure),
+        (status = 409, description = "The journey is not under way", body = Failure),
+    )
+)]
+#[post("/taxis/<number>/journey/<journey_id>/positions", data = "<data>")]
+async fn create(
+    number: &str,
+    journey_id: Uuid,
+    data: Json<Ping>,
+    user: Result<User, Error>,
+    tracking: &State<Tracking>,
+    mut data_provider: TrackingDataProvider,
+) -> Result<Hal<Position>, Error> {
+    user?;
+
+    let journey = tracked_journey(&mut data_provider, &journey_id, &Viewer::Driver(number)).await?;
+
+    let now = Utc::now();
+    if !tracking.is_active(&journey, now) {
+        return Err(Error::journey_not_active(&journey_id));
+    }
+
+    let position = data.into_inner().into_position(journey_id, now)?;
+    let last = latest_position(&mut data_provider, &journey_id).await?;
+
+    if !tracking.should_record(last.as_ref(), &position) {
+        let position = last
+            .filter(|last| last.recorded_at > position.recorded_at)
+            .unwrap_or(position);
+        return Ok(Hal(Position::new(position, &journey, tracking)));
+    }
+
+    data_provider
+        .record_position(&position, tracking.config.max_points)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?;
+
+    Ok(Hal(Position::new(position, &journey, tracking)))
+}
+
+#[utoipa::path(
+    tag = "positions",
+    responses(
+        (status = 200, description = "The latest position of the taxi", body = Position, content_type = "application/hal+json"),
+        (status = 404, description = "The journey is unknown or has no position yet", body = Failure),
+    )
+)]
+#[get("/taxis/<number>/journey/<journey_id>/position")]
+async fn show(
+    number: &str,
+    journey_id: Uuid,
+    user: Result<User, Error>,
+    tracking: &State<Tracking>,
+    mut data_provider: TrackingDataProvider,
+) -> Result<Hal<Position>, Error> {
+    user?;
+
+    let journey = tracked_journey(&mut data_provider, &journey_id, &Viewer::Driver(number)).await?;
+    position(&mut data_provider, journey, tracking).await
+}
+
+#[utoipa::path(
+    tag = "positions",
+    responses(
+        (status = 200, description = "The latest position of the taxi, for its passengers", body = Position, content_type = "application/hal+json"),
+        (status = 400, description = "The passenger holds no booking on the trip", body = Failure),
+        (status = 409, description = "The trip is not under way", body = Failure),
+    )
+)]
+#[get("/trips/<id>/position")]
+async fn passenger(
+    id: Uuid,
+    passenger: Result<JourneyPassenger, Error>,
+    tracking: &State<Tracking>,
+    mut data_provider: TrackingDataProvider,
+) -> Result<Hal<Position>, Error> {
+    let passenger = passenger?;
+
+    let viewer = Viewer::Passenger(&passenger.bookings);
+    let journey = tracked_journey(&mut data_provider, &id, &viewer).await?;
+    if !tracking.is_active(&journey, Utc::now()) {
+        return Err(Error::journey_not_active(&id));
+    }
+
+    position(&mut data_provider, journey, tracking).await
+}
+
+async fn tracked_journey(
+    data_provider: &mut TrackingDataProvider,
+    journey_id: &Uuid,
+    viewer: &Viewer<'_>,
+) -> Result<TrackedJourney, Error> {
+    data_provider
+        .get_tracked_journey(journey_id, viewer)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .ok_or_else(|| Error::unknown_journey(journey_id))
+}
+
+async fn latest_position(
+    data_provider: &mut TrackingDataProvider,
+    journey_id: &Uuid,
+) -> Result<Option<entity::Position>, Error> {
+    data_provider
+        .get_latest_position(journey_id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })
+}
+
+async fn position(
+    data_provider: &mut TrackingDataProvider,
+    journey: TrackedJourney,
+    tracking: &Tracking,
+) -> Result<Hal<Position>, Error> {
+    let position = latest_position(data_provider, &journey.journey_id)
+        .await?
+        .ok_or_else(|| Error::journey_not_active(&journey.journey_id))?;
+
+    Ok(Hal(Position::new(position, &journey, tracking)))
+}
+
+pub fn routes() -> Vec<Route> {
+    routes![create, show, passenger]
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use log::error;
+use resource_derive::Resource;
+use rocket::http::CookieJar;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post, routes, uri, Route, State};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::controllers::Error;
+use crate::controllers::{journey, taxi_ranks};
+use crate::data::QueueDataProvider;
+use crate::entity::{self, CheckIn};
+use crate::events::EventBus;
+use crate::guards::User;
+use crate::hal::{Hal, Resource};
+use crate::usecase::{CheckInRankQueue, GetQueueEntry, GetRankQueue, LeaveRankQueue};
+use crate::{Link, BASE_URL};
+
+const TARGET: &'static str = "RANK_QUEUE_CONTROLLER";
+
+#[derive(Deserialize, ToSchema)]
+pub(super) struct QueueCheckIn<'r> {
+    taxi: &'r str,
+}
+
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", route = show(&self.rank_id, &self.taxi))]
+#[link_to(rel = "queue", route = index(&self.rank_id))]
+pub(super) struct QueueEntry {
+    taxi: String,
+    journey_id: String,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<i64>,
+    checked_in_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dispatched_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ended_at: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    rank_id: Uuid,
+    #[serde(rename = "_links")]
+    #[links]
+    links: HashMap<&'static str, Link>,
+}
+
+impl From<entity::QueueEntry> for QueueEntry {
+    fn from(value: entity::QueueEntry) -> Self {
+        QueueEntry {
+            journey_id: uri!(BASE_URL, journey::show(&value.taxi, &value.journey_id)).to_string(),
+            taxi: value.taxi,
+            status: value.status,
+            position: value.position,
+            checked_in_at: value.checked_in_at,
+            dispatched_at: value.dispatched_at,
+            ended_at: value.ended_at,
+            rank_id: value.rank_id,
+            links: HashMap::new(),
+        }
+        .linked()
+    }
+}
+
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", route = index(&self.rank_id))]
+pub(super) struct Queue {
+    taxi_rank: String,
+    entries: Vec<QueueEntry>,
+    #[serde(skip)]
+    rank_id: Uuid,
+    #[serde(rename = "_links")]
+    #[links]
+    links: HashMap<&'static str, Link>,
+}
+
+#[utoipa::path(
+    tag = "queues",
+    request_body = QueueCheckIn,
+    responses(
+        (status = 201, description = "The place of the taxi in the queue", body = QueueEntry, content_type = "application/hal+json"),
+        (status = 400, description = "The owner is not signed in", body = Failure),
+        (status = 404, description = "The rank is unknown", body = Failure),
+        (status = 409, description = "The taxi is already queued, or has no journey from the rank", body = Failure),
+    )
+)]
+#[post("/taxi-ranks/<id>/queue", data = "<data>")]
+async fn check_in(
+    id: Uuid,
+    data: Json<QueueCheckIn<'_>>,
+    cookies: &CookieJar<'_>,
+    bus: &State<EventBus>,
+    mut data_provider: QueueDataProvider,
+) -> Result<status::Created<Hal<QueueEntry>>, Error> {
+    User::authenticate(cookies, data.taxi).ok_or_else(Error::no_credentials)?;
+
+    let check_in = data_provider
+        .check_in_rank_queue(&id, data.taxi)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?;
+
+    match check_in {
+        CheckIn::Queued(entry, dispatched) => {
+            bus.journey_changed(&entry.journey_id, "queue_joined");
+            if let Some(journey_id) = dispatched {
+                bus.journey_changed(&journey_id, "journey_dispatched");
+            }
+
+            let location = uri!(BASE_URL, show(&entry.rank_id, &entry.taxi)).to_string();
+            Ok(status::Created::new(location).body(Hal(entry.into())))
+        }
+        CheckIn::AlreadyQueued(entry) => Err(Error::already_queued(&entry.taxi, &entry.rank_id)),
+        CheckIn::NoJourney => Err(Error::no_journey_from_stand(data.taxi, &id)),
+        CheckIn::InactiveRank => Err(Error::stand_inactive(&id)),
+        CheckIn::UnknownRank => Err(Error::unknown_stand(&id)),
+    }
+}
+
+#[utoipa::path(
+    tag = "queues",
+    responses(
+        (status = 200, description = "The taxis queued at the rank, first in first", body = Queue, content_type = "application/hal+json"),
+    )
+)]
+#[get("/taxi-ranks/<id>/queue")]
+async fn index(id: Uuid, mut data_provider: QueueDataProvider) -> Result<Hal<Queue>, Error> {
+    let entries = data_provider.get_rank_queue(&id).await.map_err(|err| {
+        error!(target: TARGET, "{err:?}");
+        Error::server_error()
+    })?;
+
+    Ok(Hal(Queue {
+        taxi_rank: uri!(BASE_URL, taxi_ranks::show(id)).to_string(),
+        entries: entries.into_iter().map(QueueEntry::from).collect(),
+        rank_id: id,
+        links: HashMap::new(),
+    }
+    .linked()))
+}
+
+#[utoipa::path(
+    tag = "queues",
+    responses(
+        (status = 200, description = "The place of the taxi in the queue", body = QueueEntry, content_type = "application/hal+json"),
+        (status = 400, description = "The owner is not signed in", body = Failure),
+        (status = 404, description = "The taxi is not queued at the rank", body = Failure),
+    )
+)]
+#[get("/taxi-ranks/<id>/queue/<taxi>")]
+async fn show(
+    id: Uuid,
+    taxi: &str,
+    cookies: &CookieJar<'_>,
+    mut data_provider: QueueDataProvider,
+) -> Result<Hal<QueueEntry>, Error> {
+    User::authenticate(cookies, taxi).ok_or_else(Error::no_credentials)?;
+
+    data_provider
+        .get_queue_entry(&id, taxi)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .map(|entry| Hal(entry.into()))
+        .ok_or_else(|| Error::unknown_queue_entry(taxi, &id))
+}
+
+#[utoipa::path(
+    tag = "queues",
+    responses(
+        (status = 204, description = "The taxi left the queue"),
+        (status = 400, description = "The owner is not signed in", body = Failure),
+        (status = 404, description = "The taxi is not queued at the rank", body = Failure),
+    )
+)]
+#[delete("/taxi-ranks/<id>/queue/<taxi>")]
+async fn leave(
+    id: Uuid,
+    taxi: &str,
+    cookies: &CookieJar<'_>,
+    bus: &State<EventBus>,
+    mut data_provider: QueueDataProvider,
+) -> Result<status::NoContent, Error> {
+    User::authenticate(cookies, taxi).ok_or_else(Error::no_credentials)?;
+
+    let exit = data_provider
+        .leave_rank_queue(&id, taxi)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .ok_or_else(|| Error::unknown_queue_entry(taxi, &id))?;
+
+    bus.journey_changed(&exit.entry.journey_id, "queue_left");
+    if let Some(journey_id) = exit.dispatched {
+        bus.journey_changed(&journey_id, "journey_dispatched");
+    }
+
+    Ok(status::NoContent)
+}
+
+pub fn routes() -> Vec<Route> {
+    routes![check_in, index, show, leave]
+}
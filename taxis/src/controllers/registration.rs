@@ -4,16 +4,20 @@ use log::error;
 use rocket::http::{Cookie, CookieJar};
 use rocket::response::status;
 use rocket::serde::json::Json;
-use rocket::{post, routes, uri, Route};
+use rocket::{post, routes, Route};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use resource_derive::Resource;
+
 use crate::controllers::user;
 use crate::controllers::Error;
 use crate::data::RegistrationDataProvider;
 use crate::entity::{Owner, Taxi};
+use crate::hal::{Hal, Resource};
 use crate::usecase::{AddTaxi, DoesTaxiExists};
-use crate::{password, Link, BASE_URL};
+use crate::{password, Link};
 
 const TARGET: &'static str = "REGISTRATION_CONTROLLER";
 
@@ -22,12 +26,18 @@ struct PartialTaxiData {
     number: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct AdditionalTaxiData {
     owner: String,
     password: String,
     car_brand: String,
     num_of_seats: u8,
+    #[serde(default)]
+    phone_number: Option<String>,
+    #[serde(default)]
+    email: Option<String>,
+    #[serde(default)]
+    language: Option<String>,
 }
 
 impl PartialTaxiData {
@@ -36,17 +46,23 @@ impl PartialTaxiData {
     }
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "next", route = finish(&self.id))]
 pub struct StartResponse {
     id: Uuid,
     #[serde(rename = "_links")]
+    #[links]
     links: HashMap<&'static str, Link>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", route = user::show(&self.taxi))]
 pub struct FinishResponse {
     id: Uuid,
+    #[serde(skip)]
+    taxi: String,
     #[serde(rename = "_links")]
+    #[links]
     links: HashMap<&'static str, Link>,
 }
 
@@ -54,13 +70,10 @@ impl FinishResponse {
     fn new(id: Uuid, num: String) -> Self {
         FinishResponse {
             id,
-            links: HashMap::from([(
-                "self",
-                Link {
-                    href: uri!(BASE_URL, user::show(num)).to_string(),
-                },
-            )]),
+            taxi: num,
+            links: HashMap::new(),
         }
+        .linked()
     }
 }
 
@@ -68,22 +81,28 @@ impl StartResponse {
     fn new(id: Uuid) -> Self {
         StartResponse {
             id,
-            links: HashMap::from([(
-                "next",
-                Link {
-                    href: uri!(BASE_URL, finish(&id)).to_string(),
-                },
-            )]),
+            links: HashMap::new(),
         }
+        .linked()
     }
 }
 
+#[utoipa::path(
+    tag = "registration",
+    params(
+        ("number" = String, Query, description = "The number plate of the taxi to register"),
+    ),
+    responses(
+        (status = 200, description = "The registration to complete", body = StartResponse, content_type = "application/hal+json"),
+        (status = 400, description = "The taxi is already registered", body = Failure),
+    )
+)]
 #[post("/taxis/registration?<number>")]
 pub async fn start(
     number: &str,
     cookies: &CookieJar<'_>,
     mut data_provider: RegistrationDataProvider,
-) -> Result<Json<StartResponse>, Error> {
+) -> Result<Hal<StartResponse>, Error> {
     data_provider
         .does_taxi_exists(number)
         .await
@@ -103,18 +122,26 @@ pub async fn start(
                     id.to_string(),
                     serde_json::to_string(&data).unwrap(),
                 ));
-                Ok(Json(StartResponse::new(id)))
+                Ok(Hal(StartResponse::new(id)))
             }
         })
 }
 
+#[utoipa::path(
+    tag = "registration",
+    request_body = AdditionalTaxiData,
+    responses(
+        (status = 201, description = "The registered taxi", body = FinishResponse, content_type = "application/hal+json"),
+        (status = 404, description = "The registration is unknown or expired", body = Failure),
+    )
+)]
 #[post("/taxis/registration/<id>/complete", data = "<data>")]
 pub async fn finish(
     id: Uuid,
     data: Json<AdditionalTaxiData>,
     mut data_provider: RegistrationDataProvider,
     cookies: &CookieJar<'_>,
-) -> Result<status::Created<Json<FinishResponse>>, Error> {
+) -> Result<status::Created<Hal<FinishResponse>>, Error> {
     let result = cookies
         .get_private(&id.to_string())
         .map(|c| {
@@ -122,12 +149,17 @@ pub async fn finish(
             cookies.remove_private(c);
             res
         })
-        .and_then(|pd| {
+        .map(|pd| {
             let taxi = Taxi::new(&pd.number, &data.car_brand, data.num_of_seats as i32);
 
-            let owner = Owner::new(&data.owner, &data.password);
+            let mut owner = Owner::new(&data.owner, &data.password);
+            owner.phone_number = data.phone_number.clone();
+            owner.email = data.email.clone();
+            if let Some(language) = &data.language {
+                owner.language = language.clone();
+            }
 
-            Some((taxi, owner))
+            (taxi, owner)
         });
 
     if let None = result {
@@ -151,7 +183,7 @@ pub async fn finish(
                     let s = taxi.number.to_lowercase();
 
                     cookies.add_private(Cookie::new(s.to_owned(), payload.to_string()));
-                    Ok(status::Created::new("").body(Json(FinishResponse::new(taxi.id, s))))
+                    Ok(status::Created::new("").body(Hal(FinishResponse::new(taxi.id, s))))
                 }
             }
         }
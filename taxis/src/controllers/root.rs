@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use rocket::{get, routes, uri, Route};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::controllers::login;
+use crate::controllers::taxi_ranks::{self, RankQuery};
+use crate::hal::Hal;
+use crate::{Link, BASE_URL};
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct Root {
+    #[serde(rename = "_links")]
+    links: HashMap<&'static str, Link>,
+}
+
+/// The entry point of the API, from which every other resource can be discovered.
+#[utoipa::path(
+    tag = "root",
+    responses(
+        (status = 200, description = "Links to the resources of the API", body = Root, content_type = "application/hal+json"),
+    )
+)]
+#[get("/")]
+fn index() -> Hal<Root> {
+    let taxi_ranks = uri!(BASE_URL, taxi_ranks::list_all(RankQuery::default()));
+
+    Hal(Root {
+        links: HashMap::from([
+            (
+                "self",
+                Link {
+                    href: uri!(BASE_URL, index()).to_string(),
+                },
+            ),
+            (
+                "tx:login",
+                Link {
+                    href: uri!(BASE_URL, login::index()).to_string(),
+                },
+            ),
+            (
+                "tx:registration",
+                Link {
+                    href: format!("{BASE_URL}/taxis/registration{{?number}}"),
+                },
+            ),
+            (
+                "tx:taxi-ranks",
+                Link {
+                    href: format!(
+                        "{taxi_ranks}{{?near,radius,include_inactive,name,city,sort,cursor,limit}}"
+                    ),
+                },
+            ),
+            (
+                "tx:search",
+                Link {
+                    href: format!("{BASE_URL}/search{{?from,to}}"),
+                },
+            ),
+        ]),
+    })
+}
+
+pub fn routes() -> Vec<Route> {
+    routes![index]
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use log::error;
+use resource_derive::Resource;
+use rocket::{get, routes, uri, Route};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::controllers::fares::{self, Price};
+use crate::controllers::Error;
+use crate::controllers::{journey, seats, taxi_ranks};
+use crate::data::SearchDataProvider;
+use crate::hal::{Hal, Resource};
+use crate::usecase::SearchJourneys;
+use crate::{entity, Link, BASE_URL};
+
+const TARGET: &'static str = "SEARCH_CONTROLLER";
+
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", route = index(self.from, self.to))]
+pub(super) struct SearchResults {
+    trips: Vec<AvailableJourney>,
+    #[serde(skip)]
+    from: Uuid,
+    #[serde(skip)]
+    to: Uuid,
+    #[serde(rename = "_links")]
+    #[links]
+    links: HashMap<&'static str, Link>,
+}
+
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", route = journey::show(&self.taxi, &self.id))]
+#[link_to(
+    rel = "quote",
+    route = fares::quote(&self.origin, &self.destination, Some(1u8))
+)]
+#[link_to(rel = "seats", route = seats::index(&self.id, _, _))]
+pub(super) struct AvailableJourney {
+    id: Uuid,
+    taxi: String,
+    brand: String,
+    departure_id: String,
+    arrival_id: String,
+    departure_schedule: DateTime<Utc>,
+    available_seats: i32,
+    price: Option<Price>,
+    #[serde(skip)]
+    origin: Uuid,
+    #[serde(skip)]
+    destination: Uuid,
+    #[serde(rename = "_links")]
+    #[links]
+    links: HashMap<&'static str, Link>,
+}
+
+impl From<entity::SearchedJourney> for AvailableJourney {
+    fn from(value: entity::SearchedJourney) -> Self {
+        AvailableJourney {
+            price: value.fare().map(Price::from),
+            id: value.id,
+            departure_id: uri!(BASE_URL, taxi_ranks::show(value.origin)).to_string(),
+            arrival_id: uri!(BASE_URL, taxi_ranks::show(value.destination)).to_string(),
+            departure_schedule: value.departure_schedule,
+            available_seats: value.available_seats,
+            taxi: value.taxi,
+            brand: value.brand,
+            origin: value.origin,
+            destination: value.destination,
+            links: HashMap::new(),
+        }
+        .linked()
+    }
+}
+
+#[utoipa::path(
+    tag = "search",
+    params(
+        ("from" = Uuid, Query, description = "The rank to leave from"),
+        ("to" = Uuid, Query, description = "The rank to go to"),
+    ),
+    responses(
+        (status = 200, description = "The open trips from a rank to another, with their free seats", body = SearchResults, content_type = "application/hal+json"),
+    )
+)]
+#[get("/search?<from>&<to>")]
+async fn index(
+    from: Uuid,
+    to: Uuid,
+    mut data_provider: SearchDataProvider,
+) -> Result<Hal<SearchResults>, Error> {
+    data_provider
+        .search_journeys(&from, &to)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })
+        .map(|trips| {
+            Hal(SearchResults {
+                trips: trips.into_iter().map(AvailableJourney::from).collect(),
+                from,
+                to,
+                links: HashMap::new(),
+            }
+            .linked())
+        })
+}
+
+pub fn routes() -> Vec<Route> {
+    routes![index]
+}
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use log::error;
+use resource_derive::Resource;
+use rocket::{get, routes, Route};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::controllers::Error;
+use crate::data::{JourneyDataProvider, SeatDataProvider};
+use crate::entity::{self, seat_label};
+use crate::hal::{Hal, Resource};
+use crate::usecase::{GetJourneyStops, GetSeatMap};
+use crate::Link;
+
+const TARGET: &'static str = "SEATS_CONTROLLER";
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct Seat {
+    number: i32,
+    label: String,
+    available: bool,
+}
+
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", route = index(&self.journey_id, _, _))]
+pub(super) struct SeatMap {
+    journey_id: Uuid,
+    number_of_seats: i32,
+    available_seats: usize,
+    seats: Vec<Seat>,
+    #[serde(rename = "_links")]
+    #[links]
+    links: HashMap<&'static str, Link>,
+}
+
+impl From<entity::SeatMap> for SeatMap {
+    fn from(value: entity::SeatMap) -> Self {
+        let seats: Vec<Seat> = (1..=value.number_of_seats)
+            .map(|number| Seat {
+                number,
+                label: seat_label(number),
+                available: !value.taken.contains(&number),
+            })
+            .collect();
+
+        SeatMap {
+            available_seats: seats.iter().filter(|s| s.available).count(),
+            journey_id: value.journey_id,
+            number_of_seats: value.number_of_seats,
+            seats,
+            links: HashMap::new(),
+        }
+        .linked()
+    }
+}
+
+#[utoipa::path(
+    tag = "bookings",
+    params(
+        ("departure" = Option<Uuid>, Query, description = "The rank boarded at, along with `arrival`, for the seats free on that leg only"),
+        ("arrival" = Option<Uuid>, Query, description = "The rank alighted at"),
+    ),
+    responses(
+        (status = 200, description = "The seats of the trip", body = SeatMap, content_type = "application/hal+json"),
+        (status = 400, description = "The trip does not call at the ranks in that order", body = Failure),
+        (status = 404, description = "The trip is unknown", body = Failure),
+    )
+)]
+#[get("/trips/<id>/seats?<departure>&<arrival>")]
+async fn index(
+    id: Uuid,
+    departure: Option<Uuid>,
+    arrival: Option<Uuid>,
+    mut journeys: JourneyDataProvider,
+    mut data_provider: SeatDataProvider,
+) -> Result<Hal<SeatMap>, Error> {
+    // Without both ends, a seat is shown taken if it is on any leg of the journey.
+    let segment = match (departure, arrival) {
+        (Some(departure), Some(arrival)) => {
+            let stops = journeys.get_journey_stops(&id).await.map_err(|err| {
+                error!(target: TARGET, "{err:?}");
+                Error::server_error()
+            })?;
+            let segment = entity::Segment::between(&stops, &departure, &arrival)
+                .ok_or_else(|| Error::invalid_segment(&id))?;
+            Some(segment)
+        }
+        _ => None,
+    };
+
+    data_provider
+        .get_seat_map(&id, segment.as_ref())
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .map(|map| Hal(SeatMap::from(map)))
+        .ok_or_else(|| Error::unknown_journey(&id))
+}
+
+pub fn routes() -> Vec<Route> {
+    routes![index]
+}
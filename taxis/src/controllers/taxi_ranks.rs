@@ -1,59 +1,270 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use log::error;
+use rocket::response::status;
 use rocket::serde::json::Json;
 use rocket::serde::uuid::Uuid;
-use rocket::{get, post, routes, uri, Route};
-use serde::{Deserialize, Serialize};
+use rocket::{delete, get, patch, post, routes, uri, FromForm, Route, State, UriDisplayQuery};
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use resource_derive::Resource;
 
 use crate::controllers::Error;
+use crate::controllers::{pagination, rank_queue};
 use crate::data::StandDataProvider;
-use crate::usecase::{AddTaxiRanks, GetAllStand, GetStand};
+use crate::geo::Coordinates;
+use crate::hal::{Hal, Resource};
+use crate::metrics::Metrics;
+use crate::usecase::{
+    AddTaxiRanks, DeleteStand, GetAllStand, GetStand, GetStandsWithin, UpdateStand,
+};
 use crate::{entity, Link, BASE_URL};
 
 const TARGET: &'static str = "TAXI_RANKS_CONTROLLER";
 
-#[derive(Serialize)]
-struct StandList {
+const DEFAULT_RADIUS_KM: f64 = 5.0;
+const MAX_RADIUS_KM: f64 = 100.0;
+const MAX_SERVICE_RADIUS_METERS: i32 = 50_000;
+const MAX_NAME_LENGTH: usize = 80;
+
+#[derive(Clone, Copy, Default, FromForm, UriDisplayQuery, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct RankQuery<'r> {
+    /// A `latitude,longitude` position to search around
+    near: Option<&'r str>,
+    /// Search radius around `near`, in kilometres
+    radius: Option<f64>,
+    /// Whether the ranks out of service are listed too, `false` by default
+    include_inactive: Option<bool>,
+    /// Prefix of the names, regardless of case
+    name: Option<&'r str>,
+    /// The city of the ranks, regardless of case
+    city: Option<&'r str>,
+    /// `name` or `city`, descending with a leading `-`
+    sort: Option<&'r str>,
+    /// The cursor of a `next` or `prev` link
+    cursor: Option<&'r str>,
+    /// Number of ranks per page, at most 100
+    limit: Option<usize>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct StandList {
+    taxi_ranks: Vec<Stand>,
+    #[serde(rename = "_links")]
+    links: HashMap<&'static str, Link>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub(super) struct AddedStands {
     taxi_ranks: Vec<Stand>,
+    /// Requested names already used by a rank, regardless of case.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    existing: Vec<Stand>,
     #[serde(rename = "_links")]
     links: HashMap<&'static str, Link>,
 }
 
-impl Into<StandList> for Vec<entity::Stand> {
-    fn into(self) -> StandList {
-        StandList {
-            taxi_ranks: self.into_iter().map(Stand::from).collect(),
+impl From<entity::AddedStands> for AddedStands {
+    fn from(value: entity::AddedStands) -> Self {
+        AddedStands {
+            taxi_ranks: value.created.into_iter().map(Stand::from).collect(),
+            existing: value.existing.into_iter().map(Stand::from).collect(),
             links: links_for_taxi_ranks(),
         }
     }
 }
 
-#[derive(Serialize)]
-struct Stand {
+#[derive(Serialize, ToSchema)]
+pub(super) struct Stand {
     id: String,
     name: String,
+    active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    latitude: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    longitude: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    radius_meters: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distance_km: Option<f64>,
 }
 
-#[derive(Serialize)]
-struct StandResponse {
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "taxi_ranks", route = list_all(RankQuery::default()))]
+#[link_to(rel = "self", route = show(self.id))]
+#[link_to(rel = "queue", route = rank_queue::index(self.id))]
+pub(super) struct StandResponse {
+    #[serde(skip)]
+    id: Uuid,
     stand: Stand,
     #[serde(rename = "_links")]
+    #[links]
     links: HashMap<&'static str, Link>,
 }
 
 impl Into<StandResponse> for entity::Stand {
     fn into(self) -> StandResponse {
         StandResponse {
-            links: links_for_stand(&self.id),
+            id: self.id,
             stand: Stand::from(self),
+            links: HashMap::new(),
+        }
+        .linked()
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub(super) struct NewTaxiRanks {
+    taxi_ranks: Vec<NewTaxiRank>,
+}
+
+/// A rank is either given by its sole name, or with its location.
+#[derive(Deserialize, ToSchema)]
+#[serde(untagged)]
+pub(super) enum NewTaxiRank {
+    Name(String),
+    Detailed {
+        name: String,
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+        address: Option<String>,
+        city: Option<String>,
+        radius_meters: Option<i32>,
+    },
+}
+
+impl TryFrom<NewTaxiRank> for entity::NewStand {
+    type Error = Error;
+
+    fn try_from(value: NewTaxiRank) -> Result<Self, Self::Error> {
+        match value {
+            NewTaxiRank::Name(name) => Ok(entity::NewStand {
+                name: text("name", name, MAX_NAME_LENGTH)?,
+                coordinates: None,
+                address: None,
+                city: None,
+                radius_meters: None,
+            }),
+            NewTaxiRank::Detailed {
+                name,
+                latitude,
+                longitude,
+                address,
+                city,
+                radius_meters,
+            } => Ok(entity::NewStand {
+                coordinates: coordinates(latitude, longitude)?,
+                address: address.map(|a| text("address", a, 255)).transpose()?,
+                city: city.map(|c| text("city", c, 120)).transpose()?,
+                radius_meters: radius_meters.map(service_radius).transpose()?,
+                name: text("name", name, MAX_NAME_LENGTH)?,
+            }),
         }
     }
 }
 
-#[derive(Deserialize)]
-struct NewTaxiRanks {
-    taxi_ranks: Vec<String>,
+/// Absent fields are left untouched, `null` ones are cleared.
+#[derive(Deserialize, ToSchema)]
+pub(super) struct TaxiRankChanges {
+    name: Option<String>,
+    active: Option<bool>,
+    #[serde(default, deserialize_with = "nullable")]
+    latitude: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    longitude: Option<Option<f64>>,
+    #[serde(default, deserialize_with = "nullable")]
+    address: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    city: Option<Option<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    radius_meters: Option<Option<i32>>,
+}
+
+impl TryFrom<TaxiRankChanges> for entity::StandChanges {
+    type Error = Error;
+
+    fn try_from(value: TaxiRankChanges) -> Result<Self, Self::Error> {
+        let coordinates = match (value.latitude, value.longitude) {
+            (None, None) => None,
+            (Some(latitude), Some(longitude)) => Some(coordinates(latitude, longitude)?),
+            _ => {
+                return Err(Error::invalid_stand(String::from(
+                    "The latitude and the longitude must be changed together",
+                )))
+            }
+        };
+
+        Ok(entity::StandChanges {
+            name: value
+                .name
+                .map(|n| text("name", n, MAX_NAME_LENGTH))
+                .transpose()?,
+            active: value.active,
+            coordinates,
+            address: value
+                .address
+                .map(|a| a.map(|a| text("address", a, 255)).transpose())
+                .transpose()?,
+            city: value
+                .city
+                .map(|c| c.map(|c| text("city", c, 120)).transpose())
+                .transpose()?,
+            radius_meters: value
+                .radius_meters
+                .map(|r| r.map(service_radius).transpose())
+                .transpose()?,
+        })
+    }
+}
+
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+fn coordinates(
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+) -> Result<Option<Coordinates>, Error> {
+    match (latitude, longitude) {
+        (None, None) => Ok(None),
+        (Some(latitude), Some(longitude)) => Coordinates::new(latitude, longitude)
+            .map(Some)
+            .ok_or_else(|| Error::invalid_stand(String::from("The coordinates are out of range"))),
+        _ => Err(Error::invalid_stand(String::from(
+            "The latitude and the longitude must be given together",
+        ))),
+    }
+}
+
+fn text(field: &str, value: String, max: usize) -> Result<String, Error> {
+    let value = value.trim();
+    if value.is_empty() || value.chars().count() > max {
+        return Err(Error::invalid_stand(format!(
+            "The {field} must have between 1 and {max} characters"
+        )));
+    }
+
+    Ok(value.to_string())
+}
+
+fn service_radius(radius_meters: i32) -> Result<i32, Error> {
+    if !(1..=MAX_SERVICE_RADIUS_METERS).contains(&radius_meters) {
+        return Err(Error::invalid_stand(format!(
+            "The radius must be between 1 and {MAX_SERVICE_RADIUS_METERS} meters"
+        )));
+    }
+
+    Ok(radius_meters)
 }
 
 impl From<entity::Stand> for Stand {
@@ -61,42 +272,153 @@ impl From<entity::Stand> for Stand {
         Stand {
             id: uri!(BASE_URL, show(value.id)).to_string(),
             name: value.name,
+            active: value.active,
+            latitude: value.latitude,
+            longitude: value.longitude,
+            address: value.address,
+            city: value.city,
+            radius_meters: value.radius_meters,
+            distance_km: None,
         }
     }
 }
 
+#[utoipa::path(
+    tag = "taxi-ranks",
+    request_body = NewTaxiRanks,
+    responses(
+        (status = 200, description = "The created ranks, and those which already existed", body = AddedStands, content_type = "application/hal+json"),
+        (status = 400, description = "A rank is invalid", body = Failure),
+    )
+)]
 #[post("/taxi-ranks", data = "<data>")]
 async fn add_taxi_ranks(
     data: Json<NewTaxiRanks>,
+    metrics: &State<Metrics>,
     mut data_provider: StandDataProvider,
-) -> Result<Json<StandList>, Error> {
+) -> Result<Hal<AddedStands>, Error> {
+    let mut stands = data
+        .into_inner()
+        .taxi_ranks
+        .into_iter()
+        .map(entity::NewStand::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut names = HashSet::new();
+    stands.retain(|stand| names.insert(stand.name.to_lowercase()));
+
     data_provider
-        .add_taxi_ranks(data.0.taxi_ranks)
+        .add_taxi_ranks(stands)
         .await
         .map_err(|err| {
             error!(target: TARGET, "{err:?}");
             Error::server_error()
         })
-        .map(|ranks| Json(ranks.into()))
+        .map(|ranks| {
+            metrics.ranks_created(ranks.created.len());
+            Hal(ranks.into())
+        })
 }
 
-#[get("/taxi-ranks")]
-async fn list_all(mut data_provider: StandDataProvider) -> Result<Json<StandList>, Error> {
-    data_provider
-        .get_all_stand()
+/// Lists the ranks by pages, unless `near` is given to search the closest ones.
+#[utoipa::path(
+    tag = "taxi-ranks",
+    responses(
+        (status = 200, description = "A page of ranks, or the closest ones", body = StandList, content_type = "application/hal+json"),
+        (status = 400, description = "The query is invalid", body = Failure),
+    )
+)]
+#[get("/taxi-ranks?<query..>")]
+async fn list_all(
+    query: RankQuery<'_>,
+    mut data_provider: StandDataProvider,
+) -> Result<Hal<StandList>, Error> {
+    if let Some(near) = query.near {
+        return nearest(near, query.radius, data_provider).await;
+    }
+    if query.radius.is_some() {
+        return Err(Error::invalid_stand(String::from(
+            "A radius requires a `near` position",
+        )));
+    }
+
+    let filter = entity::StandFilter {
+        name_prefix: query
+            .name
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(String::from),
+        city: query
+            .city
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(String::from),
+        include_inactive: query.include_inactive.unwrap_or(false),
+        sort: pagination::sort(
+            query.sort,
+            entity::Sort {
+                field: entity::StandSortField::Name,
+                order: entity::SortOrder::Ascending,
+            },
+            |field| match field {
+                "name" => Some(entity::StandSortField::Name),
+                "city" => Some(entity::StandSortField::City),
+                _ => None,
+            },
+        )?,
+    };
+    let page = pagination::page_request(query.cursor, query.limit, |key| {
+        filter.sort.field.accepts(key)
+    })?;
+
+    let stands = data_provider
+        .get_all_stand(&filter, &page)
         .await
         .map_err(|err| {
             error!(target: TARGET, "{err:?}");
             Error::server_error()
-        })
-        .map(|items| Json(items.into()))
+        })?;
+
+    let mut links = HashMap::from([(
+        "self",
+        Link {
+            href: uri!(BASE_URL, list_all(query)).to_string(),
+        },
+    )]);
+    for (rel, cursor) in [("next", &stands.next), ("prev", &stands.prev)] {
+        if let Some(cursor) = cursor {
+            let cursor = pagination::encode(cursor);
+            links.insert(
+                rel,
+                Link {
+                    href: uri!(
+                        BASE_URL,
+                        list_all(RankQuery {
+                            cursor: Some(cursor.as_str()),
+                            ..query
+                        })
+                    )
+                    .to_string(),
+                },
+            );
+        }
+    }
+
+    Ok(Hal(StandList {
+        taxi_ranks: stands.items.into_iter().map(Stand::from).collect(),
+        links,
+    }))
 }
 
+#[utoipa::path(
+    tag = "taxi-ranks",
+    responses(
+        (status = 200, description = "The rank", body = StandResponse, content_type = "application/hal+json"),
+        (status = 404, description = "The rank is unknown", body = Failure),
+    )
+)]
 #[get("/taxi-ranks/<id>")]
-async fn show(
-    mut data_provider: StandDataProvider,
-    id: Uuid,
-) -> Result<Json<StandResponse>, Error> {
+async fn show(mut data_provider: StandDataProvider, id: Uuid) -> Result<Hal<StandResponse>, Error> {
     data_provider
         .get_stand(&id)
         .await
@@ -106,36 +428,134 @@ async fn show(
         })
         .and_then(|s| match s {
             None => Err(Error::unknown_stand(&id)),
-            Some(r) => Ok(Json(r.into())),
+            Some(r) => Ok(Hal(r.into())),
+        })
+}
+
+#[utoipa::path(
+    tag = "taxi-ranks",
+    request_body = TaxiRankChanges,
+    responses(
+        (status = 200, description = "The updated rank", body = StandResponse, content_type = "application/hal+json"),
+        (status = 400, description = "A change is invalid", body = Failure),
+        (status = 404, description = "The rank is unknown", body = Failure),
+        (status = 409, description = "Another rank has the name", body = Failure),
+    )
+)]
+#[patch("/taxi-ranks/<id>", data = "<data>")]
+async fn update(
+    id: Uuid,
+    data: Json<TaxiRankChanges>,
+    mut data_provider: StandDataProvider,
+) -> Result<Hal<StandResponse>, Error> {
+    let changes = entity::StandChanges::try_from(data.into_inner())?;
+
+    let update = data_provider
+        .update_stand(&id, &changes)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?;
+
+    match update {
+        entity::StandUpdate::Updated(stand) => Ok(Hal(stand.into())),
+        entity::StandUpdate::NameTaken => Err(Error::stand_exists(
+            changes.name.as_deref().unwrap_or_default(),
+        )),
+        entity::StandUpdate::Unknown => Err(Error::unknown_stand(&id)),
+    }
+}
+
+/// Only ranks no trip ever used can be deleted, the others are to be deactivated.
+#[utoipa::path(
+    tag = "taxi-ranks",
+    responses(
+        (status = 204, description = "The rank is deleted"),
+        (status = 404, description = "The rank is unknown", body = Failure),
+        (status = 409, description = "A trip used the rank", body = Failure),
+    )
+)]
+#[delete("/taxi-ranks/<id>")]
+async fn remove(
+    id: Uuid,
+    mut data_provider: StandDataProvider,
+) -> Result<status::NoContent, Error> {
+    let deletion = data_provider.delete_stand(&id).await.map_err(|err| {
+        error!(target: TARGET, "{err:?}");
+        Error::server_error()
+    })?;
+
+    match deletion {
+        entity::StandDeletion::Deleted => Ok(status::NoContent),
+        entity::StandDeletion::InUse => Err(Error::stand_in_use(&id)),
+        entity::StandDeletion::Unknown => Err(Error::unknown_stand(&id)),
+    }
+}
+
+/// The located ranks within `radius` kilometres of `near`, closest first. A rank limited to a
+/// service radius is left out when the position is beyond it.
+async fn nearest(
+    near: &str,
+    radius: Option<f64>,
+    mut data_provider: StandDataProvider,
+) -> Result<Hal<StandList>, Error> {
+    let centre = near
+        .split_once(',')
+        .and_then(|(lat, lng)| Some((lat.trim().parse().ok()?, lng.trim().parse().ok()?)))
+        .and_then(|(lat, lng)| Coordinates::new(lat, lng))
+        .ok_or_else(|| {
+            Error::invalid_stand(String::from("`near` must be given as `latitude,longitude`"))
+        })?;
+
+    let radius = radius.unwrap_or(DEFAULT_RADIUS_KM);
+    if !(radius > 0.0 && radius <= MAX_RADIUS_KM) {
+        return Err(Error::invalid_stand(format!(
+            "The radius must be greater than 0 and at most {MAX_RADIUS_KM} km"
+        )));
+    }
+
+    let stands = data_provider
+        .get_stands_within(&centre.bounding_box(radius))
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?;
+
+    let mut ranks = stands
+        .into_iter()
+        .filter_map(|stand| {
+            let distance = centre.distance_km(&stand.coordinates()?);
+            let served = stand
+                .radius_meters
+                .is_none_or(|r| distance * 1000.0 <= f64::from(r));
+            (distance <= radius && served).then_some((distance, stand))
         })
+        .collect::<Vec<_>>();
+    ranks.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    Ok(Hal(StandList {
+        taxi_ranks: ranks
+            .into_iter()
+            .map(|(distance, stand)| Stand {
+                distance_km: Some((distance * 1000.0).round() / 1000.0),
+                ..Stand::from(stand)
+            })
+            .collect(),
+        links: links_for_taxi_ranks(),
+    }))
 }
 
 pub fn routes() -> Vec<Route> {
-    routes![list_all, show, add_taxi_ranks]
+    routes![list_all, show, add_taxi_ranks, update, remove]
 }
 
 fn links_for_taxi_ranks() -> HashMap<&'static str, Link> {
     HashMap::from([(
         "self",
         Link {
-            href: uri!(BASE_URL, list_all()).to_string(),
+            href: uri!(BASE_URL, list_all(RankQuery::default())).to_string(),
         },
     )])
 }
-
-fn links_for_stand(id: &Uuid) -> HashMap<&'static str, Link> {
-    HashMap::from([
-        (
-            "taxi_ranks",
-            Link {
-                href: uri!(BASE_URL, list_all()).to_string(),
-            },
-        ),
-        (
-            "self",
-            Link {
-                href: uri!(BASE_URL, show(id)).to_string(),
-            },
-        ),
-    ])
-}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use log::error;
+use resource_derive::Resource;
+use rocket::http::ContentType;
+use rocket::{get, routes, uri, Route, State};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::controllers::{bookings, journey, Error};
+use crate::data::BookingDataProvider;
+use crate::entity::{self, BookingStatus};
+use crate::guards::Passenger;
+use crate::hal::{Hal, Resource};
+use crate::ticket::{qr, TicketClaims, TicketSigner};
+use crate::usecase::GetBooking;
+use crate::{Link, BASE_URL};
+
+const TARGET: &'static str = "TICKETS_CONTROLLER";
+const PNG_SCALE: u32 = 8;
+
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", route = show(&self.booking_id))]
+#[link_to(rel = "booking", route = bookings::show(&self.booking_id))]
+#[link_to(rel = "qr_svg", route = qr_svg(&self.booking_id))]
+#[link_to(rel = "qr_png", route = qr_png(&self.booking_id))]
+pub(super) struct Ticket {
+    booking_id: Uuid,
+    journey_id: String,
+    taxi: String,
+    seats: i32,
+    seat_labels: Vec<String>,
+    departure_schedule: DateTime<Utc>,
+    payload: String,
+    #[serde(rename = "_links")]
+    #[links]
+    links: HashMap<&'static str, Link>,
+}
+
+impl Ticket {
+    fn new(booking: entity::Booking, payload: String) -> Self {
+        Ticket {
+            journey_id: uri!(
+                BASE_URL,
+                journey::show(booking.taxi.as_str(), &booking.journey_id)
+            )
+            .to_string(),
+            booking_id: booking.id,
+            taxi: booking.taxi,
+            seats: booking.reserved_seats,
+            seat_labels: booking
+                .seat_numbers
+                .iter()
+                .map(|n| entity::seat_label(*n))
+                .collect(),
+            departure_schedule: booking.departure_schedule,
+            payload,
+            links: HashMap::new(),
+        }
+        .linked()
+    }
+}
+
+async fn issue(
+    id: &Uuid,
+    signer: &TicketSigner,
+    data_provider: &mut BookingDataProvider,
+) -> Result<(entity::Booking, String), Error> {
+    let booking = data_provider
+        .get_booking(id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .ok_or_else(|| Error::unknown_booking(id))?;
+
+    if booking.status != BookingStatus::Confirmed.as_str() {
+        return Err(Error::ticket_unavailable(id));
+    }
+
+    let seats = u8::try_from(booking.reserved_seats).map_err(|err| {
+        error!(
+            target: TARGET,
+            "booking `{id}` holds {} seats, more than a ticket carries\n{err:?}",
+            booking.reserved_seats
+        );
+        Error::server_error()
+    })?;
+    let payload = signer.issue(&TicketClaims {
+        booking_id: booking.id,
+        journey_id: booking.journey_id,
+        seats,
+    });
+
+    Ok((booking, payload))
+}
+
+#[utoipa::path(
+    tag = "tickets",
+    responses(
+        (status = 200, description = "The signed ticket of a confirmed booking", body = Ticket, content_type = "application/hal+json"),
+        (status = 404, description = "The booking is unknown", body = Failure),
+        (status = 409, description = "The booking is not confirmed", body = Failure),
+    )
+)]
+#[get("/bookings/<id>/ticket")]
+async fn show(
+    id: Uuid,
+    passenger: Result<Passenger, Error>,
+    signer: &State<TicketSigner>,
+    mut data_provider: BookingDataProvider,
+) -> Result<Hal<Ticket>, Error> {
+    passenger?;
+
+    let (booking, payload) = issue(&id, signer, &mut data_provider).await?;
+    Ok(Hal(Ticket::new(booking, payload)))
+}
+
+#[utoipa::path(
+    tag = "tickets",
+    responses(
+        (status = 200, description = "The ticket as a QR code", body = String, content_type = "image/svg+xml"),
+        (status = 404, description = "The booking is unknown", body = Failure),
+        (status = 409, description = "The booking is not confirmed", body = Failure),
+    )
+)]
+#[get("/bookings/<id>/ticket/qr.svg")]
+async fn qr_svg(
+    id: Uuid,
+    passenger: Result<Passenger, Error>,
+    signer: &State<TicketSigner>,
+    mut data_provider: BookingDataProvider,
+) -> Result<(ContentType, String), Error> {
+    passenger?;
+
+    let (_, payload) = issue(&id, signer, &mut data_provider).await?;
+    let svg = qr::svg(&payload).map_err(|err| {
+        error!(target: TARGET, "{err:?}");
+        Error::server_error()
+    })?;
+
+    Ok((ContentType::SVG, svg))
+}
+
+#[utoipa::path(
+    tag = "tickets",
+    responses(
+        (status = 200, description = "The ticket as a QR code", body = [u8], content_type = "image/png"),
+        (status = 404, description = "The booking is unknown", body = Failure),
+        (status = 409, description = "The booking is not confirmed", body = Failure),
+    )
+)]
+#[get("/bookings/<id>/ticket/qr.png")]
+async fn qr_png(
+    id: Uuid,
+    passenger: Result<Passenger, Error>,
+    signer: &State<TicketSigner>,
+    mut data_provider: BookingDataProvider,
+) -> Result<(ContentType, Vec<u8>), Error> {
+    passenger?;
+
+    let (_, payload) = issue(&id, signer, &mut data_provider).await?;
+    let png = qr::png(&payload, PNG_SCALE).map_err(|err| {
+        error!(target: TARGET, "{err:?}");
+        Error::server_error()
+    })?;
+
+    Ok((ContentType::PNG, png))
+}
+
+pub fn routes() -> Vec<Route> {
+    routes![show, qr_svg, qr_png]
+}
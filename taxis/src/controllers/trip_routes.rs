@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+
+use log::error;
+use resource_derive::Resource;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, patch, post, routes, uri, Route};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::controllers::taxi_ranks;
+use crate::controllers::Error;
+use crate::data::{RouteDataProvider, StandDataProvider};
+use crate::entity;
+use crate::hal::{Hal, Resource};
+use crate::usecase::{AddRoute, GetRoute, GetRoutes, GetStand, UpdateRoute};
+use crate::{Link, BASE_URL};
+
+const TARGET: &'static str = "ROUTES_CONTROLLER";
+
+#[derive(Deserialize, ToSchema)]
+pub(super) struct NewRoute<'r> {
+    departure_id: &'r str,
+    arrival_id: &'r str,
+    distance_km: f64,
+    duration_minutes: i32,
+    #[serde(default)]
+    active: Option<bool>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub(super) struct RouteChanges {
+    distance_km: Option<f64>,
+    duration_minutes: Option<i32>,
+    active: Option<bool>,
+}
+
+impl TryFrom<RouteChanges> for entity::RouteChanges {
+    type Error = Error;
+
+    fn try_from(value: RouteChanges) -> Result<Self, Self::Error> {
+        if let Some(distance_km) = value.distance_km {
+            check_distance(distance_km)?;
+        }
+        if let Some(duration_minutes) = value.duration_minutes {
+            check_duration(duration_minutes)?;
+        }
+
+        Ok(entity::RouteChanges {
+            distance_km: value.distance_km,
+            duration_minutes: value.duration_minutes,
+            active: value.active,
+        })
+    }
+}
+
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", href = self.id.clone())]
+#[link_to(rel = "routes", route = list(_, _, _))]
+pub(super) struct TripRoute {
+    id: String,
+    departure_id: String,
+    arrival_id: String,
+    distance_km: f64,
+    duration_minutes: i32,
+    active: bool,
+    #[serde(rename = "_links")]
+    #[links]
+    links: HashMap<&'static str, Link>,
+}
+
+impl From<entity::Route> for TripRoute {
+    fn from(value: entity::Route) -> Self {
+        TripRoute {
+            id: uri!(BASE_URL, show(&value.id)).to_string(),
+            departure_id: uri!(BASE_URL, taxi_ranks::show(value.origin)).to_string(),
+            arrival_id: uri!(BASE_URL, taxi_ranks::show(value.destination)).to_string(),
+            distance_km: value.distance_km,
+            duration_minutes: value.duration_minutes,
+            active: value.active,
+            links: HashMap::new(),
+        }
+        .linked()
+    }
+}
+
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", route = list(self.departure, self.arrival, self.active))]
+pub(super) struct RouteList {
+    routes: Vec<TripRoute>,
+    /// The filters of the listing.
+    #[serde(skip)]
+    departure: Option<Uuid>,
+    #[serde(skip)]
+    arrival: Option<Uuid>,
+    #[serde(skip)]
+    active: Option<bool>,
+    #[serde(rename = "_links")]
+    #[links]
+    links: HashMap<&'static str, Link>,
+}
+
+#[utoipa::path(
+    tag = "routes",
+    request_body = NewRoute,
+    responses(
+        (status = 201, description = "The route added to the catalogue", body = TripRoute, content_type = "application/hal+json"),
+        (status = 400, description = "The route is invalid", body = Failure),
+        (status = 404, description = "A rank is unknown", body = Failure),
+        (status = 409, description = "The route already exists, or a rank is no longer in service", body = Failure),
+    )
+)]
+#[post("/routes", data = "<data>")]
+async fn create(
+    data: Json<NewRoute<'_>>,
+    mut stands: StandDataProvider,
+    mut data_provider: RouteDataProvider,
+) -> Result<status::Created<Hal<TripRoute>>, Error> {
+    let origin = rank_id(data.departure_id)?;
+    let destination = rank_id(data.arrival_id)?;
+    if origin == destination {
+        return Err(Error::invalid_route(String::from(
+            "The departure and the arrival must be different",
+        )));
+    }
+    check_distance(data.distance_km)?;
+    check_duration(data.duration_minutes)?;
+
+    for id in [&origin, &destination] {
+        let stand = stands
+            .get_stand(id)
+            .await
+            .map_err(|err| {
+                error!(target: TARGET, "{err:?}");
+                Error::server_error()
+            })?
+            .ok_or_else(|| Error::unknown_stand(id))?;
+        if !stand.active {
+            return Err(Error::stand_inactive(id));
+        }
+    }
+
+    let route = entity::NewRoute {
+        origin,
+        destination,
+        distance_km: data.distance_km,
+        duration_minutes: data.duration_minutes,
+        active: data.active.unwrap_or(true),
+    };
+
+    let route = data_provider
+        .add_route(&route)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .ok_or_else(|| Error::route_exists(&origin, &destination))?;
+
+    let location = uri!(BASE_URL, show(&route.id)).to_string();
+    Ok(status::Created::new(location).body(Hal(route.into())))
+}
+
+#[utoipa::path(
+    tag = "routes",
+    params(
+        ("departure" = Option<Uuid>, Query, description = "The rank the routes leave from"),
+        ("arrival" = Option<Uuid>, Query, description = "The rank the routes go to"),
+        ("active" = Option<bool>, Query, description = "Whether the routes are in service"),
+    ),
+    responses(
+        (status = 200, description = "The routes of the catalogue", body = RouteList, content_type = "application/hal+json"),
+    )
+)]
+#[get("/routes?<departure>&<arrival>&<active>")]
+async fn list(
+    departure: Option<Uuid>,
+    arrival: Option<Uuid>,
+    active: Option<bool>,
+    mut data_provider: RouteDataProvider,
+) -> Result<Hal<RouteList>, Error> {
+    let filter = entity::RouteFilter {
+        origin: departure,
+        destination: arrival,
+        active,
+    };
+
+    data_provider
+        .get_routes(&filter)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })
+        .map(|routes| {
+            Hal(RouteList {
+                routes: routes.into_iter().map(TripRoute::from).collect(),
+                departure,
+                arrival,
+                active,
+                links: HashMap::new(),
+            }
+            .linked())
+        })
+}
+
+#[utoipa::path(
+    tag = "routes",
+    responses(
+        (status = 200, description = "The route", body = TripRoute, content_type = "application/hal+json"),
+        (status = 404, description = "The route is unknown", body = Failure),
+    )
+)]
+#[get("/routes/<id>")]
+async fn show(id: Uuid, mut data_provider: RouteDataProvider) -> Result<Hal<TripRoute>, Error> {
+    data_provider
+        .get_route(&id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .map(|route| Hal(route.into()))
+        .ok_or_else(|| Error::unknown_route(&id))
+}
+
+#[utoipa::path(
+    tag = "routes",
+    request_body = RouteChanges,
+    responses(
+        (status = 200, description = "The updated route", body = TripRoute, content_type = "application/hal+json"),
+        (status = 400, description = "A change is invalid", body = Failure),
+        (status = 404, description = "The route is unknown", body = Failure),
+    )
+)]
+#[patch("/routes/<id>", data = "<data>")]
+async fn update(
+    id: Uuid,
+    data: Json<RouteChanges>,
+    mut data_provider: RouteDataProvider,
+) -> Result<Hal<TripRoute>, Error> {
+    let changes = entity::RouteChanges::try_from(data.into_inner())?;
+
+    data_provider
+        .update_route(&id, &changes)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .map(|route| Hal(route.into()))
+        .ok_or_else(|| Error::unknown_route(&id))
+}
+
+pub fn routes() -> Vec<Route> {
+    routes![create, list, show, update]
+}
+
+fn rank_id(url: &str) -> Result<Uuid, Error> {
+    url.rsplit('/')
+        .next()
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| Error::invalid_route(format!("`{url}` is not a taxi rank")))
+}
+
+fn check_distance(distance_km: f64) -> Result<(), Error> {
+    if !(distance_km > 0.0 && distance_km <= 2000.0) {
+        return Err(Error::invalid_route(String::from(
+            "The distance must be greater than 0 and at most 2000 km",
+        )));
+    }
+
+    Ok(())
+}
+
+fn check_duration(duration_minutes: i32) -> Result<(), Error> {
+    if !(1..=24 * 60).contains(&duration_minutes) {
+        return Err(Error::invalid_route(String::from(
+            "The duration must be between 1 and 1440 minutes",
+        )));
+    }
+
+    Ok(())
+}
@@ -1,63 +1,55 @@
 use std::collections::HashMap;
 
-use rocket::serde::json::Json;
-use rocket::{get, routes, uri, Route};
+use rocket::{get, routes, Route};
 use serde::Serialize;
+use utoipa::ToSchema;
 
-use crate::controllers::journey;
+use resource_derive::Resource;
+
+use crate::controllers::journey::{self, TripQuery};
 use crate::controllers::Error;
 use crate::guards::User as UserGuard;
+use crate::hal::{Hal, Resource};
 use crate::Link;
-use crate::BASE_URL;
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "start-journey", route = journey::start(&self.taxi))]
+#[link_to(rel = "trips", route = journey::list(&self.taxi, TripQuery::default()))]
+#[link_to(rel = "self", route = show(&self.taxi))]
+#[link_to(rel = "journey_in_progress", route = journey::in_progress(&self.taxi))]
 pub struct User {
     name: String,
+    #[serde(skip)]
+    taxi: String,
     #[serde(rename = "_links")]
+    #[links]
     links: HashMap<&'static str, Link>,
 }
 
 impl User {
-    fn new(num: &str, name: &str) -> Self {
-        let links = HashMap::from([
-            (
-                "start-journey",
-                Link {
-                    href: uri!(BASE_URL, journey::start(num)).to_string(),
-                },
-            ),
-            (
-                "trips",
-                Link {
-                    href: uri!(BASE_URL, journey::list(num)).to_string(),
-                },
-            ),
-            (
-                "self",
-                Link {
-                    href: uri!(BASE_URL, show(num)).to_string(),
-                },
-            ),
-            (
-                "journey_in_progress",
-                Link {
-                    href: uri!(BASE_URL, journey::in_progress(num)).to_string(),
-                },
-            ),
-        ]);
-
+    pub(super) fn new(num: &str, name: &str) -> Self {
         User {
-            links,
             name: name.into(),
+            taxi: num.into(),
+            links: HashMap::new(),
         }
+        .linked()
     }
 }
 
+#[utoipa::path(
+    tag = "taxis",
+    responses(
+        (status = 200, description = "The owner of the taxi", body = User, content_type = "application/hal+json"),
+        (status = 400, description = "The owner is not signed in", body = Failure),
+        (status = 404, description = "The taxi is unknown", body = Failure),
+    )
+)]
 #[get("/taxis/<num>/owner", rank = 4)]
-async fn show(num: &str, user: Result<UserGuard, Error>) -> Result<Json<User>, Error> {
+async fn show(num: &str, user: Result<UserGuard, Error>) -> Result<Hal<User>, Error> {
     let user = user?;
 
-    Ok(Json(User::new(num, &user.full_name)))
+    Ok(Hal(User::new(num, &user.full_name)))
 }
 
 pub fn routes() -> Vec<Route> {
@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use log::error;
+use resource_derive::Resource;
+use rocket::http::{Cookie, CookieJar};
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post, routes, uri, Route, State};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::controllers::bookings::{self, Booking};
+use crate::controllers::Error;
+use crate::data::{BookingDataProvider, JourneyDataProvider, WaitlistDataProvider};
+use crate::entity::{self, NewBooking, NewWaitlistEntry, WaitlistJoin};
+use crate::events::EventBus;
+use crate::guards::{Checkout, Passenger};
+use crate::hal::{Hal, Resource};
+use crate::usecase::{
+    ClaimWaitlistOffer, GetJourney, GetJourneyStops, GetWaitlistEntry, JoinWaitlist, LeaveWaitlist,
+};
+use crate::waitlist::Waitlist;
+use crate::{Link, BASE_URL};
+
+const TARGET: &'static str = "WAITLIST_CONTROLLER";
+
+#[derive(Deserialize, ToSchema, Validate)]
+pub(super) struct WaitlistRequest {
+    #[validate(range(min = 1))]
+    seats: u8,
+    #[validate(length(min = 8, max = 20))]
+    phone_number: String,
+}
+
+#[derive(Serialize, ToSchema, Resource)]
+#[link_to(rel = "self", route = show(&self.id))]
+#[link_to(rel = "claim", route = claim(&self.id), when = self.offered)]
+#[link_to(
+    rel = "leave",
+    route = leave(&self.id),
+    when = self.offered || self.position.is_some()
+)]
+pub(super) struct WaitlistEntry {
+    id: Uuid,
+    journey_id: Uuid,
+    seats: i32,
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offer_expires_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    booking_id: Option<String>,
+    #[serde(skip)]
+    offered: bool,
+    #[serde(rename = "_links")]
+    #[links]
+    links: HashMap<&'static str, Link>,
+}
+
+impl From<entity::WaitlistEntry> for WaitlistEntry {
+    fn from(value: entity::WaitlistEntry) -> Self {
+        WaitlistEntry {
+            offer_expires_at: value.offer_expires_at.filter(|_| value.is_offered()),
+            offered: value.is_offered(),
+            id: value.id,
+            journey_id: value.journey_id,
+            seats: value.seats,
+            status: value.status,
+            position: value.position,
+            booking_id: value
+                .booking_id
+                .map(|id| uri!(BASE_URL, bookings::show(&id)).to_string()),
+            links: HashMap::new(),
+        }
+        .linked()
+    }
+}
+
+async fn find(
+    id: &Uuid,
+    data_provider: &mut WaitlistDataProvider,
+) -> Result<entity::WaitlistEntry, Error> {
+    data_provider
+        .get_waitlist_entry(id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .ok_or_else(|| Error::unknown_waitlist_entry(id))
+}
+
+#[utoipa::path(
+    tag = "waitlist",
+    request_body = WaitlistRequest,
+    responses(
+        (status = 201, description = "The place of the passenger on the waitlist", body = WaitlistEntry, content_type = "application/hal+json"),
+        (status = 400, description = "The request is invalid, or the trip is closed", body = Failure),
+        (status = 404, description = "The trip is unknown", body = Failure),
+        (status = 409, description = "The trip still has the seats free to book", body = Failure),
+    )
+)]
+#[post("/trips/<id>/waitlist", data = "<data>")]
+async fn join(
+    id: Uuid,
+    data: Json<WaitlistRequest>,
+    waitlist: &State<Waitlist>,
+    bus: &State<EventBus>,
+    cookies: &CookieJar<'_>,
+    mut journeys: JourneyDataProvider,
+    mut data_provider: WaitlistDataProvider,
+) -> Result<status::Created<Hal<WaitlistEntry>>, Error> {
+    data.validate()
+        .map_err(|err| Error::invalid_booking(err.to_string()))?;
+
+    let journey = journeys
+        .get_journey(&id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .ok_or_else(|| Error::unknown_journey(&id))?;
+
+    if journey.closed {
+        return Err(Error::invalid_booking(format!(
+            "The journey `{id}` is closed"
+        )));
+    }
+
+    let entry_id = Uuid::new_v4();
+    let joined = data_provider
+        .join_waitlist(&NewWaitlistEntry {
+            id: entry_id,
+            journey_id: id,
+            seats: data.seats as i32,
+            phone_number: data.phone_number.clone(),
+        })
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?;
+
+    match joined {
+        WaitlistJoin::Joined => {}
+        WaitlistJoin::TooManySeats(seats) => {
+            return Err(Error::invalid_booking(format!(
+                "The taxi of the journey `{id}` has {seats} seat(s)"
+            )))
+        }
+        WaitlistJoin::SeatsAvailable => return Err(Error::journey_not_full(&id)),
+        WaitlistJoin::Unavailable => {
+            return Err(Error::invalid_booking(format!(
+                "The journey `{id}` is closed"
+            )))
+        }
+    }
+
+    let passenger = Passenger { journey_id: id };
+    cookies.add_private(Cookie::new(
+        entry_id.to_string(),
+        serde_json::to_string(&passenger).unwrap(),
+    ));
+
+    bus.journey_changed(&id, "waitlist_joined");
+    if waitlist.promote(&mut data_provider, &id).await {
+        bus.journey_changed(&id, "waitlist_offer");
+    }
+
+    let entry = find(&entry_id, &mut data_provider).await?;
+    let location = uri!(BASE_URL, show(&entry_id)).to_string();
+    Ok(status::Created::new(location).body(Hal(WaitlistEntry::from(entry))))
+}
+
+#[utoipa::path(
+    tag = "waitlist",
+    responses(
+        (status = 200, description = "The place on the waitlist, or the offer made", body = WaitlistEntry, content_type = "application/hal+json"),
+        (status = 404, description = "The entry is unknown", body = Failure),
+    )
+)]
+#[get("/waitlist/<id>")]
+async fn show(
+    id: Uuid,
+    passenger: Result<Passenger, Error>,
+    mut data_provider: WaitlistDataProvider,
+) -> Result<Hal<WaitlistEntry>, Error> {
+    passenger?;
+
+    let entry = find(&id, &mut data_provider).await?;
+    Ok(Hal(WaitlistEntry::from(entry)))
+}
+
+#[utoipa::path(
+    tag = "waitlist",
+    responses(
+        (status = 201, description = "The booking made from the offer", body = Booking, content_type = "application/hal+json"),
+        (status = 404, description = "The entry is unknown", body = Failure),
+        (status = 409, description = "No offer is open", body = Failure),
+    )
+)]
+#[post("/waitlist/<id>/claim")]
+async fn claim(
+    id: Uuid,
+    passenger: Result<Passenger, Error>,
+    checkout: Checkout<'_>,
+    mut journeys: JourneyDataProvider,
+    mut bookings: BookingDataProvider,
+    mut data_provider: WaitlistDataProvider,
+) -> Result<status::Created<Hal<Booking>>, Error> {
+    passenger?;
+
+    let entry = find(&id, &mut data_provider).await?;
+    if !entry.is_offered() {
+        return Err(Error::offer_unavailable(&id));
+    }
+
+    // Waitlist offers hold their seats over the whole journey.
+    let segment = journeys
+        .get_journey_stops(&entry.journey_id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })
+        .map(|stops| entity::Segment::whole(&stops))?
+        .ok_or_else(|| Error::offer_unavailable(&id))?;
+
+    let price = journeys
+        .get_journey(&entry.journey_id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .and_then(|j| j.fare())
+        .and_then(|unit| unit.checked_mul(entry.seats as u64))
+        .ok_or_else(|| {
+            Error::invalid_booking(format!("The journey `{}` has no fare", entry.journey_id))
+        })?;
+
+    let booking = data_provider
+        .claim_waitlist_offer(
+            &id,
+            &NewBooking {
+                id: Uuid::new_v4(),
+                journey_id: entry.journey_id,
+                seats: entry.seats,
+                phone_number: entry.phone_number.clone(),
+                email: None,
+                language: None,
+                price,
+                hold: checkout.payments.hold(),
+                seat_numbers: None,
+                segment,
+            },
+        )
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .ok_or_else(|| Error::offer_unavailable(&id))?;
+
+    checkout
+        .bus
+        .journey_changed(&entry.journey_id, "waitlist_claimed");
+    bookings::pay(
+        booking,
+        &entry.phone_number,
+        price,
+        &checkout,
+        &mut bookings,
+    )
+    .await
+}
+
+#[utoipa::path(
+    tag = "waitlist",
+    responses(
+        (status = 204, description = "The passenger left the waitlist"),
+        (status = 400, description = "The entry can no longer be left", body = Failure),
+        (status = 404, description = "The entry is unknown", body = Failure),
+    )
+)]
+#[delete("/waitlist/<id>")]
+async fn leave(
+    id: Uuid,
+    passenger: Result<Passenger, Error>,
+    waitlist: &State<Waitlist>,
+    bus: &State<EventBus>,
+    mut data_provider: WaitlistDataProvider,
+) -> Result<status::NoContent, Error> {
+    passenger?;
+
+    let journey_id = data_provider
+        .leave_waitlist(&id)
+        .await
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Error::server_error()
+        })?
+        .ok_or_else(|| {
+            Error::invalid_booking(format!("The waitlist entry `{id}` is no longer active"))
+        })?;
+
+    bus.journey_changed(&journey_id, "waitlist_left");
+    if waitlist.promote(&mut data_provider, &journey_id).await {
+        bus.journey_changed(&journey_id, "waitlist_offer");
+    }
+
+    Ok(status::NoContent)
+}
+
+pub fn routes() -> Vec<Route> {
+    routes![join, show, claim, leave]
+}
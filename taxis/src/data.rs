@@ -1,9 +1,32 @@
+pub use boarding_data_provider::BoardingDataProvider;
+pub use booking_data_provider::BookingDataProvider;
+pub use fare_data_provider::FareDataProvider;
 pub use journey_data_provider::JourneyDataProvider;
 pub use login_data_provider::LoginDataProvider;
+pub use notification_data_provider::NotificationDataProvider;
+pub use payment_data_provider::PaymentDataProvider;
+pub use queue_data_provider::QueueDataProvider;
 pub use registration_data_provider::RegistrationDataProvider;
+pub use route_data_provider::RouteDataProvider;
+pub use search_data_provider::SearchDataProvider;
+pub use seat_data_provider::SeatDataProvider;
 pub use taxi_ranks_data_provider::StandDataProvider;
+pub use tracking_data_provider::TrackingDataProvider;
+pub use waitlist_data_provider::WaitlistDataProvider;
 
+mod boarding_data_provider;
+mod booking_data_provider;
+mod fare_data_provider;
 mod journey_data_provider;
 mod login_data_provider;
+mod notification_data_provider;
+mod pagination;
+mod payment_data_provider;
+mod queue_data_provider;
 mod registration_data_provider;
+mod route_data_provider;
+mod search_data_provider;
+mod seat_data_provider;
 mod taxi_ranks_data_provider;
+mod tracking_data_provider;
+mod waitlist_data_provider;
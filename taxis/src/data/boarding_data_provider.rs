@@ -0,0 +1,122 @@
+use async_trait::async_trait;
+use sqlx::pool::PoolConnection;
+use sqlx::{Acquire, Postgres};
+use uuid::Uuid;
+
+use database_derive::DataProvider;
+
+use crate::entity::{Boarding, Manifest, ManifestEntry};
+use crate::usecase::{BoardPassenger, GetJourneyManifest};
+
+#[derive(DataProvider)]
+pub struct BoardingDataProvider(PoolConnection<Postgres>);
+
+impl BoardingDataProvider {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
+        BoardingDataProvider(conn)
+    }
+}
+
+#[async_trait]
+impl BoardPassenger for BoardingDataProvider {
+    async fn board_passenger(
+        &mut self,
+        taxi: &str,
+        journey_id: &Uuid,
+        booking_id: &Uuid,
+    ) -> sqlx::Result<Boarding> {
+        let mut tx = self.0.begin().await?;
+
+        let boarded = sqlx::query!(
+            "UPDATE bookings b SET boarded_at = now()
+                FROM trips tp
+                WHERE b.id = $1 AND b.journey_id = $2 AND tp.id = b.journey_id
+                    AND tp.owner = lower($3) AND b.status = 'confirmed' AND b.boarded_at IS NULL
+                RETURNING b.reserved_seats, b.boarded_at AS \"boarded_at!\"",
+            booking_id,
+            journey_id,
+            taxi,
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let Some(boarded) = boarded else {
+            let booking = sqlx::query!(
+                "SELECT b.status, b.boarded_at
+                FROM bookings b
+                    INNER JOIN trips tp ON tp.id = b.journey_id
+                WHERE b.id = $1 AND b.journey_id = $2 AND tp.owner = lower($3)",
+                booking_id,
+                journey_id,
+                taxi,
+            )
+            .fetch_optional(&mut tx)
+            .await?;
+
+            return Ok(match booking {
+                None => Boarding::Unknown,
+                Some(b) => match b.boarded_at {
+                    Some(boarded_at) => Boarding::AlreadyBoarded { boarded_at },
+                    None => Boarding::NotConfirmed { status: b.status },
+                },
+            });
+        };
+
+        let totals = sqlx::query!(
+            "SELECT COALESCE(SUM(reserved_seats) FILTER (WHERE boarded_at IS NOT NULL), 0) AS \"boarded!\",
+                COALESCE(SUM(reserved_seats), 0) AS \"expected!\"
+            FROM bookings
+            WHERE journey_id = $1 AND status = 'confirmed'",
+            journey_id,
+        )
+        .fetch_one(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Boarding::Boarded {
+            seats: boarded.reserved_seats,
+            boarded_at: boarded.boarded_at,
+            boarded_seats: totals.boarded,
+            expected_seats: totals.expected,
+        })
+    }
+}
+
+#[async_trait]
+impl GetJourneyManifest for BoardingDataProvider {
+    async fn get_journey_manifest(
+        &mut self,
+        taxi: &str,
+        journey_id: &Uuid,
+    ) -> sqlx::Result<Option<Manifest>> {
+        let trip = sqlx::query!(
+            "SELECT id, departure_schedule FROM trips WHERE id = $1 AND owner = lower($2)",
+            journey_id,
+            taxi,
+        )
+        .fetch_optional(&mut *self.0)
+        .await?;
+
+        let Some(trip) = trip else {
+            return Ok(None);
+        };
+
+        let entries = sqlx::query_as!(
+            ManifestEntry,
+            "SELECT id AS booking_id, reserved_seats AS seats, boarded_at
+            FROM bookings
+            WHERE journey_id = $1 AND status = 'confirmed'
+            ORDER BY created_at",
+            journey_id,
+        )
+        .fetch_all(&mut *self.0)
+        .await?;
+
+        Ok(Some(Manifest {
+            journey_id: trip.id,
+            departure_schedule: trip.departure_schedule,
+            entries,
+        }))
+    }
+}
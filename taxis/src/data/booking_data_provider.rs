@@ -0,0 +1,319 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use domain::entity::Price;
+use sqlx::pool::PoolConnection;
+use sqlx::{Acquire, Postgres};
+use uuid::Uuid;
+
+use database_derive::DataProvider;
+
+use crate::data::seat_data_provider::assign_seats;
+use crate::entity::{Booking, BookingStatus, NewBooking, Payment, PaymentStatus, WaitlistStatus};
+use crate::usecase::{
+    AddPayment, AttachPaymentReference, CancelBooking, GetBooking, GetBookingPayment,
+    MarkPaymentRefunded, PerformBooking, ReleaseBooking,
+};
+
+#[derive(DataProvider)]
+pub struct BookingDataProvider(PoolConnection<Postgres>);
+
+impl BookingDataProvider {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
+        BookingDataProvider(conn)
+    }
+}
+
+#[async_trait]
+impl PerformBooking for BookingDataProvider {
+    async fn perform_booking(&mut self, booking: &NewBooking) -> sqlx::Result<Option<Booking>> {
+        let mut tx = self.0.begin().await?;
+
+        let segment = &booking.segment;
+
+        let trip = sqlx::query!(
+            "SELECT tp.owner, t.number_of_seats,
+                COALESCE(s.scheduled_at, tp.departure_schedule) AS \"departure_schedule!\"
+            FROM trips tp
+                INNER JOIN taxis t ON t.number = tp.owner
+                LEFT JOIN journey_stops s ON s.journey_id = tp.id AND s.position = $2
+            WHERE tp.id = $1 AND tp.closed = FALSE
+                AND NOT EXISTS (
+                    SELECT 1 FROM waitlist_entries w
+                    WHERE w.journey_id = tp.id AND w.status = $3
+                )
+            FOR UPDATE OF tp",
+            &booking.journey_id,
+            segment.board_stop,
+            WaitlistStatus::Waiting.as_str(),
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let Some(trip) = trip else {
+            return Ok(None);
+        };
+
+        // Seats are counted per leg, once the trip is locked so that no other booking interleaves.
+        let load = sqlx::query_scalar!(
+            "SELECT journey_load($1, $2, $3) AS \"load!\"",
+            &booking.journey_id,
+            segment.board_stop,
+            segment.alight_stop,
+        )
+        .fetch_one(&mut tx)
+        .await?;
+
+        if load + booking.seats > trip.number_of_seats {
+            return Ok(None);
+        }
+
+        sqlx::query!(
+            "UPDATE trips SET reserved_seats = reserved_seats + $2 WHERE id = $1",
+            &booking.journey_id,
+            booking.seats,
+        )
+        .execute(&mut tx)
+        .await?;
+
+        let created_at = Utc::now();
+        let hold_expires_at = created_at + booking.hold;
+        sqlx::query!(
+            "INSERT INTO bookings
+                (id, journey_id, reserved_seats, status, phone_number, amount, currency, created_at,
+                    hold_expires_at, email, language, board_stop, alight_stop)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, COALESCE($11, 'fr'), $12, $13)",
+            &booking.id,
+            &booking.journey_id,
+            booking.seats,
+            BookingStatus::Pending.as_str(),
+            &booking.phone_number,
+            booking.price.amount as i64,
+            booking.price.currency.code(),
+            created_at,
+            hold_expires_at,
+            booking.email.as_deref(),
+            booking.language.as_deref(),
+            segment.board_stop,
+            segment.alight_stop,
+        )
+        .execute(&mut tx)
+        .await?;
+
+        let seat_numbers = assign_seats(
+            &mut tx,
+            &booking.journey_id,
+            &booking.id,
+            trip.number_of_seats,
+            booking.seats,
+            booking.seat_numbers.as_deref(),
+            segment,
+        )
+        .await?;
+
+        let Some(seat_numbers) = seat_numbers else {
+            return Ok(None);
+        };
+
+        tx.commit().await?;
+
+        Ok(Some(Booking {
+            id: booking.id,
+            journey_id: booking.journey_id,
+            taxi: trip.owner,
+            departure_schedule: trip.departure_schedule,
+            origin: segment.origin,
+            destination: segment.destination,
+            reserved_seats: booking.seats,
+            status: BookingStatus::Pending.as_str().into(),
+            amount: Some(booking.price.amount as i64),
+            currency: Some(booking.price.currency.code().into()),
+            hold_expires_at: Some(hold_expires_at),
+            cancellation_reason: None,
+            refund_amount: None,
+            boarded_at: None,
+            seat_numbers,
+        }))
+    }
+}
+
+#[async_trait]
+impl GetBooking for BookingDataProvider {
+    async fn get_booking(&mut self, id: &Uuid) -> sqlx::Result<Option<Booking>> {
+        sqlx::query_as!(
+            Booking,
+            "SELECT b.id, b.journey_id, tp.owner AS taxi,
+                COALESCE(bs.scheduled_at, tp.departure_schedule) AS \"departure_schedule!\",
+                bs.rank_id AS origin, als.rank_id AS destination, b.reserved_seats,
+                b.status, b.amount, b.currency, b.hold_expires_at, b.cancellation_reason,
+                b.refund_amount, b.boarded_at,
+                ARRAY(
+                    SELECT seat_number FROM booking_seats
+                    WHERE booking_id = b.id ORDER BY seat_number
+                ) AS \"seat_numbers!\"
+            FROM bookings b
+                INNER JOIN trips tp ON tp.id = b.journey_id
+                INNER JOIN journey_stops bs ON bs.journey_id = b.journey_id AND bs.position = b.board_stop
+                INNER JOIN journey_stops als
+                    ON als.journey_id = b.journey_id AND als.position = b.alight_stop
+            WHERE b.id = $1",
+            id
+        )
+        .fetch_optional(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl ReleaseBooking for BookingDataProvider {
+    async fn release_booking(&mut self, id: &Uuid, status: BookingStatus) -> sqlx::Result<bool> {
+        Ok(sqlx::query!(
+            "WITH released AS (
+                UPDATE bookings SET status = $2, hold_expires_at = NULL
+                WHERE id = $1 AND status = 'pending'
+                RETURNING id, journey_id, reserved_seats
+            ), freed AS (
+                DELETE FROM booking_seats bs USING released r
+                WHERE bs.booking_id = r.id
+                RETURNING bs.seat_number
+            ), abandoned AS (
+                UPDATE payments p SET status = 'failed', failure_reason = 'booking ' || $2,
+                        updated_at = now()
+                    FROM released r
+                    WHERE p.booking_id = r.id AND p.status = 'pending'
+                    RETURNING p.id
+            )
+            UPDATE trips tp SET reserved_seats = tp.reserved_seats - r.reserved_seats
+                FROM released r
+                WHERE tp.id = r.journey_id",
+            id,
+            status.as_str(),
+        )
+        .execute(&mut *self.0)
+        .await?
+        .rows_affected()
+            > 0)
+    }
+}
+
+#[async_trait]
+impl AddPayment for BookingDataProvider {
+    async fn add_payment(&mut self, payment: &Payment) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO payments
+                (id, booking_id, provider, reference, amount, currency, status, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+            &payment.id,
+            &payment.booking_id,
+            &payment.provider,
+            &payment.reference,
+            payment.amount,
+            &payment.currency,
+            &payment.status,
+            payment.created_at,
+            payment.updated_at,
+        )
+        .execute(&mut *self.0)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AttachPaymentReference for BookingDataProvider {
+    async fn attach_payment_reference(
+        &mut self,
+        payment_id: &Uuid,
+        reference: &str,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE payments SET reference = $2, updated_at = now() WHERE id = $1",
+            payment_id,
+            reference,
+        )
+        .execute(&mut *self.0)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl GetBookingPayment for BookingDataProvider {
+    async fn get_booking_payment(&mut self, booking_id: &Uuid) -> sqlx::Result<Option<Payment>> {
+        sqlx::query_as!(
+            Payment,
+            "SELECT id, booking_id, provider, reference, amount, currency, status, failure_reason,
+                refund_amount, created_at, updated_at
+            FROM payments
+            WHERE booking_id = $1
+            ORDER BY created_at DESC
+            LIMIT 1",
+            booking_id
+        )
+        .fetch_optional(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl CancelBooking for BookingDataProvider {
+    async fn cancel_booking(
+        &mut self,
+        id: &Uuid,
+        reason: Option<&str>,
+        refund: Option<&Price>,
+    ) -> sqlx::Result<Option<Uuid>> {
+        Ok(sqlx::query!(
+            "WITH cancelled AS (
+                UPDATE bookings
+                    SET status = $4, cancelled_at = now(), cancellation_reason = $2,
+                        refund_amount = $3, hold_expires_at = NULL
+                WHERE id = $1 AND status IN ('pending', 'confirmed')
+                RETURNING id, journey_id, reserved_seats
+            ), freed AS (
+                DELETE FROM booking_seats bs USING cancelled c
+                WHERE bs.booking_id = c.id
+                RETURNING bs.seat_number
+            ), abandoned AS (
+                UPDATE payments p SET status = 'failed', failure_reason = 'booking cancelled',
+                        updated_at = now()
+                    FROM cancelled c
+                    WHERE p.booking_id = c.id AND p.status = 'pending'
+                    RETURNING p.id
+            ), refunding AS (
+                UPDATE payments p SET status = $5, refund_amount = $3, updated_at = now()
+                    FROM cancelled c
+                    WHERE p.booking_id = c.id AND p.status = 'succeeded' AND $3 > 0
+                    RETURNING p.id
+            )
+            UPDATE trips tp SET reserved_seats = tp.reserved_seats - c.reserved_seats
+                FROM cancelled c
+                WHERE tp.id = c.journey_id
+                RETURNING tp.id",
+            id,
+            reason,
+            refund.map(|p| p.amount as i64),
+            BookingStatus::Cancelled.as_str(),
+            PaymentStatus::RefundPending.as_str(),
+        )
+        .fetch_optional(&mut *self.0)
+        .await?
+        .map(|r| r.id))
+    }
+}
+
+#[async_trait]
+impl MarkPaymentRefunded for BookingDataProvider {
+    async fn mark_payment_refunded(&mut self, payment_id: &Uuid) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE payments SET status = $2, updated_at = now() WHERE id = $1",
+            payment_id,
+            PaymentStatus::Refunded.as_str(),
+        )
+        .execute(&mut *self.0)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use sqlx::pool::PoolConnection;
+use sqlx::Postgres;
+use uuid::Uuid;
+
+use database_derive::DataProvider;
+
+use crate::entity::Fare;
+use crate::usecase::{GetFare, SetFare};
+
+#[derive(DataProvider)]
+pub struct FareDataProvider(PoolConnection<Postgres>);
+
+impl FareDataProvider {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
+        FareDataProvider(conn)
+    }
+}
+
+#[async_trait]
+impl GetFare for FareDataProvider {
+    async fn get_fare(&mut self, origin: &Uuid, destination: &Uuid) -> sqlx::Result<Option<Fare>> {
+        sqlx::query_as!(
+            Fare,
+            "SELECT origin, destination, amount, currency FROM fares
+                WHERE origin = $1 AND destination = $2",
+            origin,
+            destination
+        )
+        .fetch_optional(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl SetFare for FareDataProvider {
+    async fn set_fare(&mut self, fare: &Fare) -> sqlx::Result<()> {
+        sqlx::query!(
+            "INSERT INTO fares (origin, destination, amount, currency) VALUES ($1, $2, $3, $4)
+                ON CONFLICT (origin, destination)
+                    DO UPDATE SET amount = EXCLUDED.amount, currency = EXCLUDED.currency",
+            &fare.origin,
+            &fare.destination,
+            fare.amount,
+            &fare.currency,
+        )
+        .execute(&mut *self.0)
+        .await?;
+
+        Ok(())
+    }
+}
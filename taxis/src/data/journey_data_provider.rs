@@ -1,21 +1,28 @@
 use async_trait::async_trait;
-use rocket_db_pools::Connection;
+use chrono::{DateTime, Utc};
+use domain::entity::Price;
+use sqlx::pool::PoolConnection;
+use sqlx::{Acquire, Postgres, QueryBuilder};
 use uuid::Uuid;
 
-use database::TXpressDB;
 use database_derive::DataProvider;
 
-use crate::entity::{Journey, JourneyCriteria};
+use crate::data::pagination::push_page;
+use crate::entity::{
+    Journey, JourneyCriteria, JourneyFilter, JourneySortField, JourneyStatus, JourneyStop, Page,
+    PageRequest,
+};
 use crate::usecase::{
-    CancelJourney, CloseJourney, DoesBookingExistsOnJourney, GetAllJourney, GetInProgressJourney,
-    GetJourney, HasAJourneyInProgress, PerformJourney,
+    CancelJourney, CloseJourney, CloseStaleJourneys, DoesBookingExistsOnJourney, GetAllJourney,
+    GetInProgressJourney, GetJourney, GetJourneyStops, HasAJourneyInProgress, PerformJourney,
+    SetJourneyFare,
 };
 
 #[derive(DataProvider)]
-pub struct JourneyDataProvider(Connection<TXpressDB>);
+pub struct JourneyDataProvider(PoolConnection<Postgres>);
 
 impl JourneyDataProvider {
-    fn new(conn: Connection<TXpressDB>) -> Self {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
         JourneyDataProvider(conn)
     }
 }
@@ -25,8 +32,13 @@ impl GetJourney for JourneyDataProvider {
     async fn get_journey(&mut self, id: &Uuid) -> sqlx::Result<Option<Journey>> {
         sqlx::query_as!(
             Journey,
-            "SELECT id, origin, destination, reserved_seats, departure_schedule, closed FROM trips
-            WHERE id = $1",
+            "SELECT tp.id, tp.origin, tp.destination, tp.reserved_seats, tp.departure_schedule, tp.closed,
+                COALESCE(tp.fare_amount, f.amount) AS fare_amount,
+                COALESCE(tp.fare_currency, f.currency) AS fare_currency,
+                tp.route_id, tp.duration_minutes, tp.boarding_at
+            FROM trips tp
+                LEFT JOIN fares f ON f.origin = tp.origin AND f.destination = tp.destination
+            WHERE tp.id = $1",
             id
         )
         .fetch_optional(&mut *self.0)
@@ -36,12 +48,57 @@ impl GetJourney for JourneyDataProvider {
 
 #[async_trait]
 impl GetAllJourney for JourneyDataProvider {
-    async fn get_all_journey(&mut self, taxi_num: &str) -> sqlx::Result<Vec<Journey>> {
-        sqlx::query_as!(Journey,
-           "SELECT tp.id, tp.origin, tp.destination, tp.reserved_seats, tp.departure_schedule, tp.closed FROM trips tp
-                WHERE tp.owner = lower($1)
-            ORDER BY tp.departure_schedule DESC", taxi_num)
-           .fetch_all(&mut *self.0).await
+    async fn get_all_journey(
+        &mut self,
+        taxi_num: &str,
+        filter: &JourneyFilter,
+        page: &PageRequest,
+    ) -> sqlx::Result<Page<Journey>> {
+        let mut query = QueryBuilder::new(
+            "SELECT tp.id, tp.origin, tp.destination, tp.reserved_seats, tp.departure_schedule, tp.closed,
+                COALESCE(tp.fare_amount, f.amount) AS fare_amount,
+                COALESCE(tp.fare_currency, f.currency) AS fare_currency,
+                tp.route_id, tp.duration_minutes, tp.boarding_at
+            FROM trips tp
+                LEFT JOIN fares f ON f.origin = tp.origin AND f.destination = tp.destination
+            WHERE tp.owner = ",
+        );
+        query.push_bind(taxi_num.to_lowercase());
+
+        if let Some(status) = filter.status {
+            query
+                .push(" AND tp.closed = ")
+                .push_bind(status == JourneyStatus::Closed);
+        }
+        if let Some(after) = filter.departs_after {
+            query
+                .push(" AND tp.departure_schedule >= ")
+                .push_bind(after);
+        }
+        if let Some(before) = filter.departs_before {
+            query
+                .push(" AND tp.departure_schedule < ")
+                .push_bind(before);
+        }
+        if let Some(origin) = filter.origin {
+            query.push(" AND tp.origin = ").push_bind(origin);
+        }
+        if let Some(destination) = filter.destination {
+            query.push(" AND tp.destination = ").push_bind(destination);
+        }
+
+        let (column, cast) = match filter.sort.field {
+            JourneySortField::DepartureSchedule => ("tp.departure_schedule", "::timestamptz"),
+            JourneySortField::ReservedSeats => ("tp.reserved_seats", "::integer"),
+        };
+        push_page(&mut query, column, cast, "tp.id", filter.sort.order, page);
+
+        let rows = query
+            .build_query_as::<Journey>()
+            .fetch_all(&mut *self.0)
+            .await?;
+
+        Ok(Page::new(rows, page, |j| (filter.sort.field.key(j), j.id)))
     }
 }
 
@@ -50,8 +107,13 @@ impl GetInProgressJourney for JourneyDataProvider {
     async fn get_in_progress_journey(&mut self, owner: &str) -> sqlx::Result<Option<Journey>> {
         sqlx::query_as!(
             Journey,
-            "SELECT id, origin, destination, reserved_seats, departure_schedule, closed FROM trips
-            WHERE owner = $1",
+            "SELECT tp.id, tp.origin, tp.destination, tp.reserved_seats, tp.departure_schedule, tp.closed,
+                COALESCE(tp.fare_amount, f.amount) AS fare_amount,
+                COALESCE(tp.fare_currency, f.currency) AS fare_currency,
+                tp.route_id, tp.duration_minutes, tp.boarding_at
+            FROM trips tp
+                LEFT JOIN fares f ON f.origin = tp.origin AND f.destination = tp.destination
+            WHERE tp.owner = $1",
             owner
         )
         .fetch_optional(&mut *self.0)
@@ -67,18 +129,59 @@ impl PerformJourney for JourneyDataProvider {
         criteria: &JourneyCriteria,
     ) -> sqlx::Result<Uuid> {
         let id = Uuid::new_v4();
+        let fare = criteria.fare.as_ref();
+        let route = criteria.route.as_ref();
+
+        let mut tx = self.0.begin().await?;
+
         sqlx::query!(
-            "INSERT INTO trips(id, owner, origin, destination, departure_schedule)
-                VALUES ($1, lower($2), $3, $4, $5)",
+            "INSERT INTO trips(id, owner, origin, destination, departure_schedule, fare_amount, fare_currency,
+                route_id, duration_minutes)
+                VALUES ($1, lower($2), $3, $4, $5, $6, $7, $8, $9)",
             &id,
             taxi_num,
             &criteria.origin,
             &criteria.destination,
             &criteria.departure_schedule,
+            fare.map(|p| p.amount as i64),
+            fare.map(|p| p.currency.code()),
+            route.map(|r| r.id),
+            route.map(|r| r.duration_minutes),
         )
-        .execute(&mut *self.0)
+        .execute(&mut tx)
         .await?;
 
+        let mut ranks = vec![criteria.origin];
+        let mut schedules = vec![Some(criteria.departure_schedule)];
+        for stop in &criteria.stops {
+            ranks.push(stop.rank_id);
+            schedules.push(Some(stop.scheduled_at));
+        }
+
+        // The catalogue duration gives the arrival, unless the stops make it inconsistent.
+        let last_call = schedules.last().copied().flatten();
+        let arrival = route
+            .map(|r| {
+                criteria.departure_schedule
+                    + chrono::Duration::minutes(i64::from(r.duration_minutes))
+            })
+            .filter(|arrival| Some(*arrival) > last_call);
+        ranks.push(criteria.destination);
+        schedules.push(arrival);
+
+        sqlx::query!(
+            "INSERT INTO journey_stops (journey_id, position, rank_id, scheduled_at)
+            SELECT $1, s.position - 1, s.rank_id, s.scheduled_at
+            FROM UNNEST($2::uuid[], $3::timestamptz[]) WITH ORDINALITY AS s(rank_id, scheduled_at, position)",
+            &id,
+            &ranks,
+            &schedules as &[Option<chrono::DateTime<chrono::Utc>>],
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+
         Ok(id)
     }
 }
@@ -93,13 +196,32 @@ impl CloseJourney for JourneyDataProvider {
     }
 }
 
+#[async_trait]
+impl CloseStaleJourneys for JourneyDataProvider {
+    async fn close_stale_journeys(&mut self, before: &DateTime<Utc>) -> sqlx::Result<Vec<Uuid>> {
+        sqlx::query_scalar!(
+            "UPDATE trips SET closed = TRUE
+            WHERE closed = FALSE AND departure_schedule < $1
+            RETURNING id",
+            before
+        )
+        .fetch_all(&mut *self.0)
+        .await
+    }
+}
+
 #[async_trait]
 impl CancelJourney for JourneyDataProvider {
-    async fn cancel_journey(&mut self, journey_id: &Uuid) -> sqlx::Result<()> {
-        sqlx::query!("DELETE FROM trips WHERE id = $1", journey_id)
-            .execute(&mut *self.0)
-            .await?;
-        Ok(())
+    async fn cancel_journey(&mut self, owner: &str, journey_id: &Uuid) -> sqlx::Result<bool> {
+        Ok(sqlx::query!(
+            "DELETE FROM trips WHERE id = $1 AND owner = lower($2)",
+            journey_id,
+            owner,
+        )
+        .execute(&mut *self.0)
+        .await?
+        .rows_affected()
+            > 0)
     }
 }
 
@@ -128,3 +250,47 @@ impl HasAJourneyInProgress for JourneyDataProvider {
         .is_some())
     }
 }
+
+#[async_trait]
+impl SetJourneyFare for JourneyDataProvider {
+    async fn set_journey_fare(
+        &mut self,
+        owner: &str,
+        journey_id: &Uuid,
+        fare: Option<&Price>,
+    ) -> sqlx::Result<bool> {
+        Ok(sqlx::query!(
+            "UPDATE trips SET fare_amount = $2, fare_currency = $3
+            WHERE id = $1 AND closed = FALSE AND owner = lower($4)",
+            journey_id,
+            fare.map(|p| p.amount as i64),
+            fare.map(|p| p.currency.code()),
+            owner,
+        )
+        .execute(&mut *self.0)
+        .await?
+        .rows_affected()
+            > 0)
+    }
+}
+
+#[async_trait]
+impl GetJourneyStops for JourneyDataProvider {
+    async fn get_journey_stops(&mut self, journey_id: &Uuid) -> sqlx::Result<Vec<JourneyStop>> {
+        sqlx::query_as!(
+            JourneyStop,
+            "SELECT s.position, s.rank_id, s.scheduled_at,
+                CASE WHEN s.position < MAX(s.position) OVER () THEN
+                    t.number_of_seats - journey_load(s.journey_id, s.position, s.position + 1)
+                END AS available_seats
+            FROM journey_stops s
+                INNER JOIN trips tp ON tp.id = s.journey_id
+                INNER JOIN taxis t ON t.number = tp.owner
+            WHERE s.journey_id = $1
+            ORDER BY s.position",
+            journey_id
+        )
+        .fetch_all(&mut *self.0)
+        .await
+    }
+}
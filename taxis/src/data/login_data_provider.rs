@@ -1,17 +1,17 @@
 use async_trait::async_trait;
-use rocket_db_pools::Connection;
+use sqlx::pool::PoolConnection;
+use sqlx::Postgres;
 
-use database::TXpressDB;
 use database_derive::DataProvider;
 
 use crate::entity::Owner;
-use crate::usecase::GetOwner;
+use crate::usecase::{GetOwner, SetOwnerPassword};
 
 #[derive(DataProvider)]
-pub struct LoginDataProvider(Connection<TXpressDB>);
+pub struct LoginDataProvider(PoolConnection<Postgres>);
 
 impl LoginDataProvider {
-    fn new(conn: Connection<TXpressDB>) -> Self {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
         LoginDataProvider(conn)
     }
 }
@@ -21,10 +21,25 @@ impl GetOwner for LoginDataProvider {
     async fn get_owner(&mut self, num: &str) -> sqlx::Result<Option<Owner>> {
         sqlx::query_as!(
             Owner,
-            "SELECT full_name, password FROM taxi_owners WHERE id = lower($1)",
+            "SELECT full_name, password, phone_number, email, language FROM taxi_owners WHERE id = lower($1)",
             num
         )
         .fetch_optional(&mut *self.0)
         .await
     }
 }
+
+#[async_trait]
+impl SetOwnerPassword for LoginDataProvider {
+    async fn set_owner_password(&mut self, num: &str, password: &str) -> sqlx::Result<bool> {
+        let updated = sqlx::query!(
+            "UPDATE taxi_owners SET password = $2 WHERE id = lower($1)",
+            num,
+            password
+        )
+        .execute(&mut *self.0)
+        .await?;
+
+        Ok(updated.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,234 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use sqlx::pool::PoolConnection;
+use sqlx::{Acquire, Postgres};
+use uuid::Uuid;
+
+use database_derive::DataProvider;
+
+use crate::entity::{
+    Audience, BookingStatus, DeliveryAttempt, NewNotification, Notification, NotificationEvent,
+    Recipient, WaitlistStatus,
+};
+use crate::usecase::{
+    EnqueueNotifications, GetAudience, GetDueNotifications, GetImminentDepartures,
+    RecordDeliveryAttempt,
+};
+
+#[derive(DataProvider)]
+pub struct NotificationDataProvider(PoolConnection<Postgres>);
+
+impl NotificationDataProvider {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
+        NotificationDataProvider(conn)
+    }
+}
+
+#[async_trait]
+impl GetAudience for NotificationDataProvider {
+    async fn get_audience(
+        &mut self,
+        event: NotificationEvent,
+        id: &Uuid,
+    ) -> sqlx::Result<Option<Audience>> {
+        let journey_id = match event {
+            NotificationEvent::BookingConfirmed => {
+                sqlx::query_scalar!("SELECT journey_id FROM bookings WHERE id = $1", id)
+                    .fetch_optional(&mut *self.0)
+                    .await?
+            }
+            _ => Some(*id),
+        };
+
+        let Some(journey_id) = journey_id else {
+            return Ok(None);
+        };
+
+        let journey = sqlx::query!(
+            "SELECT tp.id, tp.owner, o.name AS origin, d.name AS destination, tp.departure_schedule
+            FROM trips tp
+                INNER JOIN taxi_ranks o ON o.id = tp.origin
+                INNER JOIN taxi_ranks d ON d.id = tp.destination
+            WHERE tp.id = $1",
+            &journey_id
+        )
+        .fetch_optional(&mut *self.0)
+        .await?;
+
+        let Some(journey) = journey else {
+            return Ok(None);
+        };
+
+        let recipients = match event {
+            NotificationEvent::BookingConfirmed => {
+                sqlx::query_as!(
+                    Recipient,
+                    r#"SELECT id AS "booking_id?", phone_number, email, language AS "language?",
+                        reserved_seats AS "seats?"
+                    FROM bookings
+                    WHERE id = $1"#,
+                    id
+                )
+                .fetch_all(&mut *self.0)
+                .await?
+            }
+            NotificationEvent::JourneyCancelled | NotificationEvent::DepartureImminent => {
+                let (statuses, waitlisted) = match event {
+                    NotificationEvent::JourneyCancelled => (
+                        vec![
+                            BookingStatus::Pending.as_str(),
+                            BookingStatus::Confirmed.as_str(),
+                        ],
+                        true,
+                    ),
+                    _ => (vec![BookingStatus::Confirmed.as_str()], false),
+                };
+
+                sqlx::query_as!(
+                    Recipient,
+                    r#"SELECT NULL::uuid AS "booking_id?", o.phone_number AS "phone_number?",
+                        o.email AS "email?", o.language AS "language?", NULL::integer AS "seats?"
+                    FROM taxi_owners o
+                    WHERE o.id = $2
+                    UNION ALL
+                    SELECT b.id, b.phone_number, b.email, b.language, b.reserved_seats
+                    FROM bookings b
+                    WHERE b.journey_id = $1 AND b.status = ANY($3)
+                    UNION ALL
+                    SELECT NULL, w.phone_number, NULL, NULL, w.seats
+                    FROM waitlist_entries w
+                    WHERE $4 AND w.journey_id = $1 AND w.status = ANY($5)"#,
+                    &journey_id,
+                    &journey.owner,
+                    &statuses as &[&str],
+                    waitlisted,
+                    &[
+                        WaitlistStatus::Waiting.as_str(),
+                        WaitlistStatus::Offered.as_str()
+                    ] as &[&str],
+                )
+                .fetch_all(&mut *self.0)
+                .await?
+            }
+        };
+
+        Ok(Some(Audience {
+            journey_id: journey.id,
+            taxi: journey.owner,
+            origin: journey.origin,
+            destination: journey.destination,
+            departure_schedule: journey.departure_schedule,
+            recipients,
+        }))
+    }
+}
+
+#[async_trait]
+impl EnqueueNotifications for NotificationDataProvider {
+    async fn enqueue_notifications(
+        &mut self,
+        notifications: &[NewNotification],
+    ) -> sqlx::Result<u64> {
+        let mut tx = self.0.begin().await?;
+
+        let mut enqueued = 0;
+        for notification in notifications {
+            enqueued += sqlx::query!(
+                "INSERT INTO notifications
+                    (id, event, channel, recipient, language, subject, body, journey_id, booking_id, dedupe_key)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                    ON CONFLICT (dedupe_key) DO NOTHING",
+                &notification.id,
+                notification.event.as_str(),
+                notification.channel.as_str(),
+                &notification.recipient,
+                &notification.language,
+                notification.subject.as_deref(),
+                &notification.body,
+                notification.journey_id,
+                notification.booking_id,
+                notification.dedupe_key.as_deref(),
+            )
+            .execute(&mut tx)
+            .await?
+            .rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(enqueued)
+    }
+}
+
+#[async_trait]
+impl GetDueNotifications for NotificationDataProvider {
+    async fn get_due_notifications(
+        &mut self,
+        limit: i64,
+        lease: Duration,
+    ) -> sqlx::Result<Vec<Notification>> {
+        // Pushing `next_attempt_at` forward leases the rows, so a notification being sent is not
+        // picked up again by another worker.
+        sqlx::query_as!(
+            Notification,
+            "UPDATE notifications SET next_attempt_at = $2
+                WHERE id IN (
+                    SELECT id FROM notifications
+                    WHERE status = 'pending' AND next_attempt_at <= now()
+                    ORDER BY next_attempt_at
+                    LIMIT $1
+                    FOR UPDATE SKIP LOCKED
+                )
+                RETURNING id, channel, recipient, subject, body, attempts",
+            limit,
+            Utc::now() + lease,
+        )
+        .fetch_all(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl RecordDeliveryAttempt for NotificationDataProvider {
+    async fn record_delivery_attempt(&mut self, attempt: &DeliveryAttempt<'_>) -> sqlx::Result<()> {
+        let mut tx = self.0.begin().await?;
+        let succeeded = attempt.error.is_none();
+
+        sqlx::query!(
+            "INSERT INTO notification_attempts (notification_id, transport, succeeded, error)
+                VALUES ($1, $2, $3, $4)",
+            &attempt.notification_id,
+            attempt.transport,
+            succeeded,
+            attempt.error.as_deref(),
+        )
+        .execute(&mut tx)
+        .await?;
+
+        sqlx::query!(
+            "UPDATE notifications SET attempts = attempts + 1,
+                status = CASE WHEN $2 THEN 'sent' WHEN $3::timestamptz IS NULL THEN 'failed' ELSE 'pending' END,
+                sent_at = CASE WHEN $2 THEN now() END,
+                next_attempt_at = COALESCE($3, next_attempt_at)
+            WHERE id = $1",
+            &attempt.notification_id,
+            succeeded,
+            attempt.retry_at,
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await
+    }
+}
+
+#[async_trait]
+impl GetImminentDepartures for NotificationDataProvider {
+    async fn get_imminent_departures(&mut self, within: Duration) -> sqlx::Result<Vec<Uuid>> {
+        sqlx::query_scalar!(
+            "SELECT id FROM trips WHERE departure_schedule BETWEEN now() AND $1",
+            Utc::now() + within,
+        )
+        .fetch_all(&mut *self.0)
+        .await
+    }
+}
@@ -0,0 +1,36 @@
+use sqlx::{Postgres, QueryBuilder};
+
+use crate::entity::{PageRequest, SortOrder};
+
+/// Ends a listing query with the keyset of the page: the rows past the cursor, ordered by
+/// `column` then `id`, one more than the limit. The cursor key is bound as text and cast with
+/// `cast` to the type of the column.
+pub(super) fn push_page(
+    query: &mut QueryBuilder<'_, Postgres>,
+    column: &str,
+    cast: &str,
+    id: &str,
+    order: SortOrder,
+    page: &PageRequest,
+) {
+    // A backward page is read in reverse from its cursor, then put back in order.
+    let ascending = (order == SortOrder::Ascending) != page.is_backward();
+
+    if let Some(cursor) = &page.cursor {
+        query
+            .push(format!(" AND ({column}, {id}) "))
+            .push(if ascending { "> (" } else { "< (" })
+            .push_bind(cursor.key.clone())
+            .push(cast)
+            .push(", ")
+            .push_bind(cursor.id)
+            .push(")");
+    }
+
+    let direction = if ascending { "ASC" } else { "DESC" };
+    query
+        .push(format!(
+            " ORDER BY {column} {direction}, {id} {direction} LIMIT "
+        ))
+        .push_bind(page.limit as i64 + 1);
+}
@@ -0,0 +1,213 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::pool::PoolConnection;
+use sqlx::{Acquire, Postgres};
+use uuid::Uuid;
+
+use database_derive::DataProvider;
+
+use crate::entity::{BookingStatus, Payment, PaymentStatus, Settlement};
+use crate::usecase::{
+    ClaimPendingRefunds, MarkPaymentRefunded, ReleaseExpiredHolds, SettlePayment,
+};
+
+#[derive(DataProvider)]
+pub struct PaymentDataProvider(PoolConnection<Postgres>);
+
+impl PaymentDataProvider {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
+        PaymentDataProvider(conn)
+    }
+}
+
+#[async_trait]
+impl SettlePayment for PaymentDataProvider {
+    async fn settle_payment(
+        &mut self,
+        provider: &str,
+        reference: &str,
+        succeeded: bool,
+        reason: Option<&str>,
+    ) -> sqlx::Result<Settlement> {
+        let mut tx = self.0.begin().await?;
+
+        let status = if succeeded {
+            PaymentStatus::Succeeded
+        } else {
+            PaymentStatus::Failed
+        };
+
+        let payment = sqlx::query!(
+            "UPDATE payments SET status = $3, failure_reason = $4, updated_at = now()
+                WHERE provider = $1 AND reference = $2 AND status = 'pending'
+                RETURNING id, booking_id",
+            provider,
+            reference,
+            status.as_str(),
+            reason,
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let Some(payment) = payment else {
+            if !succeeded {
+                return Ok(Settlement::Ignored);
+            }
+
+            // The money arrived after the seat hold was given up: it has to go back.
+            let late = sqlx::query_as!(
+                Payment,
+                "UPDATE payments SET status = $3, refund_amount = amount, updated_at = now()
+                    WHERE provider = $1 AND reference = $2 AND status = 'failed'
+                    RETURNING id, booking_id, provider, reference, amount, currency, status,
+                        failure_reason, refund_amount, created_at, updated_at",
+                provider,
+                reference,
+                PaymentStatus::RefundPending.as_str(),
+            )
+            .fetch_optional(&mut tx)
+            .await?;
+            tx.commit().await?;
+
+            return Ok(match late {
+                None => Settlement::Ignored,
+                Some(payment) => Settlement::RefundRequired { payment },
+            });
+        };
+
+        if !succeeded {
+            let released = sqlx::query!(
+                "WITH released AS (
+                    UPDATE bookings SET status = $2, hold_expires_at = NULL
+                    WHERE id = $1 AND status = 'pending'
+                    RETURNING id, journey_id, reserved_seats
+                ), freed AS (
+                    DELETE FROM booking_seats bs USING released r
+                    WHERE bs.booking_id = r.id
+                    RETURNING bs.seat_number
+                )
+                UPDATE trips tp SET reserved_seats = tp.reserved_seats - r.reserved_seats
+                    FROM released r
+                    WHERE tp.id = r.journey_id
+                    RETURNING tp.id",
+                &payment.booking_id,
+                BookingStatus::Failed.as_str(),
+            )
+            .fetch_optional(&mut tx)
+            .await?;
+            tx.commit().await?;
+
+            return Ok(match released {
+                None => Settlement::Ignored,
+                Some(r) => Settlement::Released {
+                    booking_id: payment.booking_id,
+                    journey_id: r.id,
+                },
+            });
+        }
+
+        let confirmed = sqlx::query!(
+            "UPDATE bookings SET status = $2, hold_expires_at = NULL
+                WHERE id = $1 AND status = 'pending'
+                RETURNING journey_id",
+            &payment.booking_id,
+            BookingStatus::Confirmed.as_str(),
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let settlement = match confirmed {
+            Some(c) => Settlement::Confirmed {
+                booking_id: payment.booking_id,
+                journey_id: c.journey_id,
+            },
+            None => Settlement::RefundRequired {
+                payment: sqlx::query_as!(
+                    Payment,
+                    "UPDATE payments SET status = $2, refund_amount = amount, updated_at = now()
+                    WHERE id = $1
+                    RETURNING id, booking_id, provider, reference, amount, currency, status,
+                        failure_reason, refund_amount, created_at, updated_at",
+                    &payment.id,
+                    PaymentStatus::RefundPending.as_str(),
+                )
+                .fetch_one(&mut tx)
+                .await?,
+            },
+        };
+
+        tx.commit().await?;
+        Ok(settlement)
+    }
+}
+
+#[async_trait]
+impl MarkPaymentRefunded for PaymentDataProvider {
+    async fn mark_payment_refunded(&mut self, payment_id: &Uuid) -> sqlx::Result<()> {
+        sqlx::query!(
+            "UPDATE payments SET status = $2, updated_at = now() WHERE id = $1",
+            payment_id,
+            PaymentStatus::Refunded.as_str(),
+        )
+        .execute(&mut *self.0)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ClaimPendingRefunds for PaymentDataProvider {
+    async fn claim_pending_refunds(
+        &mut self,
+        before: &DateTime<Utc>,
+    ) -> sqlx::Result<Vec<Payment>> {
+        sqlx::query_as!(
+            Payment,
+            "UPDATE payments SET updated_at = now()
+                WHERE status = $2 AND updated_at < $1
+                RETURNING id, booking_id, provider, reference, amount, currency, status,
+                    failure_reason, refund_amount, created_at, updated_at",
+            before,
+            PaymentStatus::RefundPending.as_str(),
+        )
+        .fetch_all(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl ReleaseExpiredHolds for PaymentDataProvider {
+    async fn release_expired_holds(&mut self) -> sqlx::Result<Vec<Uuid>> {
+        Ok(sqlx::query!(
+            r#"WITH expired AS (
+                UPDATE bookings SET status = $1, hold_expires_at = NULL
+                WHERE status = 'pending' AND hold_expires_at < now()
+                RETURNING id, journey_id, reserved_seats
+            ), freed AS (
+                DELETE FROM booking_seats bs USING expired e
+                WHERE bs.booking_id = e.id
+                RETURNING bs.seat_number
+            ), released AS (
+                UPDATE trips tp SET reserved_seats = tp.reserved_seats - e.seats
+                    FROM (SELECT journey_id, SUM(reserved_seats)::integer AS seats
+                            FROM expired GROUP BY journey_id) e
+                    WHERE tp.id = e.journey_id
+                    RETURNING tp.id
+            ), abandoned AS (
+                UPDATE payments p SET status = 'failed', failure_reason = 'seat hold expired',
+                        updated_at = now()
+                    FROM expired e
+                    WHERE p.booking_id = e.id AND p.status = 'pending'
+                    RETURNING p.id
+            )
+            SELECT id AS "id!" FROM released"#,
+            BookingStatus::Expired.as_str(),
+        )
+        .fetch_all(&mut *self.0)
+        .await?
+        .into_iter()
+        .map(|r| r.id)
+        .collect())
+    }
+}
@@ -0,0 +1,285 @@
+use async_trait::async_trait;
+use sqlx::pool::PoolConnection;
+use sqlx::{Acquire, PgConnection, Postgres};
+use uuid::Uuid;
+
+use database_derive::DataProvider;
+
+use crate::entity::{CheckIn, QueueEntry, QueueExit, QueueStatus};
+use crate::usecase::{
+    AdvanceRankQueue, CheckInRankQueue, DepartRankQueue, GetQueueEntry, GetRankQueue,
+    LeaveRankQueue,
+};
+
+#[derive(DataProvider)]
+pub struct QueueDataProvider(PoolConnection<Postgres>);
+
+impl QueueDataProvider {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
+        QueueDataProvider(conn)
+    }
+}
+
+#[async_trait]
+impl CheckInRankQueue for QueueDataProvider {
+    async fn check_in_rank_queue(&mut self, rank_id: &Uuid, taxi: &str) -> sqlx::Result<CheckIn> {
+        let mut tx = self.0.begin().await?;
+
+        // Check-ins and departures of a rank are serialized on its row.
+        let active = sqlx::query_scalar!(
+            "SELECT active FROM taxi_ranks WHERE id = $1 FOR UPDATE",
+            rank_id
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+        match active {
+            None => return Ok(CheckIn::UnknownRank),
+            Some(false) => return Ok(CheckIn::InactiveRank),
+            Some(true) => {}
+        }
+
+        let journey_id = sqlx::query_scalar!(
+            "SELECT id FROM trips WHERE owner = lower($1) AND origin = $2 AND closed = FALSE
+            ORDER BY departure_schedule
+            LIMIT 1",
+            taxi,
+            rank_id,
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+        let Some(journey_id) = journey_id else {
+            return Ok(CheckIn::NoJourney);
+        };
+
+        // The entry in the way may end before it is read, the insert is tried again then.
+        let id = loop {
+            let id = sqlx::query_scalar!(
+                "INSERT INTO rank_queue_entries(id, rank_id, taxi, journey_id, status)
+                VALUES ($1, $2, lower($3), $4, $5)
+                ON CONFLICT (taxi) WHERE status IN ('waiting', 'dispatched') DO NOTHING
+                RETURNING id",
+                Uuid::new_v4(),
+                rank_id,
+                taxi,
+                journey_id,
+                QueueStatus::Waiting.as_str(),
+            )
+            .fetch_optional(&mut tx)
+            .await?;
+
+            if let Some(id) = id {
+                break id;
+            }
+
+            let current = sqlx::query_scalar!(
+                "SELECT id FROM rank_queue_entries
+                WHERE taxi = lower($1) AND status IN ('waiting', 'dispatched')",
+                taxi,
+            )
+            .fetch_optional(&mut tx)
+            .await?;
+
+            if let Some(current) = current {
+                let entry = entry(&mut tx, &current).await?;
+                return Ok(entry.map_or(CheckIn::NoJourney, CheckIn::AlreadyQueued));
+            }
+        };
+
+        let dispatched = advance(&mut tx, rank_id).await?;
+        let entry = entry(&mut tx, &id).await?;
+
+        tx.commit().await?;
+
+        Ok(entry.map_or(CheckIn::NoJourney, |e| CheckIn::Queued(e, dispatched)))
+    }
+}
+
+#[async_trait]
+impl GetRankQueue for QueueDataProvider {
+    async fn get_rank_queue(&mut self, rank_id: &Uuid) -> sqlx::Result<Vec<QueueEntry>> {
+        sqlx::query_as!(
+            QueueEntry,
+            "SELECT rank_id, taxi, journey_id, status,
+                ROW_NUMBER() OVER (ORDER BY status = 'dispatched' DESC, seq) AS position,
+                checked_in_at, dispatched_at, ended_at
+            FROM rank_queue_entries
+            WHERE rank_id = $1 AND status IN ('waiting', 'dispatched')
+            ORDER BY position",
+            rank_id,
+        )
+        .fetch_all(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl GetQueueEntry for QueueDataProvider {
+    async fn get_queue_entry(
+        &mut self,
+        rank_id: &Uuid,
+        taxi: &str,
+    ) -> sqlx::Result<Option<QueueEntry>> {
+        let id = sqlx::query_scalar!(
+            "SELECT id FROM rank_queue_entries
+            WHERE rank_id = $1 AND taxi = lower($2)
+            ORDER BY seq DESC
+            LIMIT 1",
+            rank_id,
+            taxi,
+        )
+        .fetch_optional(&mut *self.0)
+        .await?;
+
+        match id {
+            None => Ok(None),
+            Some(id) => entry(&mut self.0, &id).await,
+        }
+    }
+}
+
+#[async_trait]
+impl LeaveRankQueue for QueueDataProvider {
+    async fn leave_rank_queue(
+        &mut self,
+        rank_id: &Uuid,
+        taxi: &str,
+    ) -> sqlx::Result<Option<QueueExit>> {
+        let mut tx = self.0.begin().await?;
+
+        sqlx::query!(
+            "SELECT id FROM taxi_ranks WHERE id = $1 FOR UPDATE",
+            rank_id
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let id = sqlx::query_scalar!(
+            "UPDATE rank_queue_entries SET status = $3, ended_at = now()
+            WHERE rank_id = $1 AND taxi = lower($2) AND status IN ('waiting', 'dispatched')
+            RETURNING id",
+            rank_id,
+            taxi,
+            QueueStatus::Left.as_str(),
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let Some(id) = id else {
+            return Ok(None);
+        };
+
+        let dispatched = advance(&mut tx, rank_id).await?;
+        let entry = entry(&mut tx, &id).await?;
+
+        tx.commit().await?;
+
+        Ok(entry.map(|entry| QueueExit { entry, dispatched }))
+    }
+}
+
+#[async_trait]
+impl DepartRankQueue for QueueDataProvider {
+    async fn depart_rank_queue(&mut self, journey_id: &Uuid) -> sqlx::Result<Option<QueueExit>> {
+        let mut tx = self.0.begin().await?;
+
+        let rank_id = sqlx::query_scalar!(
+            "SELECT rq.id FROM taxi_ranks rq
+                INNER JOIN rank_queue_entries e ON e.rank_id = rq.id
+            WHERE e.journey_id = $1 AND e.status IN ('waiting', 'dispatched')
+            FOR UPDATE OF rq",
+            journey_id,
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let Some(rank_id) = rank_id else {
+            return Ok(None);
+        };
+
+        let id = sqlx::query_scalar!(
+            "UPDATE rank_queue_entries
+            SET status = CASE WHEN status = 'dispatched' THEN $2 ELSE $3 END, ended_at = now()
+            WHERE journey_id = $1 AND status IN ('waiting', 'dispatched')
+            RETURNING id",
+            journey_id,
+            QueueStatus::Departed.as_str(),
+            QueueStatus::Left.as_str(),
+        )
+        .fetch_one(&mut tx)
+        .await?;
+
+        let dispatched = advance(&mut tx, &rank_id).await?;
+        let entry = entry(&mut tx, &id).await?;
+
+        tx.commit().await?;
+
+        Ok(entry.map(|entry| QueueExit { entry, dispatched }))
+    }
+}
+
+#[async_trait]
+impl AdvanceRankQueue for QueueDataProvider {
+    async fn advance_rank_queue(&mut self, rank_id: &Uuid) -> sqlx::Result<Option<Uuid>> {
+        let mut tx = self.0.begin().await?;
+
+        sqlx::query!(
+            "SELECT id FROM taxi_ranks WHERE id = $1 FOR UPDATE",
+            rank_id
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+        let dispatched = advance(&mut tx, rank_id).await?;
+
+        tx.commit().await?;
+
+        Ok(dispatched)
+    }
+}
+
+/// Moves the first waiting taxi to boarding unless another one is being loaded. The rank row
+/// must be locked by the caller.
+async fn advance(conn: &mut PgConnection, rank_id: &Uuid) -> sqlx::Result<Option<Uuid>> {
+    sqlx::query_scalar!(
+        "WITH head AS (
+            UPDATE rank_queue_entries SET status = $2, dispatched_at = now()
+            WHERE id = (
+                SELECT id FROM rank_queue_entries
+                WHERE rank_id = $1 AND status = 'waiting'
+                ORDER BY seq
+                LIMIT 1
+            ) AND NOT EXISTS(
+                SELECT 1 FROM rank_queue_entries WHERE rank_id = $1 AND status = 'dispatched'
+            )
+            RETURNING journey_id
+        )
+        UPDATE trips tp SET boarding_at = now()
+        FROM head
+        WHERE tp.id = head.journey_id
+        RETURNING tp.id",
+        rank_id,
+        QueueStatus::Dispatched.as_str(),
+    )
+    .fetch_optional(conn)
+    .await
+}
+
+async fn entry(conn: &mut PgConnection, id: &Uuid) -> sqlx::Result<Option<QueueEntry>> {
+    sqlx::query_as!(
+        QueueEntry,
+        "SELECT e.rank_id, e.taxi, e.journey_id, e.status,
+            CASE e.status
+                WHEN 'dispatched' THEN 1
+                WHEN 'waiting' THEN (
+                    SELECT COUNT(*) FROM rank_queue_entries q
+                    WHERE q.rank_id = e.rank_id
+                        AND (q.status = 'dispatched' OR (q.status = 'waiting' AND q.seq <= e.seq))
+                )
+            END AS position,
+            e.checked_in_at, e.dispatched_at, e.ended_at
+        FROM rank_queue_entries e
+        WHERE e.id = $1",
+        id,
+    )
+    .fetch_optional(conn)
+    .await
+}
@@ -1,18 +1,18 @@
 use async_trait::async_trait;
-use rocket_db_pools::Connection;
+use sqlx::pool::PoolConnection;
 use sqlx::Acquire;
+use sqlx::Postgres;
 
-use database::TXpressDB;
 use database_derive::DataProvider;
 
 use crate::entity::{Owner, Taxi};
 use crate::usecase::{AddTaxi, DoesTaxiExists};
 
 #[derive(DataProvider)]
-pub struct RegistrationDataProvider(Connection<TXpressDB>);
+pub struct RegistrationDataProvider(PoolConnection<Postgres>);
 
 impl RegistrationDataProvider {
-    fn new(conn: Connection<TXpressDB>) -> Self {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
         RegistrationDataProvider(conn)
     }
 }
@@ -33,10 +33,14 @@ impl AddTaxi for RegistrationDataProvider {
         .await?;
 
         sqlx::query!(
-            "INSERT INTO taxi_owners (id, full_name, password) VALUES (lower($1), $2, $3)",
+            "INSERT INTO taxi_owners (id, full_name, password, phone_number, email, language)
+                VALUES (lower($1), $2, $3, $4, $5, $6)",
             taxi.number,
             owner.full_name,
             owner.password,
+            owner.phone_number,
+            owner.email,
+            owner.language,
         )
         .execute(&mut tx)
         .await?;
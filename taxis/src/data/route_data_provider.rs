@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use sqlx::pool::PoolConnection;
+use sqlx::Postgres;
+use uuid::Uuid;
+
+use database_derive::DataProvider;
+
+use crate::entity::{NewRoute, Route, RouteChanges, RouteFilter};
+use crate::usecase::{AddRoute, GetRoute, GetRouteBetween, GetRoutes, UpdateRoute};
+
+#[derive(DataProvider)]
+pub struct RouteDataProvider(PoolConnection<Postgres>);
+
+impl RouteDataProvider {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
+        RouteDataProvider(conn)
+    }
+}
+
+#[async_trait]
+impl GetRoutes for RouteDataProvider {
+    async fn get_routes(&mut self, filter: &RouteFilter) -> sqlx::Result<Vec<Route>> {
+        sqlx::query_as!(
+            Route,
+            "SELECT id, origin, destination, distance_km, duration_minutes, active
+            FROM routes
+            WHERE ($1::uuid IS NULL OR origin = $1)
+                AND ($2::uuid IS NULL OR destination = $2)
+                AND ($3::boolean IS NULL OR active = $3)
+            ORDER BY created_at",
+            filter.origin,
+            filter.destination,
+            filter.active,
+        )
+        .fetch_all(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl GetRoute for RouteDataProvider {
+    async fn get_route(&mut self, id: &Uuid) -> sqlx::Result<Option<Route>> {
+        sqlx::query_as!(
+            Route,
+            "SELECT id, origin, destination, distance_km, duration_minutes, active
+            FROM routes
+            WHERE id = $1",
+            id,
+        )
+        .fetch_optional(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl GetRouteBetween for RouteDataProvider {
+    async fn get_route_between(
+        &mut self,
+        origin: &Uuid,
+        destination: &Uuid,
+    ) -> sqlx::Result<Option<Route>> {
+        sqlx::query_as!(
+            Route,
+            "SELECT id, origin, destination, distance_km, duration_minutes, active
+            FROM routes
+            WHERE origin = $1 AND destination = $2",
+            origin,
+            destination,
+        )
+        .fetch_optional(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl AddRoute for RouteDataProvider {
+    async fn add_route(&mut self, route: &NewRoute) -> sqlx::Result<Option<Route>> {
+        sqlx::query_as!(
+            Route,
+            "INSERT INTO routes(id, origin, destination, distance_km, duration_minutes, active)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (origin, destination) DO NOTHING
+            RETURNING id, origin, destination, distance_km, duration_minutes, active",
+            Uuid::new_v4(),
+            route.origin,
+            route.destination,
+            route.distance_km,
+            route.duration_minutes,
+            route.active,
+        )
+        .fetch_optional(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl UpdateRoute for RouteDataProvider {
+    async fn update_route(
+        &mut self,
+        id: &Uuid,
+        changes: &RouteChanges,
+    ) -> sqlx::Result<Option<Route>> {
+        sqlx::query_as!(
+            Route,
+            "UPDATE routes SET
+                distance_km = COALESCE($2, distance_km),
+                duration_minutes = COALESCE($3, duration_minutes),
+                active = COALESCE($4, active)
+            WHERE id = $1
+            RETURNING id, origin, destination, distance_km, duration_minutes, active",
+            id,
+            changes.distance_km,
+            changes.duration_minutes,
+            changes.active,
+        )
+        .fetch_optional(&mut *self.0)
+        .await
+    }
+}
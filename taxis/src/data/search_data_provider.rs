@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use sqlx::pool::PoolConnection;
+use sqlx::Postgres;
+use uuid::Uuid;
+
+use database_derive::DataProvider;
+
+use crate::entity::SearchedJourney;
+use crate::usecase::SearchJourneys;
+
+#[derive(DataProvider)]
+pub struct SearchDataProvider(PoolConnection<Postgres>);
+
+impl SearchDataProvider {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
+        SearchDataProvider(conn)
+    }
+}
+
+#[async_trait]
+impl SearchJourneys for SearchDataProvider {
+    async fn search_journeys(
+        &mut self,
+        origin: &Uuid,
+        destination: &Uuid,
+    ) -> sqlx::Result<Vec<SearchedJourney>> {
+        sqlx::query_as!(
+            SearchedJourney,
+            r#"SELECT tp.id, tp.owner AS taxi, t.brand, o.rank_id AS origin, d.rank_id AS destination,
+                COALESCE(o.scheduled_at, tp.departure_schedule) AS "departure_schedule!",
+                t.number_of_seats - journey_load(tp.id, o.position, d.position) AS "available_seats!",
+                CASE WHEN tp.origin = o.rank_id AND tp.destination = d.rank_id
+                    THEN COALESCE(tp.fare_amount, f.amount) ELSE f.amount END AS fare_amount,
+                CASE WHEN tp.origin = o.rank_id AND tp.destination = d.rank_id
+                    THEN COALESCE(tp.fare_currency, f.currency) ELSE f.currency END AS fare_currency
+            FROM trips tp
+                INNER JOIN taxis t ON t.number = tp.owner
+                INNER JOIN journey_stops o ON o.journey_id = tp.id AND o.rank_id = $1
+                INNER JOIN journey_stops d ON d.journey_id = tp.id AND d.rank_id = $2
+                LEFT JOIN fares f ON f.origin = o.rank_id AND f.destination = d.rank_id
+            WHERE o.position < d.position AND tp.closed = FALSE
+                AND journey_load(tp.id, o.position, d.position) < t.number_of_seats
+            ORDER BY COALESCE(o.scheduled_at, tp.departure_schedule)"#,
+            origin,
+            destination
+        )
+        .fetch_all(&mut *self.0)
+        .await
+    }
+}
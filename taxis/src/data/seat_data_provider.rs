@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use sqlx::pool::PoolConnection;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use database_derive::DataProvider;
+
+use crate::entity::{SeatMap, Segment};
+use crate::usecase::GetSeatMap;
+
+#[derive(DataProvider)]
+pub struct SeatDataProvider(PoolConnection<Postgres>);
+
+impl SeatDataProvider {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
+        SeatDataProvider(conn)
+    }
+}
+
+#[async_trait]
+impl GetSeatMap for SeatDataProvider {
+    async fn get_seat_map(
+        &mut self,
+        journey_id: &Uuid,
+        segment: Option<&Segment>,
+    ) -> sqlx::Result<Option<SeatMap>> {
+        sqlx::query_as!(
+            SeatMap,
+            r#"SELECT tp.id AS journey_id, t.number_of_seats,
+                ARRAY(
+                    SELECT DISTINCT seat_number FROM booking_seats
+                    WHERE journey_id = tp.id
+                        AND ($2::integer IS NULL
+                            OR int4range(board_stop, alight_stop) && int4range($2, $3))
+                    ORDER BY seat_number
+                ) AS "taken!"
+            FROM trips tp
+                INNER JOIN taxis t ON t.number = tp.owner
+            WHERE tp.id = $1"#,
+            journey_id,
+            segment.map(|s| s.board_stop),
+            segment.map(|s| s.alight_stop),
+        )
+        .fetch_optional(&mut *self.0)
+        .await
+    }
+}
+
+/// Gives `count` seats of the journey to the booking over its segment, either the requested ones
+/// or the first free ones (the front seat last). The trip row must already be locked by the
+/// transaction.
+pub(super) async fn assign_seats(
+    tx: &mut Transaction<'_, Postgres>,
+    journey_id: &Uuid,
+    booking_id: &Uuid,
+    number_of_seats: i32,
+    count: i32,
+    requested: Option<&[i32]>,
+    segment: &Segment,
+) -> sqlx::Result<Option<Vec<i32>>> {
+    let mut assigned = match requested {
+        Some(requested) => {
+            sqlx::query_scalar!(
+                "INSERT INTO booking_seats (journey_id, seat_number, booking_id, board_stop, alight_stop)
+                    SELECT $1, s, $2, $5, $6 FROM UNNEST($3::integer[]) s WHERE s <= $4
+                    ON CONFLICT DO NOTHING
+                    RETURNING seat_number",
+                journey_id,
+                booking_id,
+                requested,
+                number_of_seats,
+                segment.board_stop,
+                segment.alight_stop,
+            )
+            .fetch_all(&mut *tx)
+            .await?
+        }
+        None => {
+            sqlx::query_scalar!(
+                "INSERT INTO booking_seats (journey_id, seat_number, booking_id, board_stop, alight_stop)
+                    SELECT $1, s, $2, $5, $6 FROM generate_series(1, $3::integer) s
+                    WHERE NOT EXISTS (
+                        SELECT 1 FROM booking_seats bs
+                        WHERE bs.journey_id = $1 AND bs.seat_number = s
+                            AND int4range(bs.board_stop, bs.alight_stop) && int4range($5, $6)
+                    )
+                    ORDER BY s = 1, s
+                    LIMIT $4
+                    RETURNING seat_number",
+                journey_id,
+                booking_id,
+                number_of_seats,
+                count as i64,
+                segment.board_stop,
+                segment.alight_stop,
+            )
+            .fetch_all(&mut *tx)
+            .await?
+        }
+    };
+
+    if assigned.len() != count as usize {
+        return Ok(None);
+    }
+
+    assigned.sort();
+    Ok(Some(assigned))
+}
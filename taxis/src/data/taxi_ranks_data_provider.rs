@@ -1,60 +1,282 @@
 use async_trait::async_trait;
-use database::TXpressDB;
-use rocket_db_pools::Connection;
+use sqlx::pool::PoolConnection;
+use sqlx::{Acquire, Postgres, QueryBuilder};
 use uuid::Uuid;
 
-use crate::entity::Stand;
-use crate::usecase::{AddTaxiRanks, GetAllStand, GetStand};
+use crate::data::pagination::push_page;
+use crate::entity::{
+    AddedStands, NewStand, Page, PageRequest, Stand, StandChanges, StandDeletion, StandFilter,
+    StandSortField, StandUpdate,
+};
+use crate::geo::BoundingBox;
+use crate::usecase::{
+    AddTaxiRanks, DeleteStand, GetAllStand, GetStand, GetStands, GetStandsWithin, UpdateStand,
+};
 use database_derive::DataProvider;
 
 #[derive(DataProvider)]
-pub struct StandDataProvider(Connection<TXpressDB>);
+pub struct StandDataProvider(PoolConnection<Postgres>);
 
 impl StandDataProvider {
-    fn new(conn: Connection<TXpressDB>) -> Self {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
         StandDataProvider(conn)
     }
 }
 
 #[async_trait]
 impl GetAllStand for StandDataProvider {
-    async fn get_all_stand(&mut self) -> sqlx::Result<Vec<Stand>> {
-        sqlx::query_as!(Stand, "SELECT id, name FROM taxi_ranks")
+    async fn get_all_stand(
+        &mut self,
+        filter: &StandFilter,
+        page: &PageRequest,
+    ) -> sqlx::Result<Page<Stand>> {
+        let mut query = QueryBuilder::new(
+            "SELECT id, name, latitude, longitude, address, city, radius_meters, active
+            FROM taxi_ranks
+            WHERE (active OR ",
+        );
+        query.push_bind(filter.include_inactive).push(")");
+
+        if let Some(prefix) = &filter.name_prefix {
+            let pattern = prefix
+                .to_lowercase()
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            query
+                .push(" AND lower(name) LIKE ")
+                .push_bind(format!("{pattern}%"));
+        }
+        if let Some(city) = &filter.city {
+            query
+                .push(" AND lower(city) = ")
+                .push_bind(city.to_lowercase());
+        }
+
+        let column = match filter.sort.field {
+            StandSortField::Name => "name",
+            StandSortField::City => "COALESCE(city, '')",
+        };
+        push_page(&mut query, column, "", "id", filter.sort.order, page);
+
+        let rows = query
+            .build_query_as::<Stand>()
             .fetch_all(&mut *self.0)
-            .await
+            .await?;
+
+        Ok(Page::new(rows, page, |s| (filter.sort.field.key(s), s.id)))
     }
 }
 
 #[async_trait]
 impl GetStand for StandDataProvider {
     async fn get_stand(&mut self, id: &Uuid) -> sqlx::Result<Option<Stand>> {
-        sqlx::query_as!(Stand, "SELECT id, name FROM taxi_ranks WHERE id = $1", id)
-            .fetch_optional(&mut *self.0)
-            .await
+        sqlx::query_as!(
+            Stand,
+            "SELECT id, name, latitude, longitude, address, city, radius_meters, active
+            FROM taxi_ranks
+            WHERE id = $1",
+            id
+        )
+        .fetch_optional(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl GetStands for StandDataProvider {
+    async fn get_stands(&mut self, ids: &[Uuid]) -> sqlx::Result<Vec<Stand>> {
+        sqlx::query_as!(
+            Stand,
+            "SELECT id, name, latitude, longitude, address, city, radius_meters, active
+            FROM taxi_ranks
+            WHERE id = ANY($1)",
+            ids
+        )
+        .fetch_all(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl GetStandsWithin for StandDataProvider {
+    async fn get_stands_within(&mut self, bounds: &BoundingBox) -> sqlx::Result<Vec<Stand>> {
+        sqlx::query_as!(
+            Stand,
+            "SELECT id, name, latitude, longitude, address, city, radius_meters, active
+            FROM taxi_ranks
+            WHERE active AND latitude BETWEEN $1 AND $2 AND longitude BETWEEN $3 AND $4",
+            bounds.min_latitude,
+            bounds.max_latitude,
+            bounds.min_longitude,
+            bounds.max_longitude,
+        )
+        .fetch_all(&mut *self.0)
+        .await
     }
 }
 
 #[async_trait]
 impl AddTaxiRanks for StandDataProvider {
-    async fn add_taxi_ranks(&mut self, station_names: Vec<String>) -> sqlx::Result<Vec<Stand>> {
-        let mut ids = Vec::with_capacity(station_names.len());
-        for _ in 0..station_names.len() {
-            ids.push(Uuid::new_v4())
+    async fn add_taxi_ranks(&mut self, stands: Vec<NewStand>) -> sqlx::Result<AddedStands> {
+        let mut ids = Vec::with_capacity(stands.len());
+        let mut names = Vec::with_capacity(stands.len());
+        let mut latitudes = Vec::with_capacity(stands.len());
+        let mut longitudes = Vec::with_capacity(stands.len());
+        let mut addresses = Vec::with_capacity(stands.len());
+        let mut cities = Vec::with_capacity(stands.len());
+        let mut radiuses = Vec::with_capacity(stands.len());
+        for stand in &stands {
+            ids.push(Uuid::new_v4());
+            names.push(stand.name.clone());
+            latitudes.push(stand.coordinates.map(|c| c.latitude));
+            longitudes.push(stand.coordinates.map(|c| c.longitude));
+            addresses.push(stand.address.clone());
+            cities.push(stand.city.clone());
+            radiuses.push(stand.radius_meters);
         }
 
-        sqlx::query!(
-            "INSERT INTO taxi_ranks (id, name) SELECT * FROM UNNEST($1::uuid[], $2::text[])",
+        let mut tx = self.0.begin().await?;
+
+        let created = sqlx::query_as!(
+            Stand,
+            "INSERT INTO taxi_ranks (id, name, latitude, longitude, address, city, radius_meters)
+            SELECT * FROM UNNEST($1::uuid[], $2::text[], $3::float8[], $4::float8[], $5::text[],
+                $6::text[], $7::int4[])
+            ON CONFLICT ((lower(name))) DO NOTHING
+            RETURNING id, name, latitude, longitude, address, city, radius_meters, active",
             &ids,
-            &station_names
+            &names,
+            &latitudes as &[Option<f64>],
+            &longitudes as &[Option<f64>],
+            &addresses as &[Option<String>],
+            &cities as &[Option<String>],
+            &radiuses as &[Option<i32>],
         )
-        .execute(&mut *self.0)
-        .await
-        .map(|_| {
-            station_names
-                .into_iter()
-                .zip(ids)
-                .map(|(name, id)| Stand { id, name })
-                .collect::<Vec<Stand>>()
-        })
+        .fetch_all(&mut tx)
+        .await?;
+
+        let created_ids = created.iter().map(|s| s.id).collect::<Vec<_>>();
+        let existing = sqlx::query_as!(
+            Stand,
+            "SELECT id, name, latitude, longitude, address, city, radius_meters, active
+            FROM taxi_ranks
+            WHERE lower(name) IN (SELECT lower(n) FROM UNNEST($1::text[]) AS n)
+                AND id <> ALL($2)
+            ORDER BY name",
+            &names,
+            &created_ids,
+        )
+        .fetch_all(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(AddedStands { created, existing })
+    }
+}
+
+#[async_trait]
+impl UpdateStand for StandDataProvider {
+    async fn update_stand(
+        &mut self,
+        id: &Uuid,
+        changes: &StandChanges,
+    ) -> sqlx::Result<StandUpdate> {
+        let coordinates = changes.coordinates.flatten();
+
+        let mut tx = self.0.begin().await?;
+
+        if let Some(name) = &changes.name {
+            let taken = sqlx::query_scalar!(
+                "SELECT EXISTS(SELECT 1 FROM taxi_ranks WHERE lower(name) = lower($1) AND id <> $2)
+                    AS \"taken!\"",
+                name,
+                id,
+            )
+            .fetch_one(&mut tx)
+            .await?;
+
+            if taken {
+                return Ok(StandUpdate::NameTaken);
+            }
+        }
+
+        let stand = sqlx::query_as!(
+            Stand,
+            "UPDATE taxi_ranks SET
+                name = COALESCE($2, name),
+                active = COALESCE($3, active),
+                latitude = CASE WHEN $4 THEN $5 ELSE latitude END,
+                longitude = CASE WHEN $4 THEN $6 ELSE longitude END,
+                address = CASE WHEN $7 THEN $8 ELSE address END,
+                city = CASE WHEN $9 THEN $10 ELSE city END,
+                radius_meters = CASE WHEN $11 THEN $12 ELSE radius_meters END
+            WHERE id = $1
+            RETURNING id, name, latitude, longitude, address, city, radius_meters, active",
+            id,
+            changes.name,
+            changes.active,
+            changes.coordinates.is_some(),
+            coordinates.map(|c| c.latitude),
+            coordinates.map(|c| c.longitude),
+            changes.address.is_some(),
+            changes.address.clone().flatten(),
+            changes.city.is_some(),
+            changes.city.clone().flatten(),
+            changes.radius_meters.is_some(),
+            changes.radius_meters.flatten(),
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(stand.map_or(StandUpdate::Unknown, StandUpdate::Updated))
+    }
+}
+
+#[async_trait]
+impl DeleteStand for StandDataProvider {
+    async fn delete_stand(&mut self, id: &Uuid) -> sqlx::Result<StandDeletion> {
+        let mut tx = self.0.begin().await?;
+
+        let exists = sqlx::query_scalar!("SELECT id FROM taxi_ranks WHERE id = $1 FOR UPDATE", id)
+            .fetch_optional(&mut tx)
+            .await?;
+        if exists.is_none() {
+            return Ok(StandDeletion::Unknown);
+        }
+
+        let in_use = sqlx::query_scalar!(
+            "SELECT EXISTS(SELECT 1 FROM trips WHERE origin = $1 OR destination = $1)
+                OR EXISTS(SELECT 1 FROM journey_stops WHERE rank_id = $1) AS \"in_use!\"",
+            id,
+        )
+        .fetch_one(&mut tx)
+        .await?;
+        if in_use {
+            return Ok(StandDeletion::InUse);
+        }
+
+        sqlx::query!(
+            "DELETE FROM routes WHERE origin = $1 OR destination = $1",
+            id
+        )
+        .execute(&mut tx)
+        .await?;
+        sqlx::query!(
+            "DELETE FROM fares WHERE origin = $1 OR destination = $1",
+            id
+        )
+        .execute(&mut tx)
+        .await?;
+        sqlx::query!("DELETE FROM taxi_ranks WHERE id = $1", id)
+            .execute(&mut tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(StandDeletion::Deleted)
     }
 }
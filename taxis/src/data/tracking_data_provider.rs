@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::pool::PoolConnection;
+use sqlx::{Acquire, Postgres};
+use uuid::Uuid;
+
+use database_derive::DataProvider;
+
+use crate::entity::{Position, TrackedJourney, Viewer};
+use crate::usecase::{GetLatestPosition, GetTrackedJourney, PrunePositions, RecordPosition};
+
+#[derive(DataProvider)]
+pub struct TrackingDataProvider(PoolConnection<Postgres>);
+
+impl TrackingDataProvider {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
+        TrackingDataProvider(conn)
+    }
+}
+
+#[async_trait]
+impl GetTrackedJourney for TrackingDataProvider {
+    async fn get_tracked_journey(
+        &mut self,
+        journey_id: &Uuid,
+        viewer: &Viewer<'_>,
+    ) -> sqlx::Result<Option<TrackedJourney>> {
+        let (owner, bookings): (Option<&str>, &[Uuid]) = match viewer {
+            Viewer::Driver(taxi) => (Some(taxi), &[]),
+            Viewer::Passenger(bookings) => (None, bookings),
+        };
+
+        sqlx::query_as!(
+            TrackedJourney,
+            "SELECT tp.id AS journey_id, tp.departure_schedule, tp.closed,
+                d.latitude AS destination_latitude, d.longitude AS destination_longitude
+            FROM trips tp
+                INNER JOIN taxi_ranks d ON d.id = tp.destination
+            WHERE tp.id = $1
+                AND (tp.owner = lower($2)
+                    OR EXISTS(
+                        SELECT 1 FROM bookings b
+                        WHERE b.journey_id = tp.id AND b.id = ANY($3) AND b.status = 'confirmed'
+                    ))",
+            journey_id,
+            owner,
+            bookings,
+        )
+        .fetch_optional(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl GetLatestPosition for TrackingDataProvider {
+    async fn get_latest_position(&mut self, journey_id: &Uuid) -> sqlx::Result<Option<Position>> {
+        sqlx::query_as!(
+            Position,
+            "SELECT journey_id, latitude, longitude, speed_kmh, heading, recorded_at
+            FROM journey_positions
+            WHERE journey_id = $1
+            ORDER BY recorded_at DESC
+            LIMIT 1",
+            journey_id,
+        )
+        .fetch_optional(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl RecordPosition for TrackingDataProvider {
+    async fn record_position(&mut self, position: &Position, max_points: i64) -> sqlx::Result<()> {
+        let mut tx = self.0.begin().await?;
+
+        sqlx::query!(
+            "INSERT INTO journey_positions(journey_id, latitude, longitude, speed_kmh, heading, recorded_at)
+            VALUES ($1, $2, $3, $4, $5, $6)",
+            position.journey_id,
+            position.latitude,
+            position.longitude,
+            position.speed_kmh,
+            position.heading,
+            position.recorded_at,
+        )
+        .execute(&mut tx)
+        .await?;
+
+        sqlx::query!(
+            "DELETE FROM journey_positions
+            WHERE journey_id = $1 AND id IN (
+                SELECT id FROM journey_positions
+                WHERE journey_id = $1
+                ORDER BY recorded_at DESC
+                OFFSET $2
+            )",
+            position.journey_id,
+            max_points,
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await
+    }
+}
+
+#[async_trait]
+impl PrunePositions for TrackingDataProvider {
+    async fn prune_positions(&mut self, before: DateTime<Utc>) -> sqlx::Result<u64> {
+        let res = sqlx::query!(
+            "DELETE FROM journey_positions WHERE recorded_at < $1",
+            before
+        )
+        .execute(&mut *self.0)
+        .await?;
+
+        Ok(res.rows_affected())
+    }
+}
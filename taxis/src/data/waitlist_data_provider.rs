@@ -0,0 +1,382 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use sqlx::pool::PoolConnection;
+use sqlx::{Acquire, Postgres};
+use uuid::Uuid;
+
+use database_derive::DataProvider;
+
+use crate::data::seat_data_provider::assign_seats;
+use crate::entity::{
+    Booking, BookingStatus, NewBooking, NewWaitlistEntry, WaitlistEntry, WaitlistJoin,
+    WaitlistStatus,
+};
+use crate::usecase::{
+    ClaimWaitlistOffer, ExpireWaitlistOffers, GetWaitlistEntry, GetWaitlistedJourneys,
+    JoinWaitlist, LeaveWaitlist, PromoteWaitlist,
+};
+
+#[derive(DataProvider)]
+pub struct WaitlistDataProvider(PoolConnection<Postgres>);
+
+impl WaitlistDataProvider {
+    pub fn new(conn: PoolConnection<Postgres>) -> Self {
+        WaitlistDataProvider(conn)
+    }
+}
+
+#[async_trait]
+impl JoinWaitlist for WaitlistDataProvider {
+    async fn join_waitlist(&mut self, entry: &NewWaitlistEntry) -> sqlx::Result<WaitlistJoin> {
+        let mut tx = self.0.begin().await?;
+
+        let trip = sqlx::query!(
+            "SELECT t.number_of_seats, (SELECT MAX(position) FROM journey_stops WHERE journey_id = tp.id)
+                AS \"last_stop!\",
+                EXISTS (
+                    SELECT 1 FROM waitlist_entries w WHERE w.journey_id = tp.id AND w.status = $2
+                ) AS \"queued!\"
+            FROM trips tp
+                INNER JOIN taxis t ON t.number = tp.owner
+            WHERE tp.id = $1 AND tp.closed = FALSE
+            FOR UPDATE OF tp",
+            &entry.journey_id,
+            WaitlistStatus::Waiting.as_str(),
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let Some(trip) = trip else {
+            return Ok(WaitlistJoin::Unavailable);
+        };
+
+        // The head of the queue is never skipped, so an entry that can never fit would block it.
+        if entry.seats > trip.number_of_seats {
+            return Ok(WaitlistJoin::TooManySeats(trip.number_of_seats));
+        }
+
+        // Nobody may book while others wait; otherwise the seats have to be taken over the whole
+        // journey, as offers are.
+        if !trip.queued {
+            let load = sqlx::query_scalar!(
+                "SELECT journey_load($1, 0, $2) AS \"load!\"",
+                &entry.journey_id,
+                trip.last_stop,
+            )
+            .fetch_one(&mut tx)
+            .await?;
+
+            if load + entry.seats <= trip.number_of_seats {
+                return Ok(WaitlistJoin::SeatsAvailable);
+            }
+        }
+
+        sqlx::query!(
+            "INSERT INTO waitlist_entries (id, journey_id, seats, phone_number, status)
+                VALUES ($1, $2, $3, $4, $5)",
+            &entry.id,
+            &entry.journey_id,
+            entry.seats,
+            &entry.phone_number,
+            WaitlistStatus::Waiting.as_str(),
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(WaitlistJoin::Joined)
+    }
+}
+
+#[async_trait]
+impl GetWaitlistEntry for WaitlistDataProvider {
+    async fn get_waitlist_entry(&mut self, id: &Uuid) -> sqlx::Result<Option<WaitlistEntry>> {
+        sqlx::query_as!(
+            WaitlistEntry,
+            "SELECT w.id, w.journey_id, w.seats, w.phone_number, w.status,
+                CASE WHEN w.status = $2 THEN (
+                    SELECT COUNT(*) FROM waitlist_entries q
+                    WHERE q.journey_id = w.journey_id AND q.status = $2 AND q.seq <= w.seq
+                ) END AS position,
+                w.offer_expires_at, w.booking_id
+            FROM waitlist_entries w
+            WHERE w.id = $1",
+            id,
+            WaitlistStatus::Waiting.as_str(),
+        )
+        .fetch_optional(&mut *self.0)
+        .await
+    }
+}
+
+#[async_trait]
+impl LeaveWaitlist for WaitlistDataProvider {
+    async fn leave_waitlist(&mut self, id: &Uuid) -> sqlx::Result<Option<Uuid>> {
+        let mut tx = self.0.begin().await?;
+
+        let entry = sqlx::query!(
+            "SELECT journey_id, seats, status FROM waitlist_entries WHERE id = $1 FOR UPDATE",
+            id
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let waiting = WaitlistStatus::Waiting.as_str();
+        let offered = WaitlistStatus::Offered.as_str();
+        let Some(entry) = entry.filter(|e| e.status == waiting || e.status == offered) else {
+            return Ok(None);
+        };
+
+        sqlx::query!(
+            "UPDATE waitlist_entries SET status = $2 WHERE id = $1",
+            id,
+            WaitlistStatus::Cancelled.as_str(),
+        )
+        .execute(&mut tx)
+        .await?;
+
+        // An open offer was holding seats on the trip.
+        if entry.status == offered {
+            sqlx::query!(
+                "UPDATE trips SET reserved_seats = reserved_seats - $2 WHERE id = $1",
+                &entry.journey_id,
+                entry.seats,
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(Some(entry.journey_id))
+    }
+}
+
+#[async_trait]
+impl ClaimWaitlistOffer for WaitlistDataProvider {
+    async fn claim_waitlist_offer(
+        &mut self,
+        id: &Uuid,
+        booking: &NewBooking,
+    ) -> sqlx::Result<Option<Booking>> {
+        let mut tx = self.0.begin().await?;
+
+        let offer = sqlx::query!(
+            "SELECT w.seats, tp.owner, tp.departure_schedule, t.number_of_seats
+            FROM waitlist_entries w
+                INNER JOIN trips tp ON tp.id = w.journey_id
+                INNER JOIN taxis t ON t.number = tp.owner
+            WHERE w.id = $1 AND w.journey_id = $2 AND w.status = $3
+                AND w.offer_expires_at > now()
+            FOR UPDATE OF w, tp",
+            id,
+            &booking.journey_id,
+            WaitlistStatus::Offered.as_str(),
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let Some(offer) = offer else {
+            return Ok(None);
+        };
+
+        // The seats are already counted in `trips.reserved_seats` since the offer was made.
+        let created_at = Utc::now();
+        let hold_expires_at = created_at + booking.hold;
+        sqlx::query!(
+            "INSERT INTO bookings
+                (id, journey_id, reserved_seats, status, phone_number, amount, currency, created_at,
+                    hold_expires_at, board_stop, alight_stop)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+            &booking.id,
+            &booking.journey_id,
+            offer.seats,
+            BookingStatus::Pending.as_str(),
+            &booking.phone_number,
+            booking.price.amount as i64,
+            booking.price.currency.code(),
+            created_at,
+            hold_expires_at,
+            booking.segment.board_stop,
+            booking.segment.alight_stop,
+        )
+        .execute(&mut tx)
+        .await?;
+
+        let seat_numbers = assign_seats(
+            &mut tx,
+            &booking.journey_id,
+            &booking.id,
+            offer.number_of_seats,
+            offer.seats,
+            None,
+            &booking.segment,
+        )
+        .await?;
+
+        let Some(seat_numbers) = seat_numbers else {
+            return Ok(None);
+        };
+
+        sqlx::query!(
+            "UPDATE waitlist_entries SET status = $3, booking_id = $2 WHERE id = $1",
+            id,
+            &booking.id,
+            WaitlistStatus::Claimed.as_str(),
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Some(Booking {
+            id: booking.id,
+            journey_id: booking.journey_id,
+            taxi: offer.owner,
+            departure_schedule: offer.departure_schedule,
+            origin: booking.segment.origin,
+            destination: booking.segment.destination,
+            reserved_seats: offer.seats,
+            status: BookingStatus::Pending.as_str().into(),
+            amount: Some(booking.price.amount as i64),
+            currency: Some(booking.price.currency.code().into()),
+            hold_expires_at: Some(hold_expires_at),
+            cancellation_reason: None,
+            refund_amount: None,
+            boarded_at: None,
+            seat_numbers,
+        }))
+    }
+}
+
+#[async_trait]
+impl PromoteWaitlist for WaitlistDataProvider {
+    async fn promote_waitlist(
+        &mut self,
+        journey_id: &Uuid,
+        offer_ttl: Duration,
+    ) -> sqlx::Result<Vec<WaitlistEntry>> {
+        let mut tx = self.0.begin().await?;
+
+        let trip = sqlx::query!(
+            "SELECT t.number_of_seats, (SELECT MAX(position) FROM journey_stops WHERE journey_id = tp.id)
+                AS \"last_stop!\"
+            FROM trips tp
+                INNER JOIN taxis t ON t.number = tp.owner
+            WHERE tp.id = $1 AND tp.closed = FALSE
+            FOR UPDATE OF tp",
+            journey_id
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let Some(trip) = trip else {
+            return Ok(vec![]);
+        };
+
+        // Offers hold their seats over the whole journey, so only the busiest leg matters.
+        let load = sqlx::query_scalar!(
+            "SELECT journey_load($1, 0, $2) AS \"load!\"",
+            journey_id,
+            trip.last_stop,
+        )
+        .fetch_one(&mut tx)
+        .await?;
+        let available = trip.number_of_seats - load;
+
+        let queue = sqlx::query!(
+            "SELECT id, seats FROM waitlist_entries
+            WHERE journey_id = $1 AND status = $2
+            ORDER BY seq
+            FOR UPDATE",
+            journey_id,
+            WaitlistStatus::Waiting.as_str(),
+        )
+        .fetch_all(&mut tx)
+        .await?;
+
+        // Strict FIFO: the head of the queue is never skipped for a smaller request behind it.
+        let mut free = available;
+        let mut offered = vec![];
+        for entry in queue {
+            if entry.seats > free {
+                break;
+            }
+            free -= entry.seats;
+            offered.push(entry.id);
+        }
+
+        if offered.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let offers = sqlx::query_as!(
+            WaitlistEntry,
+            "UPDATE waitlist_entries
+                SET status = $3, offered_at = now(), offer_expires_at = $2
+                WHERE id = ANY($1)
+                RETURNING id, journey_id, seats, phone_number, status, NULL::bigint AS position,
+                    offer_expires_at, booking_id",
+            &offered,
+            Utc::now() + offer_ttl,
+            WaitlistStatus::Offered.as_str(),
+        )
+        .fetch_all(&mut tx)
+        .await?;
+
+        sqlx::query!(
+            "UPDATE trips SET reserved_seats = reserved_seats + $2 WHERE id = $1",
+            journey_id,
+            available - free,
+        )
+        .execute(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(offers)
+    }
+}
+
+#[async_trait]
+impl ExpireWaitlistOffers for WaitlistDataProvider {
+    async fn expire_waitlist_offers(&mut self) -> sqlx::Result<Vec<Uuid>> {
+        Ok(sqlx::query!(
+            r#"WITH expired AS (
+                UPDATE waitlist_entries SET status = $1
+                WHERE status = $2 AND offer_expires_at < now()
+                RETURNING journey_id, seats
+            ), released AS (
+                UPDATE trips tp SET reserved_seats = tp.reserved_seats - e.seats
+                    FROM (SELECT journey_id, SUM(seats)::integer AS seats
+                            FROM expired GROUP BY journey_id) e
+                    WHERE tp.id = e.journey_id
+                    RETURNING tp.id
+            )
+            SELECT id AS "id!" FROM released"#,
+            WaitlistStatus::Expired.as_str(),
+            WaitlistStatus::Offered.as_str(),
+        )
+        .fetch_all(&mut *self.0)
+        .await?
+        .into_iter()
+        .map(|r| r.id)
+        .collect())
+    }
+}
+
+#[async_trait]
+impl GetWaitlistedJourneys for WaitlistDataProvider {
+    async fn get_waitlisted_journeys(&mut self) -> sqlx::Result<Vec<Uuid>> {
+        Ok(sqlx::query!(
+            "SELECT DISTINCT w.journey_id
+            FROM waitlist_entries w
+                INNER JOIN trips tp ON tp.id = w.journey_id
+            WHERE w.status = $1 AND tp.closed = FALSE",
+            WaitlistStatus::Waiting.as_str(),
+        )
+        .fetch_all(&mut *self.0)
+        .await?
+        .into_iter()
+        .map(|r| r.journey_id)
+        .collect())
+    }
+}
@@ -1,11 +1,42 @@
-pub use journey::Journey;
+pub use boarding::{Boarding, Manifest, ManifestEntry};
+pub use booking::{Booking, BookingStatus, NewBooking};
+pub use fare::Fare;
+pub use journey::{Journey, JourneyFilter, JourneySortField, JourneyStatus};
 pub use journey_criteria::JourneyCriteria;
+pub use journey_stop::{JourneyStop, NewStop, Segment};
+pub use notification::{
+    Audience, Channel, DeliveryAttempt, NewNotification, Notification, NotificationEvent, Recipient,
+};
 pub use owner::Owner;
-pub use stand::Stand;
+pub use page::{Cursor, Page, PageRequest, Sort, SortOrder};
+pub use payment::{Payment, PaymentStatus, Settlement};
+pub use position::{Position, TrackedJourney, Viewer};
+pub use queue_entry::{CheckIn, QueueEntry, QueueExit, QueueStatus};
+pub use route::{NewRoute, Route, RouteChanges, RouteFilter};
+pub use searched_journey::SearchedJourney;
+pub use seat::{seat_label, seat_number, SeatMap};
+pub use stand::{
+    AddedStands, NewStand, Stand, StandChanges, StandDeletion, StandFilter, StandSortField,
+    StandUpdate,
+};
 pub use taxi::Taxi;
+pub use waitlist_entry::{NewWaitlistEntry, WaitlistEntry, WaitlistJoin, WaitlistStatus};
 
+mod boarding;
+mod booking;
+mod fare;
 mod journey;
 mod journey_criteria;
+mod journey_stop;
+mod notification;
 mod owner;
+mod page;
+mod payment;
+mod position;
+mod queue_entry;
+mod route;
+mod searched_journey;
+mod seat;
 mod stand;
 mod taxi;
+mod waitlist_entry;
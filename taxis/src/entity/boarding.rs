@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+pub enum Boarding {
+    Boarded {
+        seats: i32,
+        boarded_at: DateTime<Utc>,
+        boarded_seats: i64,
+        expected_seats: i64,
+    },
+    AlreadyBoarded {
+        boarded_at: DateTime<Utc>,
+    },
+    NotConfirmed {
+        status: String,
+    },
+    Unknown,
+}
+
+pub struct Manifest {
+    pub journey_id: Uuid,
+    pub departure_schedule: DateTime<Utc>,
+    pub entries: Vec<ManifestEntry>,
+}
+
+pub struct ManifestEntry {
+    pub booking_id: Uuid,
+    pub seats: i32,
+    pub boarded_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,74 @@
+use chrono::{DateTime, Duration, Utc};
+use domain::entity::Price;
+use uuid::Uuid;
+
+use crate::entity::fare::to_price;
+use crate::entity::Segment;
+
+pub struct Booking {
+    pub id: Uuid,
+    pub journey_id: Uuid,
+    pub taxi: String,
+    pub departure_schedule: DateTime<Utc>,
+    /// Ranks where the passenger boards and alights.
+    pub origin: Uuid,
+    pub destination: Uuid,
+    pub reserved_seats: i32,
+    pub status: String,
+    pub amount: Option<i64>,
+    pub currency: Option<String>,
+    pub hold_expires_at: Option<DateTime<Utc>>,
+    pub cancellation_reason: Option<String>,
+    pub refund_amount: Option<i64>,
+    pub boarded_at: Option<DateTime<Utc>>,
+    pub seat_numbers: Vec<i32>,
+}
+
+impl Booking {
+    pub fn price(&self) -> Option<Price> {
+        to_price(self.amount, self.currency.as_deref())
+    }
+
+    pub fn refund(&self) -> Option<Price> {
+        to_price(self.refund_amount, self.currency.as_deref())
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == BookingStatus::Pending.as_str()
+            || self.status == BookingStatus::Confirmed.as_str()
+    }
+}
+
+pub struct NewBooking {
+    pub id: Uuid,
+    pub journey_id: Uuid,
+    pub seats: i32,
+    pub phone_number: String,
+    pub email: Option<String>,
+    pub language: Option<String>,
+    pub price: Price,
+    pub hold: Duration,
+    pub seat_numbers: Option<Vec<i32>>,
+    pub segment: Segment,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BookingStatus {
+    Pending,
+    Confirmed,
+    Expired,
+    Failed,
+    Cancelled,
+}
+
+impl BookingStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BookingStatus::Pending => "pending",
+            BookingStatus::Confirmed => "confirmed",
+            BookingStatus::Expired => "expired",
+            BookingStatus::Failed => "failed",
+            BookingStatus::Cancelled => "cancelled",
+        }
+    }
+}
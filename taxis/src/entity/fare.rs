@@ -0,0 +1,31 @@
+use domain::entity::{Currency, Price};
+use uuid::Uuid;
+
+pub struct Fare {
+    pub origin: Uuid,
+    pub destination: Uuid,
+    pub amount: i64,
+    pub currency: String,
+}
+
+impl Fare {
+    pub fn new(origin: Uuid, destination: Uuid, price: &Price) -> Self {
+        Fare {
+            origin,
+            destination,
+            amount: price.amount as i64,
+            currency: price.currency.code().into(),
+        }
+    }
+
+    pub fn price(&self) -> Option<Price> {
+        to_price(Some(self.amount), Some(&self.currency))
+    }
+}
+
+pub(crate) fn to_price(amount: Option<i64>, currency: Option<&str>) -> Option<Price> {
+    let amount = u64::try_from(amount?).ok()?;
+    let currency = currency?.parse::<Currency>().ok()?;
+
+    Some(Price::new(amount, currency))
+}
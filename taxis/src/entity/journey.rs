@@ -1,6 +1,11 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use domain::entity::Price;
 use uuid::Uuid;
 
+use crate::entity::fare::to_price;
+use crate::entity::Sort;
+
+#[derive(sqlx::FromRow)]
 pub struct Journey {
     pub id: Uuid,
     pub origin: Uuid,
@@ -8,4 +13,63 @@ pub struct Journey {
     pub reserved_seats: i32,
     pub departure_schedule: DateTime<Utc>,
     pub closed: bool,
+    pub fare_amount: Option<i64>,
+    pub fare_currency: Option<String>,
+    /// None when the journey follows a route missing from the catalogue.
+    pub route_id: Option<Uuid>,
+    pub duration_minutes: Option<i32>,
+    /// Set once the taxi reaches the head of its rank queue.
+    pub boarding_at: Option<DateTime<Utc>>,
+}
+
+impl Journey {
+    pub fn fare(&self) -> Option<Price> {
+        to_price(self.fare_amount, self.fare_currency.as_deref())
+    }
+
+    pub fn estimated_arrival(&self) -> Option<DateTime<Utc>> {
+        self.duration_minutes
+            .map(|minutes| self.departure_schedule + Duration::minutes(i64::from(minutes)))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JourneyStatus {
+    Open,
+    Closed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JourneySortField {
+    DepartureSchedule,
+    ReservedSeats,
+}
+
+impl JourneySortField {
+    /// The value of the field for the journey, as kept in a cursor.
+    pub fn key(&self, journey: &Journey) -> String {
+        match self {
+            JourneySortField::DepartureSchedule => journey.departure_schedule.to_rfc3339(),
+            JourneySortField::ReservedSeats => journey.reserved_seats.to_string(),
+        }
+    }
+
+    /// Whether a cursor key can be a value of the field.
+    pub fn accepts(&self, key: &str) -> bool {
+        match self {
+            JourneySortField::DepartureSchedule => DateTime::parse_from_rfc3339(key).is_ok(),
+            JourneySortField::ReservedSeats => key.parse::<i32>().is_ok(),
+        }
+    }
+}
+
+pub struct JourneyFilter {
+    pub status: Option<JourneyStatus>,
+    /// Departures from this time on.
+    pub departs_after: Option<DateTime<Utc>>,
+    /// Departures strictly before this time.
+    pub departs_before: Option<DateTime<Utc>>,
+    pub origin: Option<Uuid>,
+    pub destination: Option<Uuid>,
+    pub sort: Sort<JourneySortField>,
 }
@@ -1,8 +1,15 @@
 use chrono::{DateTime, Utc};
+use domain::entity::Price;
 use uuid::Uuid;
 
+use crate::entity::{NewStop, Route};
+
 pub struct JourneyCriteria {
     pub origin: Uuid,
     pub destination: Uuid,
     pub departure_schedule: DateTime<Utc>,
+    pub fare: Option<Price>,
+    pub route: Option<Route>,
+    /// The calls between the origin and the destination, in order.
+    pub stops: Vec<NewStop>,
 }
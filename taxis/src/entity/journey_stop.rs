@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+pub struct JourneyStop {
+    pub position: i32,
+    pub rank_id: Uuid,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    /// Free seats on the leg leaving the stop, none for the last one.
+    pub available_seats: Option<i32>,
+}
+
+pub struct NewStop {
+    pub rank_id: Uuid,
+    pub scheduled_at: DateTime<Utc>,
+}
+
+/// The part of a journey a booking travels, between the positions of two of its stops.
+#[derive(Clone, Copy)]
+pub struct Segment {
+    pub board_stop: i32,
+    pub alight_stop: i32,
+    pub origin: Uuid,
+    pub destination: Uuid,
+}
+
+impl Segment {
+    pub fn between(stops: &[JourneyStop], origin: &Uuid, destination: &Uuid) -> Option<Segment> {
+        let board = stops.iter().find(|s| &s.rank_id == origin)?;
+        let alight = stops.iter().find(|s| &s.rank_id == destination)?;
+
+        (board.position < alight.position).then_some(Segment {
+            board_stop: board.position,
+            alight_stop: alight.position,
+            origin: board.rank_id,
+            destination: alight.rank_id,
+        })
+    }
+
+    pub fn whole(stops: &[JourneyStop]) -> Option<Segment> {
+        let (first, last) = (stops.first()?, stops.last()?);
+        Segment::between(stops, &first.rank_id, &last.rank_id)
+    }
+
+    pub fn is_whole(&self, stops: &[JourneyStop]) -> bool {
+        Some(self.board_stop) == stops.first().map(|s| s.position)
+            && Some(self.alight_stop) == stops.last().map(|s| s.position)
+    }
+}
@@ -0,0 +1,82 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationEvent {
+    BookingConfirmed,
+    JourneyCancelled,
+    DepartureImminent,
+}
+
+impl NotificationEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationEvent::BookingConfirmed => "booking_confirmed",
+            NotificationEvent::JourneyCancelled => "journey_cancelled",
+            NotificationEvent::DepartureImminent => "departure_imminent",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Channel {
+    Sms,
+    Email,
+}
+
+impl Channel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Channel::Sms => "sms",
+            Channel::Email => "email",
+        }
+    }
+}
+
+/// Whom an event concerns and what the templates need to know about the journey.
+pub struct Audience {
+    pub journey_id: Uuid,
+    pub taxi: String,
+    pub origin: String,
+    pub destination: String,
+    pub departure_schedule: DateTime<Utc>,
+    pub recipients: Vec<Recipient>,
+}
+
+pub struct Recipient {
+    pub booking_id: Option<Uuid>,
+    pub phone_number: Option<String>,
+    pub email: Option<String>,
+    pub language: Option<String>,
+    pub seats: Option<i32>,
+}
+
+pub struct NewNotification {
+    pub id: Uuid,
+    pub event: NotificationEvent,
+    pub channel: Channel,
+    pub recipient: String,
+    pub language: String,
+    pub subject: Option<String>,
+    pub body: String,
+    pub journey_id: Option<Uuid>,
+    pub booking_id: Option<Uuid>,
+    pub dedupe_key: Option<String>,
+}
+
+pub struct Notification {
+    pub id: Uuid,
+    pub channel: String,
+    pub recipient: String,
+    pub subject: Option<String>,
+    pub body: String,
+    pub attempts: i32,
+}
+
+pub struct DeliveryAttempt<'a> {
+    pub notification_id: Uuid,
+    pub transport: &'a str,
+    pub error: Option<String>,
+    /// When to try again after a failure, `None` once the notification is given up.
+    pub retry_at: Option<DateTime<Utc>>,
+}
@@ -1,6 +1,9 @@
 pub struct Owner {
     pub full_name: String,
     pub password: String,
+    pub phone_number: Option<String>,
+    pub email: Option<String>,
+    pub language: String,
 }
 
 impl Owner {
@@ -8,6 +11,9 @@ impl Owner {
         Owner {
             full_name: name.into(),
             password: password.into(),
+            phone_number: None,
+            email: None,
+            language: String::from("fr"),
         }
     }
 }
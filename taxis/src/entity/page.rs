@@ -0,0 +1,73 @@
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// A whitelisted field to sort a listing by, and in which order.
+#[derive(Clone, Copy)]
+pub struct Sort<F> {
+    pub field: F,
+    pub order: SortOrder,
+}
+
+/// Where a page starts: right after the item with this sort key and id, or right before it when
+/// going backward.
+#[derive(Clone)]
+pub struct Cursor {
+    pub key: String,
+    pub id: Uuid,
+    pub backward: bool,
+}
+
+pub struct PageRequest {
+    pub cursor: Option<Cursor>,
+    pub limit: usize,
+}
+
+impl PageRequest {
+    pub fn is_backward(&self) -> bool {
+        self.cursor.as_ref().is_some_and(|c| c.backward)
+    }
+}
+
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<Cursor>,
+    pub prev: Option<Cursor>,
+}
+
+impl<T> Page<T> {
+    /// Builds a page from the rows read in the direction of the request, one more than the limit
+    /// being read to tell whether the listing goes on.
+    pub fn new(
+        mut rows: Vec<T>,
+        request: &PageRequest,
+        key: impl Fn(&T) -> (String, Uuid),
+    ) -> Self {
+        let backward = request.is_backward();
+        let more = rows.len() > request.limit;
+        rows.truncate(request.limit);
+        if backward {
+            rows.reverse();
+        }
+
+        let (has_prev, has_next) = if backward {
+            (more, true)
+        } else {
+            (request.cursor.is_some(), more)
+        };
+        let cursor = |item: &T, backward: bool| {
+            let (key, id) = key(item);
+            Cursor { key, id, backward }
+        };
+
+        Page {
+            next: rows.last().filter(|_| has_next).map(|i| cursor(i, false)),
+            prev: rows.first().filter(|_| has_prev).map(|i| cursor(i, true)),
+            items: rows,
+        }
+    }
+}
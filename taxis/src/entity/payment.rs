@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use domain::entity::Price;
+use uuid::Uuid;
+
+use crate::entity::fare::to_price;
+
+pub struct Payment {
+    pub id: Uuid,
+    pub booking_id: Uuid,
+    pub provider: String,
+    pub reference: String,
+    pub amount: i64,
+    pub currency: String,
+    pub status: String,
+    pub failure_reason: Option<String>,
+    /// What is to go back to the passenger, once the payment is to be refunded.
+    pub refund_amount: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Payment {
+    /// A pending payment, referred to by its id until the provider gives its own reference.
+    pub fn new(booking_id: Uuid, provider: &str, price: &Price) -> Self {
+        let now = Utc::now();
+        let id = Uuid::new_v4();
+        Payment {
+            id,
+            booking_id,
+            provider: provider.into(),
+            reference: id.to_string(),
+            amount: price.amount as i64,
+            currency: price.currency.code().into(),
+            status: PaymentStatus::Pending.as_str().into(),
+            failure_reason: None,
+            refund_amount: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn price(&self) -> Option<Price> {
+        to_price(Some(self.amount), Some(&self.currency))
+    }
+
+    pub fn refund(&self) -> Option<Price> {
+        to_price(self.refund_amount, Some(&self.currency))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaymentStatus {
+    Pending,
+    Succeeded,
+    Failed,
+    /// Paid, but to be given back, which the payments worker retries until the provider agrees.
+    RefundPending,
+    Refunded,
+}
+
+impl PaymentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentStatus::Pending => "pending",
+            PaymentStatus::Succeeded => "succeeded",
+            PaymentStatus::Failed => "failed",
+            PaymentStatus::RefundPending => "refund_pending",
+            PaymentStatus::Refunded => "refunded",
+        }
+    }
+}
+
+pub enum Settlement {
+    Confirmed { booking_id: Uuid, journey_id: Uuid },
+    Released { booking_id: Uuid, journey_id: Uuid },
+    RefundRequired { payment: Payment },
+    Ignored,
+}
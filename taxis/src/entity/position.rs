@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::geo::Coordinates;
+
+pub struct Position {
+    pub journey_id: Uuid,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub speed_kmh: Option<f64>,
+    pub heading: Option<f64>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl Position {
+    pub fn coordinates(&self) -> Coordinates {
+        Coordinates {
+            latitude: self.latitude,
+            longitude: self.longitude,
+        }
+    }
+}
+
+pub struct TrackedJourney {
+    pub journey_id: Uuid,
+    pub departure_schedule: DateTime<Utc>,
+    pub closed: bool,
+    pub destination_latitude: Option<f64>,
+    pub destination_longitude: Option<f64>,
+}
+
+impl TrackedJourney {
+    pub fn destination(&self) -> Option<Coordinates> {
+        Coordinates::new(self.destination_latitude?, self.destination_longitude?)
+    }
+}
+
+/// Who asks for the location of a journey.
+pub enum Viewer<'a> {
+    Driver(&'a str),
+    /// Bookings the requester holds credentials for.
+    Passenger(&'a [Uuid]),
+}
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+pub struct QueueEntry {
+    pub rank_id: Uuid,
+    pub taxi: String,
+    pub journey_id: Uuid,
+    pub status: String,
+    /// 1 for the taxi being loaded, then the waiting ones in arrival order.
+    pub position: Option<i64>,
+    pub checked_in_at: DateTime<Utc>,
+    pub dispatched_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueueStatus {
+    Waiting,
+    Dispatched,
+    Departed,
+    Left,
+}
+
+impl QueueStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueueStatus::Waiting => "waiting",
+            QueueStatus::Dispatched => "dispatched",
+            QueueStatus::Departed => "departed",
+            QueueStatus::Left => "left",
+        }
+    }
+}
+
+pub enum CheckIn {
+    /// The entry, and the journey dispatched as a consequence if any.
+    Queued(QueueEntry, Option<Uuid>),
+    AlreadyQueued(QueueEntry),
+    /// The taxi has no journey in progress departing from the rank.
+    NoJourney,
+    InactiveRank,
+    UnknownRank,
+}
+
+pub struct QueueExit {
+    pub entry: QueueEntry,
+    pub dispatched: Option<Uuid>,
+}
@@ -0,0 +1,32 @@
+use uuid::Uuid;
+
+pub struct Route {
+    pub id: Uuid,
+    pub origin: Uuid,
+    pub destination: Uuid,
+    pub distance_km: f64,
+    pub duration_minutes: i32,
+    pub active: bool,
+}
+
+pub struct NewRoute {
+    pub origin: Uuid,
+    pub destination: Uuid,
+    pub distance_km: f64,
+    pub duration_minutes: i32,
+    pub active: bool,
+}
+
+#[derive(Default)]
+pub struct RouteChanges {
+    pub distance_km: Option<f64>,
+    pub duration_minutes: Option<i32>,
+    pub active: Option<bool>,
+}
+
+#[derive(Default)]
+pub struct RouteFilter {
+    pub origin: Option<Uuid>,
+    pub destination: Option<Uuid>,
+    pub active: Option<bool>,
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use domain::entity::Price;
+use uuid::Uuid;
+
+use crate::entity::fare::to_price;
+
+pub struct SearchedJourney {
+    pub id: Uuid,
+    pub taxi: String,
+    pub brand: String,
+    pub origin: Uuid,
+    pub destination: Uuid,
+    pub departure_schedule: DateTime<Utc>,
+    pub available_seats: i32,
+    pub fare_amount: Option<i64>,
+    pub fare_currency: Option<String>,
+}
+
+impl SearchedJourney {
+    pub fn fare(&self) -> Option<Price> {
+        to_price(self.fare_amount, self.fare_currency.as_deref())
+    }
+}
@@ -0,0 +1,37 @@
+use uuid::Uuid;
+
+/// Seat 1 is the front seat next to the driver, the others are laid out by rows of three.
+const ROW_WIDTH: i32 = 3;
+const FRONT: &'static str = "F";
+
+pub fn seat_label(number: i32) -> String {
+    if number == 1 {
+        return FRONT.into();
+    }
+
+    let index = number - 2;
+    let letter = (b'A' + (index % ROW_WIDTH) as u8) as char;
+    format!("{}{letter}", index / ROW_WIDTH + 1)
+}
+
+pub fn seat_number(label: &str) -> Option<i32> {
+    let label = label.trim().to_uppercase();
+    if label == FRONT {
+        return Some(1);
+    }
+
+    let letter = label.chars().last().filter(|c| c.is_ascii_uppercase())?;
+    let column = letter as i32 - 'A' as i32;
+    if column >= ROW_WIDTH {
+        return None;
+    }
+
+    let row: i32 = label[..label.len() - 1].parse().ok().filter(|r| *r > 0)?;
+    Some((row - 1) * ROW_WIDTH + column + 2)
+}
+
+pub struct SeatMap {
+    pub journey_id: Uuid,
+    pub number_of_seats: i32,
+    pub taken: Vec<i32>,
+}
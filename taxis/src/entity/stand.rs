@@ -1,6 +1,90 @@
 use uuid::Uuid;
 
+use crate::entity::Sort;
+use crate::geo::Coordinates;
+
+#[derive(sqlx::FromRow)]
 pub struct Stand {
     pub id: Uuid,
     pub name: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    /// Distance around the rank it serves, if limited.
+    pub radius_meters: Option<i32>,
+    /// Inactive ranks are kept for the history but cannot be used by new journeys.
+    pub active: bool,
+}
+
+impl Stand {
+    pub fn coordinates(&self) -> Option<Coordinates> {
+        Coordinates::new(self.latitude?, self.longitude?)
+    }
+}
+
+pub struct NewStand {
+    pub name: String,
+    pub coordinates: Option<Coordinates>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub radius_meters: Option<i32>,
+}
+
+/// Fields to change on a rank; `Some(None)` clears the field.
+#[derive(Default)]
+pub struct StandChanges {
+    pub name: Option<String>,
+    pub active: Option<bool>,
+    pub coordinates: Option<Option<Coordinates>>,
+    pub address: Option<Option<String>>,
+    pub city: Option<Option<String>>,
+    pub radius_meters: Option<Option<i32>>,
+}
+
+pub struct AddedStands {
+    pub created: Vec<Stand>,
+    /// Ranks whose name, regardless of case, was already taken.
+    pub existing: Vec<Stand>,
+}
+
+pub enum StandUpdate {
+    Updated(Stand),
+    NameTaken,
+    Unknown,
+}
+
+pub enum StandDeletion {
+    Deleted,
+    InUse,
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StandSortField {
+    Name,
+    City,
+}
+
+impl StandSortField {
+    /// The value of the field for the rank, as kept in a cursor.
+    pub fn key(&self, stand: &Stand) -> String {
+        match self {
+            StandSortField::Name => stand.name.clone(),
+            StandSortField::City => stand.city.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Whether a cursor key can be a value of the field, which any text can.
+    pub fn accepts(&self, _key: &str) -> bool {
+        true
+    }
+}
+
+pub struct StandFilter {
+    /// Beginning of the name, regardless of case.
+    pub name_prefix: Option<String>,
+    pub city: Option<String>,
+    pub include_inactive: bool,
+    pub sort: Sort<StandSortField>,
 }
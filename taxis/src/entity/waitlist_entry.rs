@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+pub struct WaitlistEntry {
+    pub id: Uuid,
+    pub journey_id: Uuid,
+    pub seats: i32,
+    pub phone_number: String,
+    pub status: String,
+    pub position: Option<i64>,
+    pub offer_expires_at: Option<DateTime<Utc>>,
+    pub booking_id: Option<Uuid>,
+}
+
+impl WaitlistEntry {
+    pub fn is_offered(&self) -> bool {
+        self.status == WaitlistStatus::Offered.as_str()
+    }
+}
+
+pub struct NewWaitlistEntry {
+    pub id: Uuid,
+    pub journey_id: Uuid,
+    pub seats: i32,
+    pub phone_number: String,
+}
+
+pub enum WaitlistJoin {
+    Joined,
+    /// The taxi has fewer seats than asked for, so the entry could never be offered.
+    TooManySeats(i32),
+    /// The seats can still be booked over the whole journey.
+    SeatsAvailable,
+    /// The trip is unknown or closed.
+    Unavailable,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaitlistStatus {
+    Waiting,
+    Offered,
+    Claimed,
+    Expired,
+    Cancelled,
+}
+
+impl WaitlistStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WaitlistStatus::Waiting => "waiting",
+            WaitlistStatus::Offered => "offered",
+            WaitlistStatus::Claimed => "claimed",
+            WaitlistStatus::Expired => "expired",
+            WaitlistStatus::Cancelled => "cancelled",
+        }
+    }
+}
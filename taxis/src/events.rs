@@ -0,0 +1,233 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use log::error;
+use rocket::fairing::AdHoc;
+use rocket::tokio;
+use rocket::tokio::sync::{broadcast, mpsc};
+use rocket_db_pools::Database;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use database::TXpressDB;
+
+use crate::controllers::fares::Price;
+use crate::data::JourneyDataProvider;
+use crate::usecase::GetJourney;
+
+const TARGET: &'static str = "events";
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct EventsConfig {
+    /// Number of past events kept to resume streams from a `Last-Event-ID`.
+    pub history: usize,
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        EventsConfig { history: 512 }
+    }
+}
+
+#[derive(Serialize)]
+pub struct JourneyUpdate {
+    pub journey_id: Uuid,
+    pub origin: Uuid,
+    pub destination: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub departure_schedule: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserved_seats: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub closed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<Price>,
+}
+
+pub struct BusEvent {
+    pub id: u64,
+    pub kind: &'static str,
+    pub update: JourneyUpdate,
+}
+
+impl BusEvent {
+    pub fn concerns_journey(&self, journey_id: &Uuid) -> bool {
+        self.update.journey_id == *journey_id
+    }
+
+    pub fn concerns_rank(&self, rank_id: &Uuid) -> bool {
+        self.update.origin == *rank_id || self.update.destination == *rank_id
+    }
+}
+
+struct Change {
+    journey_id: Uuid,
+    kind: &'static str,
+}
+
+struct Inner {
+    changes: mpsc::UnboundedSender<Change>,
+    sender: broadcast::Sender<Arc<BusEvent>>,
+    history: Mutex<VecDeque<Arc<BusEvent>>>,
+    capacity: usize,
+    next_id: AtomicU64,
+}
+
+/// In-process bus of journey changes feeding the event streams.
+#[derive(Clone)]
+pub struct EventBus {
+    inner: Arc<Inner>,
+}
+
+impl EventBus {
+    fn new(config: &EventsConfig, changes: mpsc::UnboundedSender<Change>) -> Self {
+        let capacity = config.history.max(1);
+        let (sender, _) = broadcast::channel(capacity);
+
+        EventBus {
+            inner: Arc::new(Inner {
+                changes,
+                sender,
+                history: Mutex::new(VecDeque::with_capacity(capacity)),
+                capacity,
+                // Ids keep growing across restarts so a stale `Last-Event-ID` never hides new events.
+                next_id: AtomicU64::new(Utc::now().timestamp_millis() as u64),
+            }),
+        }
+    }
+
+    /// Signals that a journey changed; its fresh state is loaded and published in the background.
+    pub fn journey_changed(&self, journey_id: &Uuid, kind: &'static str) {
+        let _ = self.inner.changes.send(Change {
+            journey_id: *journey_id,
+            kind,
+        });
+    }
+
+    /// Publishes a journey that no longer exists, so its last known ranks are given by the caller.
+    pub fn journey_removed(&self, journey_id: &Uuid, origin: &Uuid, destination: &Uuid) {
+        self.publish(
+            "journey_cancelled",
+            JourneyUpdate {
+                journey_id: *journey_id,
+                origin: *origin,
+                destination: *destination,
+                departure_schedule: None,
+                reserved_seats: None,
+                closed: None,
+                price: None,
+            },
+        );
+    }
+
+    /// Receives the events published from now on, the id of the latest one before them given
+    /// along.
+    pub fn subscribe(&self) -> (broadcast::Receiver<Arc<BusEvent>>, u64) {
+        // Under the history lock, no event can be numbered without being received or counted.
+        let _history = self.inner.history.lock().unwrap();
+        (
+            self.inner.sender.subscribe(),
+            self.inner.next_id.load(Ordering::SeqCst),
+        )
+    }
+
+    /// Events published after `last_id` that are still in the history.
+    pub fn since(&self, last_id: u64) -> Vec<Arc<BusEvent>> {
+        self.inner
+            .history
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|e| e.id > last_id)
+            .cloned()
+            .collect()
+    }
+
+    fn publish(&self, kind: &'static str, update: JourneyUpdate) {
+        let mut history = self.inner.history.lock().unwrap();
+
+        // Numbering under the history lock keeps the history and the broadcast ordered by id.
+        let event = Arc::new(BusEvent {
+            id: self.inner.next_id.fetch_add(1, Ordering::SeqCst) + 1,
+            kind,
+            update,
+        });
+
+        if history.len() == self.inner.capacity {
+            history.pop_front();
+        }
+        history.push_back(event.clone());
+
+        let _ = self.inner.sender.send(event);
+    }
+}
+
+pub fn stage() -> AdHoc {
+    AdHoc::try_on_ignite("Events", |rocket| async {
+        let config = match rocket.figment().extract_inner::<EventsConfig>("events") {
+            Ok(config) => config,
+            Err(err) if err.missing() => EventsConfig::default(),
+            Err(err) => {
+                error!(target: TARGET, "invalid events configuration\n{err:?}");
+                return Err(rocket);
+            }
+        };
+
+        let (changes, receiver) = mpsc::unbounded_channel();
+        let receiver = Mutex::new(receiver);
+
+        Ok(rocket
+            .manage(EventBus::new(&config, changes))
+            .attach(AdHoc::on_liftoff("Event publisher", |rocket| {
+                Box::pin(async move {
+                    let Some(db) = TXpressDB::fetch(rocket) else {
+                        return;
+                    };
+                    let bus = rocket.state::<EventBus>().unwrap().clone();
+                    let receiver = receiver.into_inner().unwrap();
+
+                    tokio::spawn(publish_changes(PgPool::clone(db), bus, receiver));
+                })
+            })))
+    })
+}
+
+async fn publish_changes(
+    pool: PgPool,
+    bus: EventBus,
+    mut receiver: mpsc::UnboundedReceiver<Change>,
+) {
+    while let Some(change) = receiver.recv().await {
+        let conn = match pool.acquire().await {
+            Ok(conn) => conn,
+            Err(err) => {
+                error!(target: TARGET, "{err:?}");
+                continue;
+            }
+        };
+
+        match JourneyDataProvider::new(conn)
+            .get_journey(&change.journey_id)
+            .await
+        {
+            Err(err) => error!(target: TARGET, "{err:?}"),
+            Ok(None) => {}
+            Ok(Some(journey)) => bus.publish(
+                change.kind,
+                JourneyUpdate {
+                    price: journey.fare().map(Price::from),
+                    journey_id: journey.id,
+                    origin: journey.origin,
+                    destination: journey.destination,
+                    departure_schedule: Some(journey.departure_schedule),
+                    reserved_seats: Some(journey.reserved_seats),
+                    closed: Some(journey.closed),
+                },
+            ),
+        }
+    }
+}
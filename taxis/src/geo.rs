@@ -0,0 +1,151 @@
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Coordinates {
+    pub fn new(latitude: f64, longitude: f64) -> Option<Self> {
+        let valid = (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude);
+        valid.then_some(Coordinates {
+            latitude,
+            longitude,
+        })
+    }
+
+    /// Great-circle distance, by the haversine formula.
+    pub fn distance_km(&self, other: &Coordinates) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let dlat = lat2 - lat1;
+        let dlng = (other.longitude - self.longitude).to_radians();
+
+        let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlng / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
+/// Coordinates enclosing every point within a distance of a centre, wider than the circle itself.
+pub struct BoundingBox {
+    pub min_latitude: f64,
+    pub max_latitude: f64,
+    pub min_longitude: f64,
+    pub max_longitude: f64,
+}
+
+impl Coordinates {
+    pub fn bounding_box(&self, radius_km: f64) -> BoundingBox {
+        let dlat = (radius_km / EARTH_RADIUS_KM).to_degrees();
+        let min_latitude = (self.latitude - dlat).max(-90.0);
+        let max_latitude = (self.latitude + dlat).min(90.0);
+
+        // Near the poles, or across the antimeridian, every longitude has to be considered.
+        let widest = min_latitude
+            .abs()
+            .max(max_latitude.abs())
+            .to_radians()
+            .cos();
+        let dlng = (radius_km / (EARTH_RADIUS_KM * widest)).to_degrees();
+        let (min_longitude, max_longitude) =
+            if !dlng.is_finite() || self.longitude - dlng < -180.0 || self.longitude + dlng > 180.0
+            {
+                (-180.0, 180.0)
+            } else {
+                (self.longitude - dlng, self.longitude + dlng)
+            };
+
+        BoundingBox {
+            min_latitude,
+            max_latitude,
+            min_longitude,
+            max_longitude,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Coordinates, EARTH_RADIUS_KM};
+
+    fn at(latitude: f64, longitude: f64) -> Coordinates {
+        Coordinates::new(latitude, longitude).unwrap()
+    }
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    #[test]
+    fn rejects_coordinates_out_of_range() {
+        assert!(Coordinates::new(90.5, 0.0).is_none());
+        assert!(Coordinates::new(0.0, -180.5).is_none());
+    }
+
+    #[test]
+    fn measures_known_distances() {
+        let douala = at(4.0511, 9.7679);
+        assert_eq!(douala.distance_km(&douala), 0.0);
+
+        // A degree of longitude on the equator, and half the circumference between the poles.
+        assert_close(at(0.0, 0.0).distance_km(&at(0.0, 1.0)), 111.195, 0.001);
+        assert_close(at(90.0, 0.0).distance_km(&at(-90.0, 0.0)), 20015.114, 0.001);
+
+        let london = at(51.5074, -0.1278);
+        let paris = at(48.8566, 2.3522);
+        assert_close(london.distance_km(&paris), 343.5, 0.5);
+        assert_eq!(london.distance_km(&paris), paris.distance_km(&london));
+    }
+
+    #[test]
+    fn measures_across_the_antimeridian() {
+        assert_close(at(0.0, 179.5).distance_km(&at(0.0, -179.5)), 111.195, 0.001);
+    }
+
+    #[test]
+    fn bounds_the_circle() {
+        let yaounde = at(3.848, 11.5021);
+        let bounds = yaounde.bounding_box(10.0);
+
+        let dlat = (10.0 / EARTH_RADIUS_KM).to_degrees();
+        assert_close(bounds.min_latitude, yaounde.latitude - dlat, 1e-9);
+        assert_close(bounds.max_latitude, yaounde.latitude + dlat, 1e-9);
+        assert!(bounds.min_longitude < yaounde.longitude - dlat);
+        assert!(bounds.max_longitude > yaounde.longitude + dlat);
+
+        // The points of the circle furthest east and west lie on its widest parallel.
+        let east = at(bounds.min_latitude, bounds.max_longitude);
+        assert!(at(bounds.min_latitude, yaounde.longitude).distance_km(&east) >= 10.0);
+    }
+
+    #[test]
+    fn covers_every_longitude_near_a_pole() {
+        let bounds = at(89.95, 10.0).bounding_box(20.0);
+        assert_eq!(bounds.max_latitude, 90.0);
+        assert_eq!(
+            (bounds.min_longitude, bounds.max_longitude),
+            (-180.0, 180.0)
+        );
+
+        let bounds = at(-90.0, 0.0).bounding_box(1.0);
+        assert_eq!(bounds.min_latitude, -90.0);
+        assert_eq!(
+            (bounds.min_longitude, bounds.max_longitude),
+            (-180.0, 180.0)
+        );
+    }
+
+    #[test]
+    fn covers_every_longitude_across_the_antimeridian() {
+        for longitude in [179.95, -179.95] {
+            let bounds = at(0.0, longitude).bounding_box(20.0);
+            assert_eq!(
+                (bounds.min_longitude, bounds.max_longitude),
+                (-180.0, 180.0)
+            );
+        }
+    }
+}
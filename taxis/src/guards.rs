@@ -1,3 +1,19 @@
+mod callback_signature;
+mod checkout;
+mod journey_passenger;
+mod last_event_id;
+mod notifications;
+mod observers;
+mod passenger;
+mod promotions;
 mod user;
 
+pub use callback_signature::CallbackSignature;
+pub use checkout::Checkout;
+pub use journey_passenger::JourneyPassenger;
+pub use last_event_id::LastEventId;
+pub use notifications::Notifications;
+pub use observers::Observers;
+pub use passenger::Passenger;
+pub use promotions::Promotions;
 pub use user::User;
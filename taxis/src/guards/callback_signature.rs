@@ -0,0 +1,19 @@
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome};
+use rocket::Request;
+
+use crate::controllers::Error;
+
+pub struct CallbackSignature(pub String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for CallbackSignature {
+    type Error = Error;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request.headers().get_one("X-Signature") {
+            Some(signature) => Outcome::Success(CallbackSignature(signature.into())),
+            None => Outcome::Failure((Status::Unauthorized, Error::invalid_signature())),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use rocket::http::CookieJar;
+use rocket::outcome::try_outcome;
+use rocket::request::{FromRequest, Outcome};
+use rocket::{Request, State};
+
+use crate::events::EventBus;
+use crate::payment::Payments;
+
+/// What a held booking needs to be paid for and handed to its passenger.
+pub struct Checkout<'r> {
+    pub payments: &'r Payments,
+    pub bus: &'r EventBus,
+    pub cookies: &'r CookieJar<'r>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Checkout<'r> {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let payments = try_outcome!(request.guard::<&State<Payments>>().await);
+        let bus = try_outcome!(request.guard::<&State<EventBus>>().await);
+
+        Outcome::Success(Checkout {
+            payments: payments.inner(),
+            bus: bus.inner(),
+            cookies: request.cookies(),
+        })
+    }
+}
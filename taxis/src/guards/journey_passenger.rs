@@ -0,0 +1,42 @@
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome};
+use rocket::Request;
+use uuid::Uuid;
+
+use crate::controllers::Error;
+use crate::guards::Passenger;
+
+/// The bookings of a journey the requester holds credentials for.
+pub struct JourneyPassenger {
+    pub bookings: Vec<Uuid>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for JourneyPassenger {
+    type Error = Error;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let journey_id = match request.param::<Uuid>(1) {
+            Some(Ok(id)) => id,
+            _ => return Outcome::Failure((Status::Unauthorized, Error::no_credentials())),
+        };
+
+        let cookies = request.cookies();
+        let bookings: Vec<Uuid> = cookies
+            .iter()
+            .filter_map(|c| Uuid::parse_str(c.name()).ok())
+            .filter(|id| {
+                cookies
+                    .get_private(&id.to_string())
+                    .and_then(|c| serde_json::from_str::<Passenger>(c.value()).ok())
+                    .is_some_and(|p| p.journey_id == journey_id)
+            })
+            .collect();
+
+        if bookings.is_empty() {
+            return Outcome::Failure((Status::Unauthorized, Error::no_credentials()));
+        }
+
+        Outcome::Success(JourneyPassenger { bookings })
+    }
+}
@@ -0,0 +1,19 @@
+use rocket::request::{FromRequest, Outcome};
+use rocket::Request;
+
+/// Id of the last event an SSE client received, sent back when it reconnects.
+pub struct LastEventId(pub Option<u64>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for LastEventId {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(LastEventId(
+            request
+                .headers()
+                .get_one("Last-Event-ID")
+                .and_then(|id| id.trim().parse().ok()),
+        ))
+    }
+}
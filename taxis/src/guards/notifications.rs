@@ -0,0 +1,49 @@
+use rocket::outcome::try_outcome;
+use rocket::request::{FromRequest, Outcome};
+use rocket::{Request, State};
+use uuid::Uuid;
+
+use crate::data::NotificationDataProvider;
+use crate::entity::{Audience, NotificationEvent};
+use crate::notification::Notifier;
+
+/// The notifier along with the connection it looks up and queues the messages with.
+pub struct Notifications<'r> {
+    notifier: &'r Notifier,
+    data_provider: NotificationDataProvider,
+}
+
+impl Notifications<'_> {
+    pub async fn audience(&mut self, event: NotificationEvent, id: &Uuid) -> Option<Audience> {
+        self.notifier
+            .audience(&mut self.data_provider, event, id)
+            .await
+    }
+
+    pub async fn enqueue(&mut self, event: NotificationEvent, audience: &Audience) {
+        self.notifier
+            .enqueue(&mut self.data_provider, event, audience)
+            .await
+    }
+
+    pub async fn notify(&mut self, event: NotificationEvent, id: &Uuid) {
+        self.notifier
+            .notify(&mut self.data_provider, event, id)
+            .await
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Notifications<'r> {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let notifier = try_outcome!(request.guard::<&State<Notifier>>().await);
+        let data_provider = try_outcome!(request.guard::<NotificationDataProvider>().await);
+
+        Outcome::Success(Notifications {
+            notifier: notifier.inner(),
+            data_provider,
+        })
+    }
+}
@@ -0,0 +1,27 @@
+use rocket::outcome::try_outcome;
+use rocket::request::{FromRequest, Outcome};
+use rocket::{Request, State};
+
+use crate::events::EventBus;
+use crate::metrics::Metrics;
+
+/// Where the changes of a journey are told: its subscribers and the metrics.
+pub struct Observers<'r> {
+    pub bus: &'r EventBus,
+    pub metrics: &'r Metrics,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Observers<'r> {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let bus = try_outcome!(request.guard::<&State<EventBus>>().await);
+        let metrics = try_outcome!(request.guard::<&State<Metrics>>().await);
+
+        Outcome::Success(Observers {
+            bus: bus.inner(),
+            metrics: metrics.inner(),
+        })
+    }
+}
@@ -0,0 +1,39 @@
+use log::error;
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome};
+use rocket::Request;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::controllers::Error;
+
+#[derive(Serialize, Deserialize)]
+pub struct Passenger {
+    pub journey_id: Uuid,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Passenger {
+    type Error = Error;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let booking_id = match request.param::<Uuid>(1) {
+            Some(Ok(id)) => id,
+            _ => return Outcome::Failure((Status::Unauthorized, Error::no_credentials())),
+        };
+
+        let passenger = request
+            .cookies()
+            .get_private(&booking_id.to_string())
+            .and_then(|c| {
+                serde_json::from_str::<Passenger>(c.value())
+                    .map_err(|err| error!(target: "passenger-guard", "{err:?}"))
+                    .ok()
+            });
+
+        match passenger {
+            None => Outcome::Failure((Status::Unauthorized, Error::no_credentials())),
+            Some(p) => Outcome::Success(p),
+        }
+    }
+}
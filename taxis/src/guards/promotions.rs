@@ -0,0 +1,37 @@
+use rocket::outcome::try_outcome;
+use rocket::request::{FromRequest, Outcome};
+use rocket::{Request, State};
+use uuid::Uuid;
+
+use crate::data::WaitlistDataProvider;
+use crate::waitlist::Waitlist;
+
+/// The waitlist along with the connection it offers the freed seats with.
+pub struct Promotions<'r> {
+    waitlist: &'r Waitlist,
+    data_provider: WaitlistDataProvider,
+}
+
+impl Promotions<'_> {
+    /// Offers the seats freed on the journey to the head of its waitlist, if any.
+    pub async fn promote(&mut self, journey_id: &Uuid) -> bool {
+        self.waitlist
+            .promote(&mut self.data_provider, journey_id)
+            .await
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Promotions<'r> {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let waitlist = try_outcome!(request.guard::<&State<Waitlist>>().await);
+        let data_provider = try_outcome!(request.guard::<WaitlistDataProvider>().await);
+
+        Outcome::Success(Promotions {
+            waitlist: waitlist.inner(),
+            data_provider,
+        })
+    }
+}
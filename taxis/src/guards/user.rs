@@ -1,6 +1,6 @@
 use crate::controllers::Error;
 use log::error;
-use rocket::http::Status;
+use rocket::http::{CookieJar, Status};
 use rocket::request::{FromRequest, Outcome};
 use rocket::Request;
 use serde::Deserialize;
@@ -10,25 +10,32 @@ pub struct User {
     pub full_name: String,
 }
 
+impl User {
+    /// The owner logged in for the taxi, for routes where the number is not the first segment.
+    pub fn authenticate(cookies: &CookieJar<'_>, taxi_num: &str) -> Option<User> {
+        cookies
+            .get_private(taxi_num)
+            .map(|c| serde_json::from_str::<User>(c.value()))
+            .and_then(|owner| {
+                if let Err(err) = owner {
+                    error!(target: "taxi-owner-guard", "{err:?}");
+                    return None;
+                }
+
+                Some(owner.unwrap())
+            })
+    }
+}
+
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for User {
     type Error = Error;
 
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
-        let res = request.param::<&str>(1).unwrap().map(|taxi_num| {
-            request
-                .cookies()
-                .get_private(taxi_num)
-                .map(|c| serde_json::from_str::<User>(c.value()))
-                .and_then(|owner| {
-                    if let Err(err) = owner {
-                        error!(target: "taxi-owner-guard", "{err:?}");
-                        return None;
-                    }
-
-                    Some(owner.unwrap())
-                })
-        });
+        let res = request
+            .param::<&str>(1)
+            .unwrap()
+            .map(|taxi_num| User::authenticate(request.cookies(), taxi_num));
 
         if let Err(_) = res {
             return Outcome::Failure((
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use log::error;
+use rocket::http::{ContentType, Status};
+use rocket::response::{self, Responder};
+use rocket::serde::json::Json;
+use rocket::{Request, Response};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{Link, BASE_URL};
+
+const TARGET: &'static str = "HAL";
+
+/// Prefix of the relations specific to this API, resolved through the `curies` link.
+pub(crate) const CURIE: &'static str = "tx";
+
+/// A resource served as `application/hal+json`.
+///
+/// Links whose `href` is a URI template are marked `templated`, and the curies are advertised
+/// as soon as a relation uses the API prefix, down to the embedded resources.
+pub(crate) struct Hal<T>(pub T);
+
+impl<'r, T: Serialize> Responder<'r, 'static> for Hal<T> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let mut resource = serde_json::to_value(&self.0).map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Status::InternalServerError
+        })?;
+        decorate(&mut resource);
+
+        Response::build_from(Json(resource).respond_to(request)?)
+            .header(ContentType::new("application", "hal+json"))
+            .ok()
+    }
+}
+
+/// A representation that knows its own links, usually through `#[derive(Resource)]`.
+pub(crate) trait Resource {
+    fn links(&self) -> HashMap<&'static str, Link>;
+
+    fn set_links(&mut self, links: HashMap<&'static str, Link>);
+
+    /// The resource with its links filled in.
+    fn linked(mut self) -> Self
+    where
+        Self: Sized,
+    {
+        let links = self.links();
+        self.set_links(links);
+        self
+    }
+}
+
+fn decorate(resource: &mut Value) {
+    let Some(resource) = resource.as_object_mut() else {
+        return;
+    };
+
+    if let Some(links) = resource.get_mut("_links").and_then(Value::as_object_mut) {
+        for link in links.values_mut().filter_map(Value::as_object_mut) {
+            let templated = link
+                .get("href")
+                .and_then(Value::as_str)
+                .is_some_and(|href| href.contains('{'));
+            if templated {
+                link.insert(String::from("templated"), Value::Bool(true));
+            }
+        }
+
+        let prefix = format!("{CURIE}:");
+        if links.keys().any(|rel| rel.starts_with(&prefix)) {
+            links.insert(
+                String::from("curies"),
+                json!([{
+                    "name": CURIE,
+                    "href": format!("{BASE_URL}/rels/{{rel}}"),
+                    "templated": true,
+                }]),
+            );
+        }
+    }
+
+    if let Some(embedded) = resource.get_mut("_embedded").and_then(Value::as_object_mut) {
+        for value in embedded.values_mut() {
+            match value {
+                Value::Array(resources) => resources.iter_mut().for_each(decorate),
+                resource => decorate(resource),
+            }
+        }
+    }
+}
@@ -1,20 +1,41 @@
 use rocket::http::uri::Absolute;
 use rocket::uri;
 use serde::Serialize;
+use utoipa::ToSchema;
 
-pub use controllers::routes;
+pub use controllers::{docs, routes, spec as openapi};
+pub use events::stage as events;
+pub use metrics::stage as metrics;
+pub use notification::stage as notifications;
+pub use payment::stage as payments;
+pub use request_id::{stage as request_ids, RequestId};
+pub use ticket::stage as tickets;
+pub use tracking::stage as tracking;
+pub use waitlist::stage as waitlist;
 
+pub mod admin;
 mod controllers;
 mod data;
 mod entity;
 mod errors;
+mod events;
+mod geo;
+mod hal;
+mod metrics;
+pub mod notification;
 mod password;
+pub mod payment;
+mod request_id;
+mod signature;
+mod ticket;
+mod tracking;
+mod waitlist;
 mod usecase;
 mod guards;
 
-const BASE_URL: Absolute<'static> = uri!("http://localhost:8000");
+const BASE_URL: Absolute<'static> = uri!("http://localhost:8000/v1");
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 struct Link {
     href: String,
 }
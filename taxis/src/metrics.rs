@@ -0,0 +1,187 @@
+use std::time::Instant;
+
+use log::error;
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, HistogramTimer, HistogramVec, IntCounter, IntCounterVec,
+    IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use rocket::fairing::{AdHoc, Fairing, Info, Kind};
+use rocket::http::{ContentType, Status};
+use rocket::{get, routes, Data, Request, Response, State};
+
+use database::TXpressDB;
+
+const TARGET: &'static str = "metrics";
+
+/// Counters of the requests and of what they achieved, exposed to Prometheus at `/metrics`.
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    requests: IntCounterVec,
+    request_duration: HistogramVec,
+    connections: IntGaugeVec,
+    password_verification: Histogram,
+    journeys: IntCounterVec,
+    logins: IntCounterVec,
+    ranks_created: IntCounter,
+}
+
+impl Metrics {
+    fn new() -> prometheus::Result<Self> {
+        let registry = Registry::new_custom(Some(String::from("txpress")), None)?;
+
+        let requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "Requests handled, by route"),
+            &["method", "route", "status"],
+        )?;
+        let request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Time to handle the requests, by route",
+            ),
+            &["method", "route"],
+        )?;
+        let connections = IntGaugeVec::new(
+            Opts::new(
+                "db_connections",
+                "Connections of the database pool, by state",
+            ),
+            &["state"],
+        )?;
+        let password_verification = Histogram::with_opts(HistogramOpts::new(
+            "password_verification_seconds",
+            "Time to verify the password of an owner signing in",
+        ))?;
+        let journeys = IntCounterVec::new(
+            Opts::new("journeys_total", "Journeys started, closed or cancelled"),
+            &["event"],
+        )?;
+        let logins = IntCounterVec::new(
+            Opts::new("logins_total", "Owners signing in, by outcome"),
+            &["outcome"],
+        )?;
+        let ranks_created = IntCounter::new("ranks_created_total", "Taxi ranks created")?;
+
+        registry.register(Box::new(requests.clone()))?;
+        registry.register(Box::new(request_duration.clone()))?;
+        registry.register(Box::new(connections.clone()))?;
+        registry.register(Box::new(password_verification.clone()))?;
+        registry.register(Box::new(journeys.clone()))?;
+        registry.register(Box::new(logins.clone()))?;
+        registry.register(Box::new(ranks_created.clone()))?;
+
+        Ok(Metrics {
+            registry,
+            requests,
+            request_duration,
+            connections,
+            password_verification,
+            journeys,
+            logins,
+            ranks_created,
+        })
+    }
+
+    /// `started`, `closed` or `cancelled`.
+    pub fn journey(&self, event: &str) {
+        self.journeys.with_label_values(&[event]).inc();
+    }
+
+    pub fn login(&self, succeeded: bool) {
+        let outcome = match succeeded {
+            true => "succeeded",
+            false => "failed",
+        };
+        self.logins.with_label_values(&[outcome]).inc();
+    }
+
+    pub fn ranks_created(&self, count: usize) {
+        self.ranks_created.inc_by(count as u64);
+    }
+
+    /// Times a password verification until the timer is dropped.
+    pub fn verifying_password(&self) -> HistogramTimer {
+        self.password_verification.start_timer()
+    }
+
+    fn render(&self, pool: &TXpressDB) -> Result<String, prometheus::Error> {
+        let size = i64::from(pool.size());
+        let idle = pool.num_idle() as i64;
+        self.connections
+            .with_label_values(&["in_use"])
+            .set(size - idle);
+        self.connections.with_label_values(&["idle"]).set(idle);
+
+        let mut buffer = vec![];
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+}
+
+/// When the request came in.
+struct Received(Instant);
+
+#[rocket::async_trait]
+impl Fairing for Metrics {
+    fn info(&self) -> Info {
+        Info {
+            name: "Count and time the requests",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
+        request.local_cache(|| Received(Instant::now()));
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let Received(received) = request.local_cache(|| Received(Instant::now()));
+        // Unrouted requests are counted together, whatever their path.
+        let route = request
+            .route()
+            .map(|r| r.uri.to_string())
+            .unwrap_or_default();
+        let method = request.method().as_str();
+
+        self.requests
+            .with_label_values(&[method, &route, response.status().code.to_string().as_str()])
+            .inc();
+        self.request_duration
+            .with_label_values(&[method, &route])
+            .observe(received.elapsed().as_secs_f64());
+    }
+}
+
+#[get("/metrics")]
+fn index(metrics: &State<Metrics>, db: &TXpressDB) -> Result<(ContentType, String), Status> {
+    metrics
+        .render(db)
+        .map(|text| {
+            (
+                ContentType::new("text", "plain").with_params(("version", "0.0.4")),
+                text,
+            )
+        })
+        .map_err(|err| {
+            error!(target: TARGET, "{err:?}");
+            Status::InternalServerError
+        })
+}
+
+/// Counts the requests, and serves the metrics at `/metrics`.
+pub fn stage() -> AdHoc {
+    AdHoc::try_on_ignite("Metrics", |rocket| async {
+        let metrics = match Metrics::new() {
+            Ok(metrics) => metrics,
+            Err(err) => {
+                error!(target: TARGET, "{err:?}");
+                return Err(rocket);
+            }
+        };
+
+        Ok(rocket
+            .attach(metrics.clone())
+            .manage(metrics)
+            .mount("/", routes![index]))
+    })
+}
@@ -0,0 +1,336 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use log::{error, info, warn};
+use rocket::fairing::AdHoc;
+use rocket::tokio::{self, time};
+use rocket_db_pools::Database;
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use database::TXpressDB;
+
+pub use crate::entity::Channel;
+pub use sink::SinkTransport;
+pub use smtp::{SmtpConfig, SmtpSecurity, SmtpTransport};
+pub use transport::{Message, NotificationTransport, TransportError};
+
+use crate::data::NotificationDataProvider;
+use crate::entity::{Audience, DeliveryAttempt, NewNotification, Notification, NotificationEvent};
+use crate::usecase::{
+    EnqueueNotifications, GetAudience, GetDueNotifications, GetImminentDepartures,
+    RecordDeliveryAttempt,
+};
+
+mod sink;
+mod smtp;
+mod template;
+mod transport;
+
+const TARGET: &'static str = "notifications";
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct NotificationConfig {
+    pub default_language: String,
+    pub sms_transport: String,
+    pub email_transport: String,
+    pub sink_path: Option<String>,
+    pub smtp: SmtpConfig,
+    pub max_attempts: i32,
+    pub retry_seconds: i64,
+    pub sweep_seconds: u64,
+    pub batch_size: i64,
+    pub departure_notice_minutes: i64,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        NotificationConfig {
+            default_language: String::from("fr"),
+            sms_transport: SinkTransport::NAME.into(),
+            email_transport: SinkTransport::NAME.into(),
+            sink_path: None,
+            smtp: SmtpConfig::default(),
+            max_attempts: 5,
+            retry_seconds: 30,
+            sweep_seconds: 15,
+            batch_size: 50,
+            departure_notice_minutes: 30,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Notifier {
+    pub config: NotificationConfig,
+    sms: Arc<dyn NotificationTransport>,
+    email: Arc<dyn NotificationTransport>,
+}
+
+impl Notifier {
+    fn transport(&self, channel: &str) -> &Arc<dyn NotificationTransport> {
+        if channel == Channel::Email.as_str() {
+            &self.email
+        } else {
+            &self.sms
+        }
+    }
+
+    pub async fn audience(
+        &self,
+        data_provider: &mut NotificationDataProvider,
+        event: NotificationEvent,
+        id: &Uuid,
+    ) -> Option<Audience> {
+        data_provider
+            .get_audience(event, id)
+            .await
+            .map_err(|err| error!(target: TARGET, "{err:?}"))
+            .ok()
+            .flatten()
+    }
+
+    /// Renders the event for every recipient and queues the messages for delivery.
+    pub async fn enqueue(
+        &self,
+        data_provider: &mut NotificationDataProvider,
+        event: NotificationEvent,
+        audience: &Audience,
+    ) {
+        let notifications = self.prepare(event, audience);
+        if notifications.is_empty() {
+            return;
+        }
+
+        if let Err(err) = data_provider.enqueue_notifications(&notifications).await {
+            error!(target: TARGET, "unable to enqueue `{}`\n{err:?}", event.as_str());
+        }
+    }
+
+    pub async fn notify(
+        &self,
+        data_provider: &mut NotificationDataProvider,
+        event: NotificationEvent,
+        id: &Uuid,
+    ) {
+        if let Some(audience) = self.audience(data_provider, event, id).await {
+            self.enqueue(data_provider, event, &audience).await;
+        }
+    }
+
+    fn prepare(&self, event: NotificationEvent, audience: &Audience) -> Vec<NewNotification> {
+        let fallback = self.config.default_language.as_str();
+        let mut notifications = vec![];
+
+        for recipient in &audience.recipients {
+            let language = recipient.language.as_deref().unwrap_or(fallback);
+            let Some(template) = template::find(event, language, fallback) else {
+                warn!(target: TARGET, "no template for `{}` in `{language}`", event.as_str());
+                continue;
+            };
+
+            let values = [
+                ("taxi", audience.taxi.to_uppercase()),
+                ("origin", audience.origin.clone()),
+                ("destination", audience.destination.clone()),
+                (
+                    "departure",
+                    audience
+                        .departure_schedule
+                        .format("%d/%m/%Y %H:%M UTC")
+                        .to_string(),
+                ),
+                ("seats", recipient.seats.unwrap_or_default().to_string()),
+            ];
+            let body = template::render(template.body, &values);
+
+            let addresses = [
+                (Channel::Sms, recipient.phone_number.as_ref()),
+                (Channel::Email, recipient.email.as_ref()),
+            ];
+            for (channel, address) in addresses {
+                let Some(address) = address else {
+                    continue;
+                };
+
+                notifications.push(NewNotification {
+                    id: Uuid::new_v4(),
+                    event,
+                    channel,
+                    recipient: address.clone(),
+                    language: template.language.into(),
+                    subject: match channel {
+                        Channel::Email => Some(template::render(template.subject, &values)),
+                        Channel::Sms => None,
+                    },
+                    body: body.clone(),
+                    journey_id: Some(audience.journey_id),
+                    booking_id: recipient.booking_id,
+                    dedupe_key: Some(format!(
+                        "{}:{}:{}:{address}",
+                        event.as_str(),
+                        recipient.booking_id.unwrap_or(audience.journey_id),
+                        channel.as_str()
+                    )),
+                });
+            }
+        }
+
+        notifications
+    }
+
+    async fn deliver(
+        &self,
+        data_provider: &mut NotificationDataProvider,
+        notification: Notification,
+    ) {
+        let transport = self.transport(&notification.channel);
+        let channel = if notification.channel == Channel::Email.as_str() {
+            Channel::Email
+        } else {
+            Channel::Sms
+        };
+
+        let result = transport
+            .send(&Message {
+                channel,
+                to: &notification.recipient,
+                subject: notification.subject.as_deref(),
+                body: &notification.body,
+            })
+            .await;
+
+        let attempt = notification.attempts + 1;
+        let (error, retry_at) = match result {
+            Ok(_) => (None, None),
+            Err(err) => {
+                warn!(target: TARGET, "attempt {attempt} on `{}` failed: {err}", notification.id);
+
+                // Exponential backoff until the configured number of attempts is reached.
+                let retry_at = (attempt < self.config.max_attempts).then(|| {
+                    let delay = self.config.retry_seconds << (attempt - 1).min(16);
+                    Utc::now() + chrono::Duration::seconds(delay)
+                });
+                (Some(err.to_string()), retry_at)
+            }
+        };
+
+        let attempt = DeliveryAttempt {
+            notification_id: notification.id,
+            transport: transport.name(),
+            error,
+            retry_at,
+        };
+        if let Err(err) = data_provider.record_delivery_attempt(&attempt).await {
+            error!(target: TARGET, "{err:?}");
+        }
+    }
+}
+
+fn transport(
+    name: &str,
+    config: &NotificationConfig,
+) -> Result<Arc<dyn NotificationTransport>, String> {
+    match name {
+        SinkTransport::NAME => Ok(Arc::new(SinkTransport::new(config.sink_path.clone()))),
+        SmtpTransport::NAME => SmtpTransport::new(&config.smtp)
+            .map(|t| Arc::new(t) as Arc<dyn NotificationTransport>)
+            .map_err(|err| err.to_string()),
+        other => Err(format!("unknown notification transport `{other}`")),
+    }
+}
+
+pub fn stage() -> AdHoc {
+    AdHoc::try_on_ignite("Notifications", |rocket| async {
+        let config = match rocket
+            .figment()
+            .extract_inner::<NotificationConfig>("notifications")
+        {
+            Ok(config) => config,
+            Err(err) if err.missing() => NotificationConfig::default(),
+            Err(err) => {
+                error!(target: TARGET, "invalid notifications configuration\n{err:?}");
+                return Err(rocket);
+            }
+        };
+
+        let transports = transport(&config.sms_transport, &config)
+            .and_then(|sms| transport(&config.email_transport, &config).map(|email| (sms, email)));
+        let (sms, email) = match transports {
+            Ok(transports) => transports,
+            Err(err) => {
+                error!(target: TARGET, "{err}");
+                return Err(rocket);
+            }
+        };
+
+        Ok(rocket
+            .manage(Notifier { config, sms, email })
+            .attach(AdHoc::on_liftoff("Notification worker", |rocket| {
+                Box::pin(async move {
+                    let Some(db) = TXpressDB::fetch(rocket) else {
+                        return;
+                    };
+                    let notifier = rocket.state::<Notifier>().unwrap().clone();
+
+                    tokio::spawn(run(PgPool::clone(db), notifier));
+                })
+            })))
+    })
+}
+
+async fn run(pool: PgPool, notifier: Notifier) {
+    let mut interval = time::interval(Duration::from_secs(notifier.config.sweep_seconds.max(1)));
+    let notice = chrono::Duration::minutes(notifier.config.departure_notice_minutes);
+    let lease = chrono::Duration::seconds(notifier.config.retry_seconds.max(1) * 2);
+
+    loop {
+        interval.tick().await;
+
+        let conn = match pool.acquire().await {
+            Ok(conn) => conn,
+            Err(err) => {
+                error!(target: TARGET, "{err:?}");
+                continue;
+            }
+        };
+        let mut data_provider = NotificationDataProvider::new(conn);
+
+        // Recipients already told about a departure are skipped through the dedupe key.
+        match data_provider.get_imminent_departures(notice).await {
+            Err(err) => error!(target: TARGET, "{err:?}"),
+            Ok(journeys) => {
+                for journey_id in journeys {
+                    notifier
+                        .notify(
+                            &mut data_provider,
+                            NotificationEvent::DepartureImminent,
+                            &journey_id,
+                        )
+                        .await;
+                }
+            }
+        }
+
+        let due = match data_provider
+            .get_due_notifications(notifier.config.batch_size, lease)
+            .await
+        {
+            Ok(due) => due,
+            Err(err) => {
+                error!(target: TARGET, "{err:?}");
+                continue;
+            }
+        };
+
+        if !due.is_empty() {
+            info!(target: TARGET, "delivering {} notification(s)", due.len());
+        }
+        for notification in due {
+            notifier.deliver(&mut data_provider, notification).await;
+        }
+    }
+}
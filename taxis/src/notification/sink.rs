@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use rocket::tokio::fs::OpenOptions;
+use rocket::tokio::io::AsyncWriteExt;
+
+use crate::notification::transport::{Message, NotificationTransport, TransportError};
+
+/// Writes every message as a JSON line, to a file when a path is configured or to stdout.
+pub struct SinkTransport {
+    path: Option<String>,
+}
+
+impl SinkTransport {
+    pub const NAME: &'static str = "sink";
+
+    pub fn new(path: Option<String>) -> Self {
+        SinkTransport { path }
+    }
+}
+
+#[async_trait]
+impl NotificationTransport for SinkTransport {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    async fn send(&self, message: &Message<'_>) -> Result<(), TransportError> {
+        let line = serde_json::json!({
+            "sent_at": Utc::now(),
+            "channel": message.channel.as_str(),
+            "to": message.to,
+            "subject": message.subject,
+            "body": message.body,
+        })
+        .to_string();
+
+        let Some(path) = &self.path else {
+            println!("{line}");
+            return Ok(());
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+            .map_err(|err| TransportError::Failed(err.to_string()))?;
+
+        file.write_all(format!("{line}\n").as_bytes())
+            .await
+            .map_err(|err| TransportError::Failed(err.to_string()))
+    }
+}
@@ -0,0 +1,109 @@
+use async_trait::async_trait;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+use serde::Deserialize;
+
+use crate::notification::transport::{Message, NotificationTransport, TransportError};
+
+type Mailer = AsyncSmtpTransport<Tokio1Executor>;
+
+/// How the connection to the server is secured.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Upgraded with STARTTLS, which the server must offer.
+    Starttls,
+    /// TLS from the first byte, usually on port 465.
+    Tls,
+    /// No encryption at all: credentials and messages travel in clear.
+    Plaintext,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        SmtpConfig {
+            host: String::from("localhost"),
+            port: 587,
+            security: SmtpSecurity::Starttls,
+            username: None,
+            password: None,
+            from: String::from("TXpress <no-reply@txpress.local>"),
+        }
+    }
+}
+
+/// SMTP over TLS, or in clear when the configuration explicitly asks for it, e.g. for a fake
+/// server in development (MailHog, smtp4dev...).
+pub struct SmtpTransport {
+    mailer: Mailer,
+    from: Mailbox,
+}
+
+impl SmtpTransport {
+    pub const NAME: &'static str = "smtp";
+
+    pub fn new(config: &SmtpConfig) -> Result<Self, TransportError> {
+        let from = config
+            .from
+            .parse()
+            .map_err(|err| TransportError::Invalid(format!("invalid sender: {err}")))?;
+
+        let builder = match config.security {
+            SmtpSecurity::Starttls => Mailer::starttls_relay(&config.host),
+            SmtpSecurity::Tls => Mailer::relay(&config.host),
+            SmtpSecurity::Plaintext => Ok(Mailer::builder_dangerous(&config.host)),
+        };
+        let mut builder = builder
+            .map_err(|err| TransportError::Invalid(format!("invalid SMTP server: {err}")))?
+            .port(config.port);
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(SmtpTransport {
+            mailer: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationTransport for SmtpTransport {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    async fn send(&self, message: &Message<'_>) -> Result<(), TransportError> {
+        let to: Mailbox = message
+            .to
+            .parse()
+            .map_err(|err| TransportError::Invalid(format!("invalid recipient: {err}")))?;
+
+        let email = lettre::Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(message.subject.unwrap_or_default())
+            .header(ContentType::TEXT_PLAIN)
+            .body(message.body.to_string())
+            .map_err(|err| TransportError::Invalid(err.to_string()))?;
+
+        self.mailer
+            .send(email)
+            .await
+            .map(|_| ())
+            .map_err(|err| TransportError::Failed(err.to_string()))
+    }
+}
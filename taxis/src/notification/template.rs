@@ -0,0 +1,70 @@
+use crate::entity::NotificationEvent;
+
+pub struct Template {
+    pub event: NotificationEvent,
+    pub language: &'static str,
+    pub subject: &'static str,
+    pub body: &'static str,
+}
+
+const TEMPLATES: &[Template] = &[
+    Template {
+        event: NotificationEvent::BookingConfirmed,
+        language: "fr",
+        subject: "Réservation confirmée",
+        body: "TXpress : votre réservation de {seats} place(s) sur le taxi {taxi} ({origin} → {destination}) \
+            du {departure} est confirmée.",
+    },
+    Template {
+        event: NotificationEvent::BookingConfirmed,
+        language: "en",
+        subject: "Booking confirmed",
+        body: "TXpress: your booking of {seats} seat(s) on taxi {taxi} ({origin} → {destination}) \
+            on {departure} is confirmed.",
+    },
+    Template {
+        event: NotificationEvent::JourneyCancelled,
+        language: "fr",
+        subject: "Voyage annulé",
+        body: "TXpress : le voyage du taxi {taxi} ({origin} → {destination}) prévu le {departure} \
+            est annulé.",
+    },
+    Template {
+        event: NotificationEvent::JourneyCancelled,
+        language: "en",
+        subject: "Journey cancelled",
+        body: "TXpress: the journey of taxi {taxi} ({origin} → {destination}) scheduled on \
+            {departure} is cancelled.",
+    },
+    Template {
+        event: NotificationEvent::DepartureImminent,
+        language: "fr",
+        subject: "Départ imminent",
+        body: "TXpress : le taxi {taxi} ({origin} → {destination}) part le {departure}. \
+            Présentez-vous au rang.",
+    },
+    Template {
+        event: NotificationEvent::DepartureImminent,
+        language: "en",
+        subject: "Departure soon",
+        body: "TXpress: taxi {taxi} ({origin} → {destination}) leaves on {departure}. \
+            Please head to the rank.",
+    },
+];
+
+/// Looks the template up in the requested language, then in the fallback one.
+pub fn find(event: NotificationEvent, language: &str, fallback: &str) -> Option<&'static Template> {
+    let lookup = |language: &str| {
+        TEMPLATES
+            .iter()
+            .find(|t| t.event == event && t.language.eq_ignore_ascii_case(language))
+    };
+
+    lookup(language).or_else(|| lookup(fallback))
+}
+
+pub fn render(text: &str, values: &[(&str, String)]) -> String {
+    values.iter().fold(text.to_string(), |text, (key, value)| {
+        text.replace(&format!("{{{key}}}"), value)
+    })
+}
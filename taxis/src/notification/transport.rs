@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+
+use crate::entity::Channel;
+
+pub struct Message<'a> {
+    pub channel: Channel,
+    pub to: &'a str,
+    pub subject: Option<&'a str>,
+    pub body: &'a str,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TransportError {
+    #[error("the message can not be sent: {0}")]
+    Invalid(String),
+    #[error("the transport failed: {0}")]
+    Failed(String),
+}
+
+#[async_trait]
+pub trait NotificationTransport: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn send(&self, message: &Message<'_>) -> Result<(), TransportError>;
+}
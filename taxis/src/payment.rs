@@ -0,0 +1,341 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use log::{error, info, warn};
+use rocket::fairing::AdHoc;
+use rocket::tokio::sync::mpsc::{self, UnboundedReceiver};
+use rocket::tokio::{self, time};
+use rocket_db_pools::Database;
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use database::TXpressDB;
+
+pub use mock::MockProvider;
+pub use provider::{
+    CallbackEvent, CallbackStatus, PaymentError, PaymentProvider, PaymentRequest, ProviderIntent,
+};
+pub use refund::RefundPolicy;
+
+use crate::data::{NotificationDataProvider, PaymentDataProvider};
+use crate::entity::{NotificationEvent, Payment, Settlement};
+use crate::events::EventBus;
+use crate::notification::Notifier;
+use crate::usecase::{
+    ClaimPendingRefunds, MarkPaymentRefunded, ReleaseExpiredHolds, SettlePayment,
+};
+
+mod mock;
+mod provider;
+mod refund;
+
+const TARGET: &'static str = "payments";
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct PaymentConfig {
+    pub provider: String,
+    pub hold_minutes: i64,
+    pub sweep_seconds: u64,
+    /// How long a pending refund is left alone before it is tried again.
+    pub refund_retry_seconds: u64,
+    pub callback_secret: String,
+    pub mock: MockConfig,
+    pub refunds: RefundPolicy,
+}
+
+impl Default for PaymentConfig {
+    fn default() -> Self {
+        PaymentConfig {
+            provider: MockProvider::NAME.into(),
+            hold_minutes: 10,
+            sweep_seconds: 30,
+            refund_retry_seconds: 300,
+            callback_secret: String::new(),
+            mock: MockConfig::default(),
+            refunds: RefundPolicy::default(),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct MockConfig {
+    pub confirmation_delay_seconds: u64,
+    pub failing_suffix: String,
+}
+
+impl Default for MockConfig {
+    fn default() -> Self {
+        MockConfig {
+            confirmation_delay_seconds: 5,
+            failing_suffix: String::from("0000"),
+        }
+    }
+}
+
+pub struct Callback {
+    pub provider: String,
+    pub payload: String,
+    pub signature: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CallbackError {
+    #[error("unknown payment provider")]
+    UnknownProvider,
+    #[error("invalid callback signature")]
+    InvalidSignature,
+    #[error("malformed callback payload")]
+    Malformed,
+    #[error(transparent)]
+    Provider(#[from] PaymentError),
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+}
+
+#[derive(Clone)]
+pub struct Payments {
+    pub provider: Arc<dyn PaymentProvider>,
+    pub config: PaymentConfig,
+}
+
+impl Payments {
+    pub fn hold(&self) -> chrono::Duration {
+        chrono::Duration::minutes(self.config.hold_minutes)
+    }
+
+    pub async fn process_callback(
+        &self,
+        data_provider: &mut PaymentDataProvider,
+        provider: &str,
+        payload: &[u8],
+        signature: &str,
+    ) -> Result<Settlement, CallbackError> {
+        if provider != self.provider.name() {
+            return Err(CallbackError::UnknownProvider);
+        }
+
+        if !self.provider.verify_callback(payload, signature) {
+            return Err(CallbackError::InvalidSignature);
+        }
+
+        let event = self
+            .provider
+            .parse_callback(payload)
+            .ok_or(CallbackError::Malformed)?;
+
+        let settlement = data_provider
+            .settle_payment(
+                provider,
+                &event.reference,
+                matches!(event.status, CallbackStatus::Succeeded),
+                event.reason.as_deref(),
+            )
+            .await?;
+
+        if let Settlement::RefundRequired { payment } = &settlement {
+            self.refund(data_provider, payment).await;
+        }
+
+        Ok(settlement)
+    }
+
+    /// Refunds a payment with the provider and records it. A refund that fails stays pending, for
+    /// the payments worker to try again.
+    pub async fn refund<D>(&self, data_provider: &mut D, payment: &Payment) -> bool
+    where
+        D: MarkPaymentRefunded + Send,
+    {
+        if let Some(amount) = payment.refund() {
+            if let Err(err) = self.provider.refund(&payment.reference, &amount).await {
+                warn!(target: TARGET, "unable to refund `{}`, to be retried\n{err:?}", payment.id);
+                return false;
+            }
+        }
+
+        match data_provider.mark_payment_refunded(&payment.id).await {
+            Ok(()) => true,
+            Err(err) => {
+                error!(target: TARGET, "{err:?}");
+                false
+            }
+        }
+    }
+}
+
+pub fn stage() -> AdHoc {
+    AdHoc::try_on_ignite("Payments", |rocket| async {
+        let config = match rocket.figment().extract_inner::<PaymentConfig>("payments") {
+            Ok(config) => config,
+            Err(err) if err.missing() => PaymentConfig::default(),
+            Err(err) => {
+                error!(target: TARGET, "invalid payments configuration\n{err:?}");
+                return Err(rocket);
+            }
+        };
+
+        // Callbacks signed with an empty key would be anybody's to forge.
+        if config.callback_secret.is_empty() {
+            error!(target: TARGET, "the callback secret must not be empty");
+            return Err(rocket);
+        }
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let provider: Arc<dyn PaymentProvider> = match config.provider.as_str() {
+            MockProvider::NAME => Arc::new(MockProvider::new(&config, sender)),
+            other => {
+                error!(target: TARGET, "unknown payment provider `{other}`");
+                return Err(rocket);
+            }
+        };
+
+        let receiver = Mutex::new(receiver);
+        Ok(rocket
+            .manage(Payments { provider, config })
+            .attach(AdHoc::on_liftoff("Payment workers", |rocket| {
+                Box::pin(async move {
+                    let Some(db) = TXpressDB::fetch(rocket) else {
+                        return;
+                    };
+                    let pool = PgPool::clone(db);
+                    let payments = rocket.state::<Payments>().unwrap().clone();
+                    let notifier = rocket.state::<Notifier>().cloned();
+                    let bus = rocket.state::<EventBus>().cloned();
+                    let receiver = receiver.into_inner().unwrap();
+
+                    let every = Duration::from_secs(payments.config.sweep_seconds.max(1));
+                    tokio::spawn(release_expired_holds(pool.clone(), bus.clone(), every));
+                    tokio::spawn(retry_refunds(pool.clone(), payments.clone(), every));
+                    tokio::spawn(deliver_callbacks(pool, payments, notifier, bus, receiver));
+                })
+            })))
+    })
+}
+
+async fn release_expired_holds(pool: PgPool, bus: Option<EventBus>, every: Duration) {
+    let mut interval = time::interval(every);
+    loop {
+        interval.tick().await;
+
+        let conn = match pool.acquire().await {
+            Ok(conn) => conn,
+            Err(err) => {
+                error!(target: TARGET, "{err:?}");
+                continue;
+            }
+        };
+
+        match PaymentDataProvider::new(conn).release_expired_holds().await {
+            Err(err) => error!(target: TARGET, "unable to release expired holds\n{err:?}"),
+            Ok(journeys) if !journeys.is_empty() => {
+                info!(target: TARGET, "released expired holds on {} journey(s)", journeys.len());
+                if let Some(bus) = &bus {
+                    for journey_id in &journeys {
+                        bus.journey_changed(journey_id, "hold_expired");
+                    }
+                }
+            }
+            Ok(_) => {}
+        }
+    }
+}
+
+async fn retry_refunds(pool: PgPool, payments: Payments, every: Duration) {
+    let idle = chrono::Duration::seconds(payments.config.refund_retry_seconds as i64);
+    let mut interval = time::interval(every);
+    loop {
+        interval.tick().await;
+
+        let conn = match pool.acquire().await {
+            Ok(conn) => conn,
+            Err(err) => {
+                error!(target: TARGET, "{err:?}");
+                continue;
+            }
+        };
+
+        let mut data_provider = PaymentDataProvider::new(conn);
+        let refunds = match data_provider
+            .claim_pending_refunds(&(Utc::now() - idle))
+            .await
+        {
+            Ok(refunds) => refunds,
+            Err(err) => {
+                error!(target: TARGET, "unable to claim pending refunds\n{err:?}");
+                continue;
+            }
+        };
+
+        for payment in &refunds {
+            if payments.refund(&mut data_provider, payment).await {
+                info!(target: TARGET, "refunded `{}` on retry", payment.id);
+            }
+        }
+    }
+}
+
+async fn deliver_callbacks(
+    pool: PgPool,
+    payments: Payments,
+    notifier: Option<Notifier>,
+    bus: Option<EventBus>,
+    mut receiver: UnboundedReceiver<Callback>,
+) {
+    while let Some(callback) = receiver.recv().await {
+        let conn = match pool.acquire().await {
+            Ok(conn) => conn,
+            Err(err) => {
+                error!(target: TARGET, "{err:?}");
+                continue;
+            }
+        };
+
+        let mut data_provider = PaymentDataProvider::new(conn);
+        let settlement = payments
+            .process_callback(
+                &mut data_provider,
+                &callback.provider,
+                callback.payload.as_bytes(),
+                &callback.signature,
+            )
+            .await;
+
+        let settlement = match settlement {
+            Ok(settlement) => settlement,
+            Err(err) => {
+                error!(target: TARGET, "unable to process callback\n{err:?}");
+                continue;
+            }
+        };
+
+        let change = match &settlement {
+            Settlement::Confirmed { journey_id, .. } => Some((journey_id, "booking_confirmed")),
+            Settlement::Released { journey_id, .. } => Some((journey_id, "booking_failed")),
+            _ => None,
+        };
+        if let (Some(bus), Some((journey_id, kind))) = (&bus, change) {
+            bus.journey_changed(journey_id, kind);
+        }
+
+        if let (Settlement::Confirmed { booking_id, .. }, Some(notifier)) = (&settlement, &notifier)
+        {
+            let conn = match pool.acquire().await {
+                Ok(conn) => conn,
+                Err(err) => {
+                    error!(target: TARGET, "{err:?}");
+                    continue;
+                }
+            };
+
+            notifier
+                .notify(
+                    &mut NotificationDataProvider::new(conn),
+                    NotificationEvent::BookingConfirmed,
+                    booking_id,
+                )
+                .await;
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use domain::entity::Price;
+use log::info;
+use rocket::tokio::sync::mpsc::UnboundedSender;
+use rocket::tokio::{self, time};
+
+use crate::payment::provider::{
+    CallbackEvent, CallbackStatus, PaymentError, PaymentProvider, PaymentRequest, ProviderIntent,
+};
+use crate::payment::{Callback, PaymentConfig};
+use crate::signature;
+
+const TARGET: &'static str = "mock-payment-provider";
+
+pub struct MockProvider {
+    secret: String,
+    delay: Duration,
+    failing_suffix: String,
+    callbacks: UnboundedSender<Callback>,
+}
+
+impl MockProvider {
+    pub const NAME: &'static str = "mock";
+
+    pub fn new(config: &PaymentConfig, callbacks: UnboundedSender<Callback>) -> Self {
+        MockProvider {
+            secret: config.callback_secret.clone(),
+            delay: Duration::from_secs(config.mock.confirmation_delay_seconds),
+            failing_suffix: config.mock.failing_suffix.clone(),
+            callbacks,
+        }
+    }
+
+    fn outcome(&self, phone_number: &str) -> (CallbackStatus, Option<String>) {
+        if !self.failing_suffix.is_empty() && phone_number.ends_with(&self.failing_suffix) {
+            return (
+                CallbackStatus::Failed,
+                Some(String::from("insufficient funds")),
+            );
+        }
+
+        (CallbackStatus::Succeeded, None)
+    }
+}
+
+#[async_trait]
+impl PaymentProvider for MockProvider {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    async fn create_intent(
+        &self,
+        request: &PaymentRequest,
+    ) -> Result<ProviderIntent, PaymentError> {
+        let reference = request.reference.clone();
+        let (status, reason) = self.outcome(&request.phone_number);
+
+        let payload = serde_json::to_string(&CallbackEvent {
+            reference: reference.clone(),
+            status,
+            reason,
+        })
+        .map_err(|err| PaymentError::Unavailable(err.to_string()))?;
+        let signature = hex::encode(signature::sign(self.secret.as_bytes(), payload.as_bytes()));
+
+        info!(target: TARGET, "payment `{reference}` of {} requested", request.amount);
+
+        let callbacks = self.callbacks.clone();
+        let delay = self.delay;
+        tokio::spawn(async move {
+            time::sleep(delay).await;
+            let _ = callbacks.send(Callback {
+                provider: Self::NAME.into(),
+                payload,
+                signature,
+            });
+        });
+
+        Ok(ProviderIntent { reference })
+    }
+
+    async fn refund(&self, reference: &str, amount: &Price) -> Result<(), PaymentError> {
+        info!(target: TARGET, "refunded {amount} on payment `{reference}`");
+        Ok(())
+    }
+
+    fn verify_callback(&self, payload: &[u8], signature: &str) -> bool {
+        hex::decode(signature)
+            .map(|s| signature::verify(self.secret.as_bytes(), payload, &s))
+            .unwrap_or(false)
+    }
+
+    fn parse_callback(&self, payload: &[u8]) -> Option<CallbackEvent> {
+        serde_json::from_slice(payload).ok()
+    }
+}
@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use domain::entity::Price;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub struct PaymentRequest {
+    pub booking_id: Uuid,
+    /// Ours, under which the payment is already recorded, for providers letting the merchant
+    /// choose it.
+    pub reference: String,
+    pub phone_number: String,
+    pub amount: Price,
+}
+
+pub struct ProviderIntent {
+    /// The one callbacks refer to, when the provider gives its own.
+    pub reference: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallbackStatus {
+    Succeeded,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CallbackEvent {
+    pub reference: String,
+    pub status: CallbackStatus,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum PaymentError {
+    #[error("the payment provider declined the request: {0}")]
+    Declined(String),
+    #[error("the payment provider is unavailable: {0}")]
+    Unavailable(String),
+}
+
+#[async_trait]
+pub trait PaymentProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn create_intent(&self, request: &PaymentRequest)
+        -> Result<ProviderIntent, PaymentError>;
+
+    async fn refund(&self, reference: &str, amount: &Price) -> Result<(), PaymentError>;
+
+    fn verify_callback(&self, payload: &[u8], signature: &str) -> bool;
+
+    fn parse_callback(&self, payload: &[u8]) -> Option<CallbackEvent>;
+}
@@ -0,0 +1,98 @@
+use chrono::{DateTime, Duration, Utc};
+use domain::entity::Price;
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct RefundPolicy {
+    pub full_refund_hours: i64,
+    pub partial_refund_hours: i64,
+    pub partial_refund_percent: u8,
+}
+
+impl Default for RefundPolicy {
+    fn default() -> Self {
+        RefundPolicy {
+            full_refund_hours: 24,
+            partial_refund_hours: 2,
+            partial_refund_percent: 50,
+        }
+    }
+}
+
+impl RefundPolicy {
+    pub fn refund(&self, paid: &Price, departure: DateTime<Utc>, now: DateTime<Utc>) -> Price {
+        let notice = departure - now;
+        let percent = if notice >= Duration::hours(self.full_refund_hours) {
+            100
+        } else if notice >= Duration::hours(self.partial_refund_hours) {
+            self.partial_refund_percent.min(100) as u128
+        } else {
+            0
+        };
+
+        Price::new((paid.amount as u128 * percent / 100) as u64, paid.currency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use domain::entity::{Currency, Price};
+
+    use super::RefundPolicy;
+
+    fn departure() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2023, 7, 1, 8, 0, 0).unwrap()
+    }
+
+    fn refund(policy: &RefundPolicy, paid: u64, notice: Duration) -> u64 {
+        let paid = Price::new(paid, Currency::XAF);
+        let refund = policy.refund(&paid, departure(), departure() - notice);
+        assert_eq!(refund.currency, paid.currency);
+        refund.amount
+    }
+
+    #[test]
+    fn refunds_in_full_from_the_full_refund_notice() {
+        let policy = RefundPolicy::default();
+        assert_eq!(refund(&policy, 5000, Duration::hours(24)), 5000);
+        assert_eq!(refund(&policy, 5000, Duration::days(3)), 5000);
+    }
+
+    #[test]
+    fn refunds_in_part_below_the_full_refund_notice() {
+        let policy = RefundPolicy::default();
+        let notice = Duration::hours(24) - Duration::seconds(1);
+        assert_eq!(refund(&policy, 5000, notice), 2500);
+        assert_eq!(refund(&policy, 5000, Duration::hours(2)), 2500);
+    }
+
+    #[test]
+    fn refunds_nothing_below_the_partial_refund_notice() {
+        let policy = RefundPolicy::default();
+        let notice = Duration::hours(2) - Duration::seconds(1);
+        assert_eq!(refund(&policy, 5000, notice), 0);
+        assert_eq!(refund(&policy, 5000, Duration::zero()), 0);
+        assert_eq!(refund(&policy, 5000, -Duration::hours(1)), 0);
+    }
+
+    #[test]
+    fn rounds_partial_refunds_down() {
+        let policy = RefundPolicy {
+            partial_refund_percent: 33,
+            ..RefundPolicy::default()
+        };
+        assert_eq!(refund(&policy, 100, Duration::hours(2)), 33);
+        assert_eq!(refund(&policy, 1, Duration::hours(2)), 0);
+    }
+
+    #[test]
+    fn caps_the_partial_refund_at_the_amount_paid() {
+        let policy = RefundPolicy {
+            partial_refund_percent: 150,
+            ..RefundPolicy::default()
+        };
+        assert_eq!(refund(&policy, 5000, Duration::hours(2)), 5000);
+    }
+}
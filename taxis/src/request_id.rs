@@ -0,0 +1,73 @@
+use std::fmt;
+
+use rocket::fairing::{AdHoc, Fairing, Info, Kind};
+use rocket::http::Header;
+use rocket::{Data, Request, Response};
+use uuid::Uuid;
+
+pub const HEADER: &'static str = "X-Request-Id";
+
+/// Longest id accepted from a client, beyond which a new one is made.
+const MAX_LENGTH: usize = 128;
+
+/// Identifies a request across the logs, its response and the failure it may end with.
+#[derive(Clone, Debug)]
+pub struct RequestId(String);
+
+impl RequestId {
+    fn new() -> Self {
+        RequestId(Uuid::new_v4().to_string())
+    }
+
+    /// The id given by the client, when it is fit to be logged and echoed as is.
+    fn accepted(value: &str) -> Option<Self> {
+        let valid = (1..=MAX_LENGTH).contains(&value.len())
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        valid.then(|| RequestId(value.into()))
+    }
+
+    /// The id of the request, made up on the spot if the fairing did not run.
+    pub fn of<'r>(request: &'r Request<'_>) -> &'r RequestId {
+        request.local_cache(RequestId::new)
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Gives every request the id of its `X-Request-Id` header, or a new one, and echoes it in the
+/// response.
+struct RequestIds;
+
+#[rocket::async_trait]
+impl Fairing for RequestIds {
+    fn info(&self) -> Info {
+        Info {
+            name: "Identify requests",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
+        let id = request
+            .headers()
+            .get_one(HEADER)
+            .and_then(RequestId::accepted)
+            .unwrap_or_else(RequestId::new);
+
+        request.local_cache(|| id);
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        response.set_header(Header::new(HEADER, RequestId::of(request).to_string()));
+    }
+}
+
+pub fn stage() -> AdHoc {
+    AdHoc::on_ignite("Request ids", |rocket| async { rocket.attach(RequestIds) })
+}
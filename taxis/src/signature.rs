@@ -0,0 +1,16 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub fn sign(secret: &[u8], payload: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(payload);
+    mac.finalize().into_bytes().to_vec()
+}
+
+pub fn verify(secret: &[u8], payload: &[u8], signature: &[u8]) -> bool {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(payload);
+    mac.verify_slice(signature).is_ok()
+}
@@ -0,0 +1,140 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use log::error;
+use rocket::fairing::AdHoc;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::signature;
+
+pub mod qr;
+
+const TARGET: &'static str = "tickets";
+pub const VERSION: &'static str = "TX1";
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct TicketConfig {
+    secret: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct TicketClaims {
+    pub booking_id: Uuid,
+    pub journey_id: Uuid,
+    pub seats: u8,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum TicketError {
+    #[error("the ticket is malformed")]
+    Malformed,
+    #[error("the ticket signature is invalid")]
+    InvalidSignature,
+}
+
+pub struct TicketSigner {
+    secret: Vec<u8>,
+}
+
+impl TicketSigner {
+    pub fn new(secret: &[u8]) -> Self {
+        TicketSigner {
+            secret: secret.to_vec(),
+        }
+    }
+
+    /// Signing key of a single journey, derived from the secret so that none has to be stored.
+    fn signing_key(&self, journey_id: &Uuid) -> SigningKey {
+        let seed = signature::sign(&self.secret, journey_id.as_bytes());
+        SigningKey::from_bytes(
+            seed.as_slice()
+                .try_into()
+                .expect("HMAC-SHA256 yields 32 bytes"),
+        )
+    }
+
+    /// Public key of a single journey: it checks the tickets of the trip, but can't forge any.
+    pub fn journey_key(&self, journey_id: &Uuid) -> [u8; 32] {
+        self.signing_key(journey_id).verifying_key().to_bytes()
+    }
+
+    pub fn issue(&self, claims: &TicketClaims) -> String {
+        let body = format!(
+            "{VERSION}.{}.{}.{}",
+            claims.booking_id.simple(),
+            claims.journey_id.simple(),
+            claims.seats
+        );
+        let sig = self.signing_key(&claims.journey_id).sign(body.as_bytes());
+
+        format!("{body}.{}", URL_SAFE_NO_PAD.encode(sig.to_bytes()))
+    }
+
+    pub fn verify(&self, payload: &str) -> Result<TicketClaims, TicketError> {
+        let claims = parse(payload)?;
+        verify(&self.journey_key(&claims.journey_id), payload)
+    }
+}
+
+/// Checks a ticket with the public key of its journey only, the same way a driver's device does
+/// offline.
+pub fn verify(journey_key: &[u8; 32], payload: &str) -> Result<TicketClaims, TicketError> {
+    let (body, sig) = payload.rsplit_once('.').ok_or(TicketError::Malformed)?;
+    let sig = URL_SAFE_NO_PAD
+        .decode(sig)
+        .map_err(|_| TicketError::Malformed)?;
+    let sig = Signature::from_slice(&sig).map_err(|_| TicketError::Malformed)?;
+
+    let key = VerifyingKey::from_bytes(journey_key).map_err(|_| TicketError::InvalidSignature)?;
+    if key.verify_strict(body.as_bytes(), &sig).is_err() {
+        return Err(TicketError::InvalidSignature);
+    }
+
+    parse(payload)
+}
+
+fn parse(payload: &str) -> Result<TicketClaims, TicketError> {
+    let mut parts = payload.split('.');
+    if parts.next() != Some(VERSION) {
+        return Err(TicketError::Malformed);
+    }
+
+    let mut next = || parts.next().ok_or(TicketError::Malformed);
+    let booking_id = Uuid::try_parse(next()?).map_err(|_| TicketError::Malformed)?;
+    let journey_id = Uuid::try_parse(next()?).map_err(|_| TicketError::Malformed)?;
+    let seats = next()?.parse().map_err(|_| TicketError::Malformed)?;
+    next()?;
+
+    if parts.next().is_some() {
+        return Err(TicketError::Malformed);
+    }
+
+    Ok(TicketClaims {
+        booking_id,
+        journey_id,
+        seats,
+    })
+}
+
+pub fn stage() -> AdHoc {
+    AdHoc::try_on_ignite("Tickets", |rocket| async {
+        let config = match rocket.figment().extract_inner::<TicketConfig>("tickets") {
+            Ok(config) => config,
+            Err(err) if err.missing() => TicketConfig::default(),
+            Err(err) => {
+                error!(target: TARGET, "invalid tickets configuration\n{err:?}");
+                return Err(rocket);
+            }
+        };
+
+        // Tickets signed with an empty key would be anybody's to forge.
+        if config.secret.is_empty() {
+            error!(target: TARGET, "the ticket secret must not be empty");
+            return Err(rocket);
+        }
+
+        Ok(rocket.manage(TicketSigner::new(config.secret.as_bytes())))
+    })
+}
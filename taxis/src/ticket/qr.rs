@@ -0,0 +1,69 @@
+use std::fmt::Write;
+
+use qrcodegen::{DataTooLong, QrCode, QrCodeEcc};
+
+const BORDER: i32 = 4;
+
+#[derive(thiserror::Error, Debug)]
+pub enum QrError {
+    #[error(transparent)]
+    TooLong(#[from] DataTooLong),
+    #[error(transparent)]
+    Png(#[from] png::EncodingError),
+}
+
+pub fn svg(payload: &str) -> Result<String, QrError> {
+    let qr = QrCode::encode_text(payload, QrCodeEcc::Medium)?;
+    let dimension = qr.size() + BORDER * 2;
+
+    let mut path = String::new();
+    for y in 0..qr.size() {
+        for x in 0..qr.size() {
+            if qr.get_module(x, y) {
+                let _ = write!(path, "M{},{}h1v1h-1z ", x + BORDER, y + BORDER);
+            }
+        }
+    }
+
+    Ok(format!(
+        concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" ",
+            "viewBox=\"0 0 {0} {0}\" stroke=\"none\">\n",
+            "<rect width=\"100%\" height=\"100%\" fill=\"#FFFFFF\"/>\n",
+            "<path d=\"{1}\" fill=\"#000000\"/>\n",
+            "</svg>\n"
+        ),
+        dimension,
+        path.trim_end()
+    ))
+}
+
+pub fn png(payload: &str, scale: u32) -> Result<Vec<u8>, QrError> {
+    let qr = QrCode::encode_text(payload, QrCodeEcc::Medium)?;
+    let scale = scale.max(1);
+    let modules = (qr.size() + BORDER * 2) as u32;
+    let dimension = modules * scale;
+
+    let mut pixels = Vec::with_capacity((dimension * dimension) as usize);
+    for py in 0..dimension {
+        for px in 0..dimension {
+            let x = (px / scale) as i32 - BORDER;
+            let y = (py / scale) as i32 - BORDER;
+            pixels.push(if qr.get_module(x, y) { 0x00 } else { 0xFF });
+        }
+    }
+
+    let mut image = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut image, dimension, dimension);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels)?;
+        writer.finish()?;
+    }
+
+    Ok(image)
+}
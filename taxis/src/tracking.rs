@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use log::{error, info};
+use rocket::fairing::AdHoc;
+use rocket::tokio::{self, time};
+use rocket_db_pools::Database;
+use serde::Deserialize;
+use sqlx::PgPool;
+
+use database::TXpressDB;
+
+use crate::data::TrackingDataProvider;
+use crate::entity::{Position, TrackedJourney};
+use crate::usecase::PrunePositions;
+
+const TARGET: &'static str = "tracking";
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct TrackingConfig {
+    /// Pings closer than both thresholds to the last recorded one are dropped.
+    pub min_interval_seconds: i64,
+    pub min_distance_meters: f64,
+    pub max_points: i64,
+    pub retention_hours: i64,
+    /// How long before its departure a journey starts accepting positions.
+    pub lead_minutes: i64,
+    /// How long after its departure an unclosed journey keeps accepting positions.
+    pub max_journey_hours: i64,
+    /// Used for the ETA when the taxi does not report its speed, or stands still.
+    pub default_speed_kmh: f64,
+    /// Ratio of the road distance to the straight-line distance.
+    pub road_factor: f64,
+    pub sweep_seconds: u64,
+}
+
+impl Default for TrackingConfig {
+    fn default() -> Self {
+        TrackingConfig {
+            min_interval_seconds: 10,
+            min_distance_meters: 25.0,
+            max_points: 2000,
+            retention_hours: 48,
+            lead_minutes: 60,
+            max_journey_hours: 12,
+            default_speed_kmh: 40.0,
+            road_factor: 1.3,
+            sweep_seconds: 600,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Tracking {
+    pub config: TrackingConfig,
+}
+
+impl Tracking {
+    pub fn is_active(&self, journey: &TrackedJourney, now: DateTime<Utc>) -> bool {
+        let opens =
+            journey.departure_schedule - chrono::Duration::minutes(self.config.lead_minutes);
+        let closes =
+            journey.departure_schedule + chrono::Duration::hours(self.config.max_journey_hours);
+
+        !journey.closed && opens <= now && now <= closes
+    }
+
+    pub fn should_record(&self, last: Option<&Position>, position: &Position) -> bool {
+        let Some(last) = last else {
+            return true;
+        };
+        if position.recorded_at <= last.recorded_at {
+            return false;
+        }
+
+        let elapsed = position.recorded_at - last.recorded_at;
+        let moved = last.coordinates().distance_km(&position.coordinates()) * 1000.0;
+
+        elapsed >= chrono::Duration::seconds(self.config.min_interval_seconds)
+            || moved >= self.config.min_distance_meters
+    }
+
+    /// Remaining distance to the destination rank and the expected arrival, when the rank is
+    /// located.
+    pub fn eta(
+        &self,
+        journey: &TrackedJourney,
+        position: &Position,
+    ) -> Option<(f64, DateTime<Utc>)> {
+        let distance = position.coordinates().distance_km(&journey.destination()?);
+
+        let speed = position
+            .speed_kmh
+            .filter(|speed| *speed >= 5.0)
+            .unwrap_or(self.config.default_speed_kmh)
+            .max(1.0);
+        let seconds = distance * self.config.road_factor / speed * 3600.0;
+
+        let start = position.recorded_at.max(journey.departure_schedule);
+        Some((
+            distance,
+            start + chrono::Duration::seconds(seconds.round() as i64),
+        ))
+    }
+}
+
+pub fn stage() -> AdHoc {
+    AdHoc::try_on_ignite("Tracking", |rocket| async {
+        let config = match rocket.figment().extract_inner::<TrackingConfig>("tracking") {
+            Ok(config) => config,
+            Err(err) if err.missing() => TrackingConfig::default(),
+            Err(err) => {
+                error!(target: TARGET, "invalid tracking configuration\n{err:?}");
+                return Err(rocket);
+            }
+        };
+
+        Ok(rocket.manage(Tracking { config }).attach(AdHoc::on_liftoff(
+            "Tracking worker",
+            |rocket| {
+                Box::pin(async move {
+                    let Some(db) = TXpressDB::fetch(rocket) else {
+                        return;
+                    };
+                    let tracking = rocket.state::<Tracking>().unwrap().clone();
+
+                    tokio::spawn(prune(PgPool::clone(db), tracking));
+                })
+            },
+        )))
+    })
+}
+
+/// Drops the positions older than the retention period.
+async fn prune(pool: PgPool, tracking: Tracking) {
+    let retention = chrono::Duration::hours(tracking.config.retention_hours);
+    let mut interval = time::interval(Duration::from_secs(tracking.config.sweep_seconds.max(1)));
+    loop {
+        interval.tick().await;
+
+        let conn = match pool.acquire().await {
+            Ok(conn) => conn,
+            Err(err) => {
+                error!(target: TARGET, "{err:?}");
+                continue;
+            }
+        };
+
+        match TrackingDataProvider::new(conn)
+            .prune_positions(Utc::now() - retention)
+            .await
+        {
+            Err(err) => error!(target: TARGET, "unable to prune positions\n{err:?}"),
+            Ok(0) => {}
+            Ok(count) => info!(target: TARGET, "{count} position(s) pruned"),
+        }
+    }
+}
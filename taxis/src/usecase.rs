@@ -1,29 +1,133 @@
+pub use add_payment::AddPayment;
+pub use add_route::AddRoute;
 pub use add_taxi::AddTaxi;
 pub use add_taxi_ranks::AddTaxiRanks;
+pub use advance_rank_queue::AdvanceRankQueue;
+pub use attach_payment_reference::AttachPaymentReference;
+pub use board_passenger::BoardPassenger;
+pub use cancel_booking::CancelBooking;
 pub use cancel_journey::CancelJourney;
+pub use check_in_rank_queue::CheckInRankQueue;
+pub use claim_pending_refunds::ClaimPendingRefunds;
+pub use claim_waitlist_offer::ClaimWaitlistOffer;
 pub use close_journey::CloseJourney;
+pub use close_stale_journeys::CloseStaleJourneys;
+pub use delete_stand::DeleteStand;
+pub use depart_rank_queue::DepartRankQueue;
 pub use does_booking_exists_on_journey::DoesBookingExistsOnJourney;
 pub use does_taxi_exists::DoesTaxiExists;
+pub use enqueue_notifications::EnqueueNotifications;
+pub use expire_waitlist_offers::ExpireWaitlistOffers;
 pub use get_all_journey::GetAllJourney;
 pub use get_all_stand::GetAllStand;
+pub use get_audience::GetAudience;
+pub use get_booking::GetBooking;
+pub use get_booking_payment::GetBookingPayment;
+pub use get_due_notifications::GetDueNotifications;
+pub use get_fare::GetFare;
+pub use get_imminent_departures::GetImminentDepartures;
 pub use get_in_progress_journey::GetInProgressJourney;
 pub use get_journey::GetJourney;
+pub use get_journey_manifest::GetJourneyManifest;
+pub use get_journey_stops::GetJourneyStops;
+pub use get_latest_position::GetLatestPosition;
 pub use get_owner::GetOwner;
+pub use get_queue_entry::GetQueueEntry;
+pub use get_rank_queue::GetRankQueue;
+pub use get_route::GetRoute;
+pub use get_route_between::GetRouteBetween;
+pub use get_routes::GetRoutes;
+pub use get_seat_map::GetSeatMap;
 pub use get_stand::GetStand;
+pub use get_stands::GetStands;
+pub use get_stands_within::GetStandsWithin;
+pub use get_tracked_journey::GetTrackedJourney;
+pub use get_waitlist_entry::GetWaitlistEntry;
+pub use get_waitlisted_journeys::GetWaitlistedJourneys;
 pub use has_a_journey_in_progress::HasAJourneyInProgress;
+pub use join_waitlist::JoinWaitlist;
+pub use leave_rank_queue::LeaveRankQueue;
+pub use leave_waitlist::LeaveWaitlist;
+pub use mark_payment_refunded::MarkPaymentRefunded;
+pub use perform_booking::PerformBooking;
 pub use perform_journey::PerformJourney;
+pub use promote_waitlist::PromoteWaitlist;
+pub use prune_positions::PrunePositions;
+pub use record_delivery_attempt::RecordDeliveryAttempt;
+pub use record_position::RecordPosition;
+pub use release_booking::ReleaseBooking;
+pub use release_expired_holds::ReleaseExpiredHolds;
+pub use search_journeys::SearchJourneys;
+pub use set_fare::SetFare;
+pub use set_journey_fare::SetJourneyFare;
+pub use set_owner_password::SetOwnerPassword;
+pub use settle_payment::SettlePayment;
+pub use update_route::UpdateRoute;
+pub use update_stand::UpdateStand;
 
+mod add_payment;
+mod add_route;
 mod add_taxi;
 mod add_taxi_ranks;
+mod advance_rank_queue;
+mod attach_payment_reference;
+mod board_passenger;
+mod cancel_booking;
 mod cancel_journey;
+mod check_in_rank_queue;
+mod claim_pending_refunds;
+mod claim_waitlist_offer;
 mod close_journey;
+mod close_stale_journeys;
+mod delete_stand;
+mod depart_rank_queue;
 mod does_booking_exists_on_journey;
 mod does_taxi_exists;
+mod enqueue_notifications;
+mod expire_waitlist_offers;
 mod get_all_journey;
 mod get_all_stand;
+mod get_audience;
+mod get_booking;
+mod get_booking_payment;
+mod get_due_notifications;
+mod get_fare;
+mod get_imminent_departures;
 mod get_in_progress_journey;
 mod get_journey;
+mod get_journey_manifest;
+mod get_journey_stops;
+mod get_latest_position;
 mod get_owner;
+mod get_queue_entry;
+mod get_rank_queue;
+mod get_route;
+mod get_route_between;
+mod get_routes;
+mod get_seat_map;
 mod get_stand;
+mod get_stands;
+mod get_stands_within;
+mod get_tracked_journey;
+mod get_waitlist_entry;
+mod get_waitlisted_journeys;
 mod has_a_journey_in_progress;
+mod join_waitlist;
+mod leave_rank_queue;
+mod leave_waitlist;
+mod mark_payment_refunded;
+mod perform_booking;
 mod perform_journey;
+mod promote_waitlist;
+mod prune_positions;
+mod record_delivery_attempt;
+mod record_position;
+mod release_booking;
+mod release_expired_holds;
+mod search_journeys;
+mod set_fare;
+mod set_journey_fare;
+mod set_owner_password;
+mod settle_payment;
+mod update_route;
+mod update_stand;
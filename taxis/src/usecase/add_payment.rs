@@ -0,0 +1,8 @@
+use async_trait::async_trait;
+
+use crate::entity::Payment;
+
+#[async_trait]
+pub trait AddPayment {
+    async fn add_payment(&mut self, payment: &Payment) -> sqlx::Result<()>;
+}
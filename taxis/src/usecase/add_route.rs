@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+
+use crate::entity::{NewRoute, Route};
+
+#[async_trait]
+pub trait AddRoute {
+    /// None when the ranks are already linked by a route.
+    async fn add_route(&mut self, route: &NewRoute) -> sqlx::Result<Option<Route>>;
+}
@@ -1,8 +1,8 @@
 use async_trait::async_trait;
 
-use crate::entity::Stand;
+use crate::entity::{AddedStands, NewStand};
 
 #[async_trait]
 pub trait AddTaxiRanks {
-    async fn add_taxi_ranks(&mut self, stand_names: Vec<String>) -> sqlx::Result<Vec<Stand>>;
+    async fn add_taxi_ranks(&mut self, stands: Vec<NewStand>) -> sqlx::Result<AddedStands>;
 }
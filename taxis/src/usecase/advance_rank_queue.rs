@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait AdvanceRankQueue {
+    /// Dispatches the taxi at the head of the queue when no other is being loaded, returning its
+    /// journey.
+    async fn advance_rank_queue(&mut self, rank_id: &Uuid) -> sqlx::Result<Option<Uuid>>;
+}
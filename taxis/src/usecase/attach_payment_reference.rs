@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait AttachPaymentReference {
+    async fn attach_payment_reference(
+        &mut self,
+        payment_id: &Uuid,
+        reference: &str,
+    ) -> sqlx::Result<()>;
+}
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::Boarding;
+
+#[async_trait]
+pub trait BoardPassenger {
+    async fn board_passenger(
+        &mut self,
+        taxi: &str,
+        journey_id: &Uuid,
+        booking_id: &Uuid,
+    ) -> sqlx::Result<Boarding>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use domain::entity::Price;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait CancelBooking {
+    async fn cancel_booking(
+        &mut self,
+        id: &Uuid,
+        reason: Option<&str>,
+        refund: Option<&Price>,
+    ) -> sqlx::Result<Option<Uuid>>;
+}
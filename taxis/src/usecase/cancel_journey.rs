@@ -3,5 +3,5 @@ use uuid::Uuid;
 
 #[async_trait]
 pub trait CancelJourney {
-    async fn cancel_journey(&mut self, journey_id: &Uuid) -> sqlx::Result<()>;
+    async fn cancel_journey(&mut self, owner: &str, journey_id: &Uuid) -> sqlx::Result<bool>;
 }
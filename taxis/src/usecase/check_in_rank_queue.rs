@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::CheckIn;
+
+#[async_trait]
+pub trait CheckInRankQueue {
+    async fn check_in_rank_queue(&mut self, rank_id: &Uuid, taxi: &str) -> sqlx::Result<CheckIn>;
+}
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::entity::Payment;
+
+#[async_trait]
+pub trait ClaimPendingRefunds {
+    /// The refunds left pending since before `before`, claimed until they are tried again.
+    async fn claim_pending_refunds(&mut self, before: &DateTime<Utc>)
+        -> sqlx::Result<Vec<Payment>>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::{Booking, NewBooking};
+
+#[async_trait]
+pub trait ClaimWaitlistOffer {
+    async fn claim_waitlist_offer(
+        &mut self,
+        id: &Uuid,
+        booking: &NewBooking,
+    ) -> sqlx::Result<Option<Booking>>;
+}
@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[async_trait]
+pub trait CloseStaleJourneys {
+    /// Closes the journeys still open that departed before `before`, returning their ids.
+    async fn close_stale_journeys(&mut self, before: &DateTime<Utc>) -> sqlx::Result<Vec<Uuid>>;
+}
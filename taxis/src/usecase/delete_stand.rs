@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::StandDeletion;
+
+#[async_trait]
+pub trait DeleteStand {
+    /// Removes the rank along with its fares and routes, unless a trip uses it.
+    async fn delete_stand(&mut self, id: &Uuid) -> sqlx::Result<StandDeletion>;
+}
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::QueueExit;
+
+#[async_trait]
+pub trait DepartRankQueue {
+    /// Ends the queue entry of a journey once it is closed.
+    async fn depart_rank_queue(&mut self, journey_id: &Uuid) -> sqlx::Result<Option<QueueExit>>;
+}
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+
+use crate::entity::NewNotification;
+
+#[async_trait]
+pub trait EnqueueNotifications {
+    async fn enqueue_notifications(
+        &mut self,
+        notifications: &[NewNotification],
+    ) -> sqlx::Result<u64>;
+}
@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait ExpireWaitlistOffers {
+    async fn expire_waitlist_offers(&mut self) -> sqlx::Result<Vec<Uuid>>;
+}
@@ -1,7 +1,12 @@
 use async_trait::async_trait;
-use crate::entity::Journey;
+use crate::entity::{Journey, JourneyFilter, Page, PageRequest};
 
 #[async_trait]
 pub trait GetAllJourney {
-    async fn get_all_journey(&mut self, taxi_num: &str) -> sqlx::Result<Vec<Journey>>;
+    async fn get_all_journey(
+        &mut self,
+        taxi_num: &str,
+        filter: &JourneyFilter,
+        page: &PageRequest,
+    ) -> sqlx::Result<Page<Journey>>;
 }
@@ -1,8 +1,12 @@
 use async_trait::async_trait;
 
-use crate::entity::Stand;
+use crate::entity::{Page, PageRequest, Stand, StandFilter};
 
 #[async_trait]
 pub trait GetAllStand {
-    async fn get_all_stand(&mut self) -> sqlx::Result<Vec<Stand>>;
+    async fn get_all_stand(
+        &mut self,
+        filter: &StandFilter,
+        page: &PageRequest,
+    ) -> sqlx::Result<Page<Stand>>;
 }
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::{Audience, NotificationEvent};
+
+#[async_trait]
+pub trait GetAudience {
+    /// `id` is the booking for booking events and the journey for journey events.
+    async fn get_audience(
+        &mut self,
+        event: NotificationEvent,
+        id: &Uuid,
+    ) -> sqlx::Result<Option<Audience>>;
+}
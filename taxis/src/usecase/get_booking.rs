@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::Booking;
+
+#[async_trait]
+pub trait GetBooking {
+    async fn get_booking(&mut self, id: &Uuid) -> sqlx::Result<Option<Booking>>;
+}
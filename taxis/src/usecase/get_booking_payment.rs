@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::Payment;
+
+#[async_trait]
+pub trait GetBookingPayment {
+    async fn get_booking_payment(&mut self, booking_id: &Uuid) -> sqlx::Result<Option<Payment>>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use chrono::Duration;
+
+use crate::entity::Notification;
+
+#[async_trait]
+pub trait GetDueNotifications {
+    async fn get_due_notifications(
+        &mut self,
+        limit: i64,
+        lease: Duration,
+    ) -> sqlx::Result<Vec<Notification>>;
+}
@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::Fare;
+
+#[async_trait]
+pub trait GetFare {
+    async fn get_fare(&mut self, origin: &Uuid, destination: &Uuid) -> sqlx::Result<Option<Fare>>;
+}
@@ -0,0 +1,8 @@
+use async_trait::async_trait;
+use chrono::Duration;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait GetImminentDepartures {
+    async fn get_imminent_departures(&mut self, within: Duration) -> sqlx::Result<Vec<Uuid>>;
+}
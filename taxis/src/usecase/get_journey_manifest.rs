@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::Manifest;
+
+#[async_trait]
+pub trait GetJourneyManifest {
+    async fn get_journey_manifest(
+        &mut self,
+        taxi: &str,
+        journey_id: &Uuid,
+    ) -> sqlx::Result<Option<Manifest>>;
+}
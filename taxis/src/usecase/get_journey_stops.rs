@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::JourneyStop;
+
+#[async_trait]
+pub trait GetJourneyStops {
+    async fn get_journey_stops(&mut self, journey_id: &Uuid) -> sqlx::Result<Vec<JourneyStop>>;
+}
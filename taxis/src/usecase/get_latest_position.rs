@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::Position;
+
+#[async_trait]
+pub trait GetLatestPosition {
+    async fn get_latest_position(&mut self, journey_id: &Uuid) -> sqlx::Result<Option<Position>>;
+}
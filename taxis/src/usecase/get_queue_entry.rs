@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::QueueEntry;
+
+#[async_trait]
+pub trait GetQueueEntry {
+    /// The latest entry of the taxi in the queue of the rank.
+    async fn get_queue_entry(
+        &mut self,
+        rank_id: &Uuid,
+        taxi: &str,
+    ) -> sqlx::Result<Option<QueueEntry>>;
+}
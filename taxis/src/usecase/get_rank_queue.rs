@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::QueueEntry;
+
+#[async_trait]
+pub trait GetRankQueue {
+    /// The dispatched and waiting taxis of the rank, head first.
+    async fn get_rank_queue(&mut self, rank_id: &Uuid) -> sqlx::Result<Vec<QueueEntry>>;
+}
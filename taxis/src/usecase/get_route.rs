@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::Route;
+
+#[async_trait]
+pub trait GetRoute {
+    async fn get_route(&mut self, id: &Uuid) -> sqlx::Result<Option<Route>>;
+}
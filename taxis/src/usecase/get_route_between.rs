@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::Route;
+
+#[async_trait]
+pub trait GetRouteBetween {
+    async fn get_route_between(
+        &mut self,
+        origin: &Uuid,
+        destination: &Uuid,
+    ) -> sqlx::Result<Option<Route>>;
+}
@@ -0,0 +1,8 @@
+use async_trait::async_trait;
+
+use crate::entity::{Route, RouteFilter};
+
+#[async_trait]
+pub trait GetRoutes {
+    async fn get_routes(&mut self, filter: &RouteFilter) -> sqlx::Result<Vec<Route>>;
+}
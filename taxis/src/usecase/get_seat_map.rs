@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::{SeatMap, Segment};
+
+#[async_trait]
+pub trait GetSeatMap {
+    /// The seats taken on any leg of the segment, or of the whole journey.
+    async fn get_seat_map(
+        &mut self,
+        journey_id: &Uuid,
+        segment: Option<&Segment>,
+    ) -> sqlx::Result<Option<SeatMap>>;
+}
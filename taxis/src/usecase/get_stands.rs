@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::Stand;
+
+#[async_trait]
+pub trait GetStands {
+    /// The ranks among `ids`, in no particular order.
+    async fn get_stands(&mut self, ids: &[Uuid]) -> sqlx::Result<Vec<Stand>>;
+}
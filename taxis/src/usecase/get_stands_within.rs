@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+
+use crate::entity::Stand;
+use crate::geo::BoundingBox;
+
+#[async_trait]
+pub trait GetStandsWithin {
+    /// The located ranks inside the box.
+    async fn get_stands_within(&mut self, bounds: &BoundingBox) -> sqlx::Result<Vec<Stand>>;
+}
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::{TrackedJourney, Viewer};
+
+#[async_trait]
+pub trait GetTrackedJourney {
+    /// The journey, only when the viewer is one of its parties.
+    async fn get_tracked_journey(
+        &mut self,
+        journey_id: &Uuid,
+        viewer: &Viewer<'_>,
+    ) -> sqlx::Result<Option<TrackedJourney>>;
+}
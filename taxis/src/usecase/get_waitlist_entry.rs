@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::WaitlistEntry;
+
+#[async_trait]
+pub trait GetWaitlistEntry {
+    async fn get_waitlist_entry(&mut self, id: &Uuid) -> sqlx::Result<Option<WaitlistEntry>>;
+}
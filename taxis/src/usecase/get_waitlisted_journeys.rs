@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait GetWaitlistedJourneys {
+    async fn get_waitlisted_journeys(&mut self) -> sqlx::Result<Vec<Uuid>>;
+}
@@ -0,0 +1,8 @@
+use async_trait::async_trait;
+
+use crate::entity::{NewWaitlistEntry, WaitlistJoin};
+
+#[async_trait]
+pub trait JoinWaitlist {
+    async fn join_waitlist(&mut self, entry: &NewWaitlistEntry) -> sqlx::Result<WaitlistJoin>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::QueueExit;
+
+#[async_trait]
+pub trait LeaveRankQueue {
+    async fn leave_rank_queue(
+        &mut self,
+        rank_id: &Uuid,
+        taxi: &str,
+    ) -> sqlx::Result<Option<QueueExit>>;
+}
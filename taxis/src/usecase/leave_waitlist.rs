@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait LeaveWaitlist {
+    async fn leave_waitlist(&mut self, id: &Uuid) -> sqlx::Result<Option<Uuid>>;
+}
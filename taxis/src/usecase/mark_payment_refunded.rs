@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait MarkPaymentRefunded {
+    async fn mark_payment_refunded(&mut self, payment_id: &Uuid) -> sqlx::Result<()>;
+}
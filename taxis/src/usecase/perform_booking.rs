@@ -0,0 +1,8 @@
+use async_trait::async_trait;
+
+use crate::entity::{Booking, NewBooking};
+
+#[async_trait]
+pub trait PerformBooking {
+    async fn perform_booking(&mut self, booking: &NewBooking) -> sqlx::Result<Option<Booking>>;
+}
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use chrono::Duration;
+use uuid::Uuid;
+
+use crate::entity::WaitlistEntry;
+
+#[async_trait]
+pub trait PromoteWaitlist {
+    async fn promote_waitlist(
+        &mut self,
+        journey_id: &Uuid,
+        offer_ttl: Duration,
+    ) -> sqlx::Result<Vec<WaitlistEntry>>;
+}
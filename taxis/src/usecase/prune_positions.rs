@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+#[async_trait]
+pub trait PrunePositions {
+    async fn prune_positions(&mut self, before: DateTime<Utc>) -> sqlx::Result<u64>;
+}
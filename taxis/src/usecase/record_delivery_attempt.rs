@@ -0,0 +1,8 @@
+use async_trait::async_trait;
+
+use crate::entity::DeliveryAttempt;
+
+#[async_trait]
+pub trait RecordDeliveryAttempt {
+    async fn record_delivery_attempt(&mut self, attempt: &DeliveryAttempt<'_>) -> sqlx::Result<()>;
+}
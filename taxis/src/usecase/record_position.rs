@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+
+use crate::entity::Position;
+
+#[async_trait]
+pub trait RecordPosition {
+    /// Stores the position and drops the oldest ones beyond `max_points` for the journey.
+    async fn record_position(&mut self, position: &Position, max_points: i64) -> sqlx::Result<()>;
+}
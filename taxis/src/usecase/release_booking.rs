@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::BookingStatus;
+
+#[async_trait]
+pub trait ReleaseBooking {
+    async fn release_booking(&mut self, id: &Uuid, status: BookingStatus) -> sqlx::Result<bool>;
+}
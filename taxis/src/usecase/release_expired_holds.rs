@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait ReleaseExpiredHolds {
+    async fn release_expired_holds(&mut self) -> sqlx::Result<Vec<Uuid>>;
+}
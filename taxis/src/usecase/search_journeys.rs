@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::SearchedJourney;
+
+#[async_trait]
+pub trait SearchJourneys {
+    async fn search_journeys(
+        &mut self,
+        origin: &Uuid,
+        destination: &Uuid,
+    ) -> sqlx::Result<Vec<SearchedJourney>>;
+}
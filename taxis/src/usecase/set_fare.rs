@@ -0,0 +1,8 @@
+use async_trait::async_trait;
+
+use crate::entity::Fare;
+
+#[async_trait]
+pub trait SetFare {
+    async fn set_fare(&mut self, fare: &Fare) -> sqlx::Result<()>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use domain::entity::Price;
+use uuid::Uuid;
+
+#[async_trait]
+pub trait SetJourneyFare {
+    async fn set_journey_fare(
+        &mut self,
+        owner: &str,
+        journey_id: &Uuid,
+        fare: Option<&Price>,
+    ) -> sqlx::Result<bool>;
+}
@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait SetOwnerPassword {
+    /// Replaces the password hash of the owner of a taxi, telling whether the owner exists.
+    async fn set_owner_password(&mut self, num: &str, password: &str) -> sqlx::Result<bool>;
+}
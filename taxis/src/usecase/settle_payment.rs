@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+
+use crate::entity::Settlement;
+
+#[async_trait]
+pub trait SettlePayment {
+    async fn settle_payment(
+        &mut self,
+        provider: &str,
+        reference: &str,
+        succeeded: bool,
+        reason: Option<&str>,
+    ) -> sqlx::Result<Settlement>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::{Route, RouteChanges};
+
+#[async_trait]
+pub trait UpdateRoute {
+    async fn update_route(
+        &mut self,
+        id: &Uuid,
+        changes: &RouteChanges,
+    ) -> sqlx::Result<Option<Route>>;
+}
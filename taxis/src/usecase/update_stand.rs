@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::entity::{StandChanges, StandUpdate};
+
+#[async_trait]
+pub trait UpdateStand {
+    async fn update_stand(
+        &mut self,
+        id: &Uuid,
+        changes: &StandChanges,
+    ) -> sqlx::Result<StandUpdate>;
+}
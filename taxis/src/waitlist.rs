@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use log::{error, info};
+use rocket::fairing::AdHoc;
+use rocket::tokio::{self, time};
+use rocket_db_pools::Database;
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use database::TXpressDB;
+
+use crate::data::WaitlistDataProvider;
+use crate::events::EventBus;
+use crate::usecase::{ExpireWaitlistOffers, GetWaitlistedJourneys, PromoteWaitlist};
+
+const TARGET: &'static str = "waitlist";
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct WaitlistConfig {
+    pub offer_minutes: i64,
+    pub sweep_seconds: u64,
+}
+
+impl Default for WaitlistConfig {
+    fn default() -> Self {
+        WaitlistConfig {
+            offer_minutes: 15,
+            sweep_seconds: 30,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Waitlist {
+    pub config: WaitlistConfig,
+}
+
+impl Waitlist {
+    pub fn offer_ttl(&self) -> chrono::Duration {
+        chrono::Duration::minutes(self.config.offer_minutes)
+    }
+
+    /// Offers the free seats of a journey to the passengers waiting for it, telling whether any
+    /// offer was made.
+    pub async fn promote(
+        &self,
+        data_provider: &mut WaitlistDataProvider,
+        journey_id: &Uuid,
+    ) -> bool {
+        match data_provider
+            .promote_waitlist(journey_id, self.offer_ttl())
+            .await
+        {
+            Err(err) => {
+                error!(target: TARGET, "unable to promote `{journey_id}`\n{err:?}");
+                false
+            }
+            Ok(offers) if !offers.is_empty() => {
+                info!(target: TARGET, "{} offer(s) made on `{journey_id}`", offers.len());
+                true
+            }
+            Ok(_) => false,
+        }
+    }
+}
+
+pub fn stage() -> AdHoc {
+    AdHoc::try_on_ignite("Waitlist", |rocket| async {
+        let config = match rocket.figment().extract_inner::<WaitlistConfig>("waitlist") {
+            Ok(config) => config,
+            Err(err) if err.missing() => WaitlistConfig::default(),
+            Err(err) => {
+                error!(target: TARGET, "invalid waitlist configuration\n{err:?}");
+                return Err(rocket);
+            }
+        };
+
+        Ok(rocket.manage(Waitlist { config }).attach(AdHoc::on_liftoff(
+            "Waitlist worker",
+            |rocket| {
+                Box::pin(async move {
+                    let Some(db) = TXpressDB::fetch(rocket) else {
+                        return;
+                    };
+                    let waitlist = rocket.state::<Waitlist>().unwrap().clone();
+                    let bus = rocket.state::<EventBus>().cloned();
+
+                    tokio::spawn(sweep(PgPool::clone(db), waitlist, bus));
+                })
+            },
+        )))
+    })
+}
+
+/// Expires unclaimed offers, then hands the seats freed since the last pass (expired offers,
+/// failed payments, released holds) to the next passengers in line.
+async fn sweep(pool: PgPool, waitlist: Waitlist, bus: Option<EventBus>) {
+    let mut interval = time::interval(Duration::from_secs(waitlist.config.sweep_seconds.max(1)));
+    loop {
+        interval.tick().await;
+
+        let conn = match pool.acquire().await {
+            Ok(conn) => conn,
+            Err(err) => {
+                error!(target: TARGET, "{err:?}");
+                continue;
+            }
+        };
+        let mut data_provider = WaitlistDataProvider::new(conn);
+
+        match (data_provider.expire_waitlist_offers().await, &bus) {
+            (Err(err), _) => error!(target: TARGET, "unable to expire offers\n{err:?}"),
+            (Ok(journeys), Some(bus)) => {
+                for journey_id in &journeys {
+                    bus.journey_changed(journey_id, "waitlist_offer_expired");
+                }
+            }
+            (Ok(_), None) => {}
+        }
+
+        let journeys = match data_provider.get_waitlisted_journeys().await {
+            Ok(journeys) => journeys,
+            Err(err) => {
+                error!(target: TARGET, "{err:?}");
+                continue;
+            }
+        };
+
+        for journey_id in journeys {
+            let offered = waitlist.promote(&mut data_provider, &journey_id).await;
+            if let (true, Some(bus)) = (offered, &bus) {
+                bus.journey_changed(&journey_id, "waitlist_offer");
+            }
+        }
+    }
+}
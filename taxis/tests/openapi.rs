@@ -0,0 +1,143 @@
+use std::collections::BTreeSet;
+
+use serde_json::Value;
+
+/// The `{param}` template of a Rocket path, whose dynamic segments are `<param>` or `<param..>`.
+fn template(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix('<') {
+            Some(param) => format!("{{{}}}", param.trim_end_matches('>').trim_end_matches("..")),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn spec() -> Value {
+    serde_json::to_value(taxis::openapi()).unwrap()
+}
+
+#[test]
+fn documents_every_route() {
+    let mounted = taxis::routes()
+        .iter()
+        .map(|route| {
+            (
+                route.method.as_str().to_lowercase(),
+                template(&route.uri.path().to_string()),
+            )
+        })
+        .collect::<BTreeSet<_>>();
+
+    let spec = spec();
+    let documented = spec["paths"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .flat_map(|(path, item)| {
+            item.as_object()
+                .unwrap()
+                .keys()
+                .map(move |method| (method.clone(), path.clone()))
+        })
+        .collect::<BTreeSet<_>>();
+
+    let undocumented = mounted.difference(&documented).collect::<Vec<_>>();
+    let unknown = documented.difference(&mounted).collect::<Vec<_>>();
+    assert!(
+        undocumented.is_empty(),
+        "undocumented routes: {undocumented:?}"
+    );
+    assert!(
+        unknown.is_empty(),
+        "documented routes not mounted: {unknown:?}"
+    );
+}
+
+#[test]
+fn resolves_every_schema() {
+    fn refs<'a>(value: &'a Value, found: &mut BTreeSet<&'a str>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(reference)) = map.get("$ref") {
+                    found.insert(reference);
+                }
+                map.values().for_each(|v| refs(v, found));
+            }
+            Value::Array(values) => values.iter().for_each(|v| refs(v, found)),
+            _ => {}
+        }
+    }
+
+    let spec = spec();
+    let mut found = BTreeSet::new();
+    refs(&spec, &mut found);
+
+    let schemas = spec["components"]["schemas"].as_object().unwrap();
+    let missing = found
+        .into_iter()
+        .filter(|r| {
+            r.strip_prefix("#/components/schemas/")
+                .is_none_or(|name| !schemas.contains_key(name))
+        })
+        .collect::<Vec<_>>();
+    assert!(missing.is_empty(), "unresolved schemas: {missing:?}");
+}
+
+#[test]
+fn documents_every_query_parameter() {
+    let spec = spec();
+    let mut mismatches = vec![];
+
+    for route in taxis::routes() {
+        // Only single parameters are named in the path, those spreading over a struct (`<param..>`)
+        // are not, so their fields are only checked to be described.
+        let params = route
+            .uri
+            .query()
+            .map(|query| {
+                query
+                    .split('&')
+                    .filter_map(|segment| segment.strip_prefix('<')?.strip_suffix('>'))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let spread = params.iter().any(|param| param.ends_with(".."));
+        let mounted = params
+            .into_iter()
+            .filter(|param| !param.ends_with(".."))
+            .map(String::from)
+            .collect::<BTreeSet<_>>();
+
+        let method = route.method.as_str().to_lowercase();
+        let path = template(&route.uri.path().to_string());
+        // Parameters utoipa infers from the handler on its own come without a description.
+        let (documented, undocumented): (Vec<_>, Vec<_>) = spec["paths"][&path][&method]
+            ["parameters"]
+            .as_array()
+            .map(|params| {
+                params
+                    .iter()
+                    .filter(|p| p["in"] == "query")
+                    .partition(|p| p["description"].is_string())
+            })
+            .unwrap_or_default();
+        let documented = documented
+            .into_iter()
+            .map(|p| p["name"].as_str().unwrap().to_string())
+            .collect::<BTreeSet<_>>();
+
+        let matches = if spread {
+            undocumented.is_empty() && mounted.is_subset(&documented)
+        } else {
+            mounted == documented
+        };
+        if !matches {
+            mismatches.push(format!(
+                "{method} {path}: mounted {mounted:?}, described {documented:?}"
+            ));
+        }
+    }
+
+    assert!(mismatches.is_empty(), "query parameters: {mismatches:#?}");
+}
@@ -0,0 +1,107 @@
+use rocket::tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use rocket::tokio::net::TcpListener;
+use rocket::tokio::task::JoinHandle;
+
+use taxis::notification::{
+    Channel, Message, NotificationTransport, SmtpConfig, SmtpSecurity, SmtpTransport,
+    TransportError,
+};
+
+/// A server speaking just enough SMTP to take one message, without offering STARTTLS. Gives back
+/// the commands and the message it received.
+async fn fake_server() -> (u16, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = rocket::tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut received = vec![];
+
+        writer.write_all(b"220 fake ESMTP\r\n").await.unwrap();
+        let mut in_data = false;
+        while let Ok(Some(line)) = lines.next_line().await {
+            let reply: &[u8] = match line.as_str() {
+                "." if in_data => {
+                    in_data = false;
+                    b"250 queued\r\n"
+                }
+                _ if in_data => {
+                    received.push(line);
+                    continue;
+                }
+                _ if line.starts_with("EHLO") => b"250-fake\r\n250 8BITMIME\r\n",
+                "DATA" => {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                }
+                "QUIT" => {
+                    writer.write_all(b"221 bye\r\n").await.unwrap();
+                    received.push(line);
+                    break;
+                }
+                _ => b"250 ok\r\n",
+            };
+            received.push(line);
+            writer.write_all(reply).await.unwrap();
+        }
+
+        received
+    });
+
+    (port, server)
+}
+
+fn config(port: u16, security: SmtpSecurity) -> SmtpConfig {
+    SmtpConfig {
+        host: String::from("127.0.0.1"),
+        port,
+        security,
+        ..SmtpConfig::default()
+    }
+}
+
+fn message() -> Message<'static> {
+    Message {
+        channel: Channel::Email,
+        to: "Passenger <passenger@example.com>",
+        subject: Some("Your journey"),
+        body: "Your taxi leaves in 30 minutes.",
+    }
+}
+
+#[test]
+fn requires_starttls_by_default() {
+    assert_eq!(SmtpConfig::default().security, SmtpSecurity::Starttls);
+}
+
+#[rocket::async_test]
+async fn delivers_in_clear_when_asked_to() {
+    let (port, server) = fake_server().await;
+    let transport = SmtpTransport::new(&config(port, SmtpSecurity::Plaintext)).unwrap();
+
+    transport.send(&message()).await.unwrap();
+    drop(transport);
+
+    let received = server.await.unwrap();
+    assert!(received.contains(&String::from("MAIL FROM:<no-reply@txpress.local>")));
+    assert!(received.contains(&String::from("RCPT TO:<passenger@example.com>")));
+    assert!(received.contains(&String::from("Subject: Your journey")));
+    assert!(received.contains(&String::from("Your taxi leaves in 30 minutes.")));
+}
+
+#[rocket::async_test]
+async fn refuses_a_server_without_starttls() {
+    let (port, server) = fake_server().await;
+    let transport = SmtpTransport::new(&config(port, SmtpSecurity::Starttls)).unwrap();
+
+    let result = transport.send(&message()).await;
+    assert!(matches!(result, Err(TransportError::Failed(_))));
+    drop(transport);
+
+    let received = server.await.unwrap();
+    assert!(received.iter().any(|line| line.starts_with("EHLO")));
+    assert!(!received.iter().any(|line| line.starts_with("MAIL FROM")));
+    assert!(!received.iter().any(|line| line.starts_with("DATA")));
+}